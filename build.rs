@@ -7,9 +7,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     println!("cargo:rerun-if-changed=src/api/queries/schema.graphql");
 
     // Check if graphql_client_cli is installed
-    let check_graphql_cli = Command::new("cargo")
-        .args(&["install", "--list"])
-        .output()?;
+    let check_graphql_cli = Command::new("cargo").args(["install", "--list"]).output()?;
 
     let output = String::from_utf8(check_graphql_cli.stdout)?;
 
@@ -18,7 +16,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 
         // Install graphql_client_cli
         let install_result = Command::new("cargo")
-            .args(&["install", "graphql_client_cli"])
+            .args(["install", "graphql_client_cli"])
             .status()?;
 
         if !install_result.success() {
@@ -12,8 +12,8 @@ use std::sync::{Arc, Mutex};
 use tokio::sync::oneshot;
 use url::Url;
 
+use crate::api::transport::{HttpTransport, Transport, TransportRequest};
 use crate::data::database::Database;
-use crate::utils::config;
 use crate::utils::error::AppError;
 
 // Re-use the AuthConfig from utils to avoid duplication
@@ -31,8 +31,8 @@ pub struct AuthToken {
 #[derive(Debug, Clone)]
 pub struct AuthManager {
     client: BasicClient,
-    config: AuthConfig,
     db: Arc<Mutex<Database>>,
+    transport: Arc<dyn Transport>,
 }
 
 impl AuthToken {
@@ -68,11 +68,20 @@ impl AuthManager {
 
         Self {
             client,
-            config,
             db: Arc::new(Mutex::new(db)),
+            transport: Arc::new(HttpTransport::default()),
         }
     }
 
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        self.transport.clone()
+    }
+
     pub async fn authenticate(&self) -> Result<AuthToken, AppError> {
         // Create oneshot channel for the auth callback
         let (tx, rx) = oneshot::channel();
@@ -337,25 +346,23 @@ impl AuthManager {
 
     // Helper function to get the user ID for the authenticated user
     async fn get_user_id(&self, access_token: &str) -> Result<i32, AppError> {
-        let client = reqwest::Client::new();
-        let response = client
-            .post("https://graphql.anilist.co")
-            .header("Authorization", format!("Bearer {}", access_token))
-            .json(&serde_json::json!({
-                "query": "query { Viewer { id } }"
-            }))
-            .send()
-            .await
-            .map_err(|e| AppError::NetworkError(format!("Network error: {}", e)))?;
-
-        let status = response.status();
+        let response = self
+            .transport
+            .send(TransportRequest {
+                body: serde_json::json!({
+                    "operationName": "ViewerId",
+                    "query": "query ViewerId { Viewer { id } }"
+                }),
+                bearer_token: Some(access_token.to_string()),
+            })
+            .await?;
+
+        let status = response.status;
         if !status.is_success() {
             return Err(AppError::ApiError(format!("API error: HTTP {}", status)));
         }
 
-        let json: serde_json::Value = response
-            .json()
-            .await
+        let json: serde_json::Value = serde_json::from_slice(&response.body)
             .map_err(|e| AppError::ApiError(format!("Failed to parse response: {}", e)))?;
 
         // Check for GraphQL errors
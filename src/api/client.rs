@@ -1,8 +1,19 @@
 use crate::api::auth::{AuthManager, AuthToken};
+use crate::api::models::{
+    Activity, ActivityKind, ActivityQuery, ActivityReply, FavouriteTarget, FollowList, FollowUser,
+    FuzzyDate, LikeTarget, MediaFormat, MediaSeason, MediaSort, MediaStatus, MediaTitle, MediaType,
+    Notification, NotificationType, ScoreFormat, SearchFilters, TitleLanguage, ViewerPreferences,
+};
+use crate::api::rate_limit::{RateLimitHeaders, RateLimiter, RetryPolicy};
+use crate::api::transport::{HttpTransport, Transport, TransportRequest};
+use crate::data::database::ListUpdate;
+use crate::data::outbox::parse_status;
 use crate::utils::error::AppError;
+use crate::utils::text::plain_description;
 use graphql_client::{GraphQLQuery, Response};
-use reqwest::{Client, StatusCode};
-use serde::{Deserialize, Serialize};
+use log::warn;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
 use std::sync::{Arc, Mutex};
 
 // -----------------------------------------------------------------------------
@@ -57,21 +68,310 @@ pub struct Viewer;
 )]
 pub struct UpdateMediaList;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/update_media_list_entries.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct UpdateMediaListEntries;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/seasonal_anime.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct SeasonalAnime;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/airing_schedule.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct AiringSchedule;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/delete_media_list_entry.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct DeleteMediaListEntry;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/toggle_favourite.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct ToggleFavourite;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/character_details.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct CharacterDetails;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/staff_details.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct StaffDetails;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/studio_details.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct StudioDetails;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/media_relations.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct MediaRelations;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/media_by_mal_id.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct MediaByMalId;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/notifications.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct Notifications;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/unread_notification_count.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct UnreadNotificationCount;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/activity_feed.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct ActivityFeed;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/activity_replies.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct ActivityReplies;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/save_text_activity.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct SaveTextActivity;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/save_activity_reply.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct SaveActivityReply;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/toggle_like.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct ToggleLike;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/toggle_follow.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct ToggleFollow;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/api/queries/schema.graphql",
+    query_path = "src/api/queries/user_follows.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct UserFollows;
+
+// AniList custom scalars used by query variables
+pub type FuzzyDateInt = i64;
+pub type CountryCode = String;
+pub type Json = serde_json::Value;
+
+// Each generated query module carries its own copy of the schema enums
+macro_rules! impl_enum_conversion {
+    ($enum:ident $variants:tt => $($module:ident),+) => {
+        $(
+            impl_enum_conversion!(@impl $enum $variants $module);
+        )+
+    };
+    (@impl $enum:ident { $($variant:ident),* } $module:ident) => {
+        impl From<$enum> for $module::$enum {
+            fn from(value: $enum) -> Self {
+                match value {
+                    $($enum::$variant => $module::$enum::$variant,)*
+                }
+            }
+        }
+    };
+}
+
+impl_enum_conversion!(
+    MediaType { ANIME, MANGA } => anime_details, anime_search, media_by_mal_id, user_anime_list
+);
+impl_enum_conversion!(
+    NotificationType {
+        AIRING,
+        FOLLOWING,
+        ACTIVITY_MESSAGE,
+        ACTIVITY_MENTION,
+        ACTIVITY_REPLY,
+        ACTIVITY_REPLY_SUBSCRIBED,
+        ACTIVITY_LIKE,
+        ACTIVITY_REPLY_LIKE,
+        RELATED_MEDIA_ADDITION,
+        MEDIA_DATA_CHANGE,
+        MEDIA_MERGE,
+        MEDIA_DELETION
+    } => notifications
+);
+impl_enum_conversion!(MediaSeason { WINTER, SPRING, SUMMER, FALL } => anime_search, seasonal_anime);
+impl_enum_conversion!(
+    MediaFormat { TV, TV_SHORT, MOVIE, SPECIAL, OVA, ONA, MUSIC, MANGA, NOVEL, ONE_SHOT }
+        => anime_search, seasonal_anime
+);
+impl_enum_conversion!(
+    MediaStatus { FINISHED, RELEASING, NOT_YET_RELEASED, CANCELLED, HIATUS } => anime_search
+);
+impl_enum_conversion!(
+    MediaSort {
+        SEARCH_MATCH,
+        POPULARITY_DESC,
+        SCORE_DESC,
+        TRENDING_DESC,
+        FAVOURITES_DESC,
+        START_DATE,
+        START_DATE_DESC,
+        TITLE_ROMAJI
+    } => anime_search, seasonal_anime
+);
+
+// Every query selects the same title fields, so each generated title type
+// converts to the shared model and is resolved with `MediaTitle::preferred`
+macro_rules! impl_title_conversion {
+    ($($title:ty),+ $(,)?) => {
+        $(
+            impl From<$title> for MediaTitle {
+                fn from(title: $title) -> Self {
+                    MediaTitle {
+                        romaji: title.romaji,
+                        english: title.english,
+                        native: title.native,
+                        romaji_stylised: title.romaji_stylised,
+                        english_stylised: title.english_stylised,
+                        native_stylised: title.native_stylised,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_title_conversion!(
+    activity_feed::ActivityFeedPageActivitiesOnListActivityMediaTitle,
+    airing_schedule::AiringSchedulePageAiringSchedulesMediaTitle,
+    anime_details::AnimeDetailsMediaTitle,
+    anime_details::AnimeDetailsMediaRecommendationsNodesMediaRecommendationTitle,
+    anime_details::AnimeDetailsMediaRelationsEdgesNodeTitle,
+    anime_search::AnimeSearchPageMediaTitle,
+    character_details::CharacterDetailsCharacterMediaEdgesNodeTitle,
+    media_by_mal_id::MediaByMalIdPageMediaTitle,
+    media_relations::MediaRelationsMediaTitle,
+    notifications::NotificationMediaTitle,
+    seasonal_anime::SeasonalAnimePageMediaTitle,
+    staff_details::StaffDetailsStaffCharacterMediaEdgesNodeTitle,
+    staff_details::StaffDetailsStaffStaffMediaEdgesNodeTitle,
+    studio_details::StudioDetailsStudioMediaEdgesNodeTitle,
+    user_anime_list::UserAnimeListMediaListCollectionListsEntriesMediaTitle,
+    user_profile::UserProfileUserFavouritesAnimeNodesTitle,
+    user_profile::UserProfileUserFavouritesMangaNodesTitle,
+);
+
+// Replies read from a thread and the one returned after posting
+macro_rules! impl_reply_conversion {
+    ($($reply:ty),+ $(,)?) => {
+        $(
+            impl From<$reply> for ActivityReply {
+                fn from(reply: $reply) -> Self {
+                    ActivityReply {
+                        id: reply.id as i32,
+                        user: reply.user.map(|u| u.name).unwrap_or_default(),
+                        text: reply.text.as_deref().map(plain_description).unwrap_or_default(),
+                        created_at: reply.created_at,
+                        like_count: reply.like_count as i32,
+                        is_liked: reply.is_liked.unwrap_or(false),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_reply_conversion!(
+    activity_replies::Reply,
+    save_activity_reply::SaveActivityReplySaveActivityReply,
+);
+
 #[derive(Debug, Clone)]
 pub struct AniListClient {
-    client: Client,
-    endpoint: String,
+    transport: Arc<dyn Transport>,
     auth_manager: Option<Arc<AuthManager>>,
     auth_token: Arc<Mutex<Option<AuthToken>>>,
+    rate_limiter: Arc<RateLimiter>,
+    retry_policy: RetryPolicy,
+}
+
+impl Default for AniListClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AniListClient {
     pub fn new() -> Self {
         Self {
-            client: Client::new(),
-            endpoint: "https://graphql.anilist.co".to_string(),
+            transport: Arc::new(HttpTransport::default()),
             auth_manager: None,
             auth_token: Arc::new(Mutex::new(None)),
+            rate_limiter: Arc::new(RateLimiter::default()),
+            retry_policy: RetryPolicy::default(),
         }
     }
 
@@ -93,10 +393,26 @@ impl AniListClient {
 
     pub fn with_auth_manager(auth_manager: AuthManager) -> Self {
         let mut client = Self::new();
+        // Share the auth manager's transport so both talk to the same server
+        client.transport = auth_manager.transport();
         client.auth_manager = Some(Arc::new(auth_manager));
         client
     }
 
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    pub fn transport(&self) -> Arc<dyn Transport> {
+        self.transport.clone()
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     pub async fn get_current_token(&self) -> Result<Option<String>, AppError> {
         // First check if we have a token in memory
         let token_option = {
@@ -150,16 +466,84 @@ impl AniListClient {
     where
         Q: GraphQLQuery,
     {
-        let request_body = Q::build_query(variables);
-        let mut request_builder = self.client.post(&self.endpoint).json(&request_body);
+        self.execute(serde_json::to_value(Q::build_query(variables))?)
+            .await
+    }
 
-        // Add auth token if available
-        if let Ok(Some(token)) = self.get_current_token().await {
-            request_builder = request_builder.header("Authorization", format!("Bearer {}", token));
-        }
+    // For mutations that flip or append state (toggles, posts). A failed
+    // request may still have been applied, so only a 429 is retried.
+    async fn execute_once<Q>(&self, variables: Q::Variables) -> Result<Q::ResponseData, AppError>
+    where
+        Q: GraphQLQuery,
+    {
+        self.send(serde_json::to_value(Q::build_query(variables))?, false)
+            .await
+    }
+
+    // Send a request body and decode its data, for documents that are built at
+    // runtime as well as the generated queries
+    async fn execute<T: DeserializeOwned>(&self, body: serde_json::Value) -> Result<T, AppError> {
+        self.send(body, true).await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        body: serde_json::Value,
+        repeatable: bool,
+    ) -> Result<T, AppError> {
+        let request = TransportRequest {
+            body,
+            // Add auth token if available
+            bearer_token: self.get_current_token().await.ok().flatten(),
+        };
+        let mut attempt = 0;
+
+        let response = loop {
+            // Wait for a free slot in the rate limit budget
+            self.rate_limiter.acquire().await;
+
+            let response = match self.transport.send(request.clone()).await {
+                Ok(response) => response,
+                Err(e @ AppError::NetworkError(_))
+                    if repeatable && attempt < self.retry_policy.max_retries =>
+                {
+                    let delay = self.retry_policy.backoff(attempt);
+                    warn!("Request failed ({}), retrying in {:?}", e, delay);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let status = response.status;
+            let rate_limit = RateLimitHeaders::from_headers(&response.headers);
+            self.rate_limiter.update(&rate_limit).await;
+
+            // A 429 was turned away before AniList did anything, so it is always safe
+            let retryable =
+                status == StatusCode::TOO_MANY_REQUESTS || (repeatable && status.is_server_error());
+            if !retryable || attempt >= self.retry_policy.max_retries {
+                break response;
+            }
+
+            if rate_limit.retry_after.is_some() {
+                // The limiter already holds every request back until the server's delay passes
+                warn!("API returned {}, retrying after server delay", status);
+            } else {
+                let delay = self.retry_policy.backoff(attempt);
+                warn!("API returned {}, retrying in {:?}", status, delay);
+
+                if status == StatusCode::TOO_MANY_REQUESTS {
+                    self.rate_limiter.block(delay).await;
+                } else {
+                    tokio::time::sleep(delay).await;
+                }
+            }
+            attempt += 1;
+        };
 
-        let response = request_builder.send().await?;
-        let status = response.status();
+        let status = response.status;
 
         // Handle rate limiting
         if status == StatusCode::TOO_MANY_REQUESTS {
@@ -176,7 +560,7 @@ impl AniListClient {
             )));
         }
 
-        let response_body: Response<Q::ResponseData> = response.json().await?;
+        let response_body: Response<T> = serde_json::from_slice(&response.body)?;
 
         // Check for GraphQL errors
         if let Some(errors) = response_body.errors {
@@ -199,89 +583,872 @@ impl AniListClient {
     pub async fn get_anime_details(
         &self,
         id: i32,
+    ) -> Result<anime_details::ResponseData, AppError> {
+        self.get_media_details(id, MediaType::ANIME).await
+    }
+
+    pub async fn get_manga_details(
+        &self,
+        id: i32,
+    ) -> Result<anime_details::ResponseData, AppError> {
+        self.get_media_details(id, MediaType::MANGA).await
+    }
+
+    pub async fn get_media_details(
+        &self,
+        id: i32,
+        media_type: MediaType,
     ) -> Result<anime_details::ResponseData, AppError> {
         let variables = anime_details::Variables {
             id: Some(id.into()),
+            type_: Some(media_type.into()),
         };
         self.execute_query::<AnimeDetails>(variables).await
     }
 
-    pub async fn search_anime(
+    /// A media with the ids of everything related to it, one step of a
+    /// franchise walk
+    pub async fn get_media_relations(
         &self,
-        search: String,
+        id: i32,
+    ) -> Result<media_relations::ResponseData, AppError> {
+        let variables = media_relations::Variables {
+            id: Some(id.into()),
+        };
+        self.execute_query::<MediaRelations>(variables).await
+    }
+
+    /// A character with one page of the media they appear in
+    pub async fn get_character(
+        &self,
+        id: i32,
         page: Option<i32>,
         per_page: Option<i32>,
-    ) -> Result<anime_search::ResponseData, AppError> {
-        let variables = anime_search::Variables {
-            search: Some(search),
+    ) -> Result<character_details::ResponseData, AppError> {
+        let variables = character_details::Variables {
+            id: Some(id.into()),
             page: page.map(|p| p.into()),
             per_page: per_page.map(|pp| pp.into()),
         };
-        self.execute_query::<AnimeSearch>(variables).await
+        self.execute_query::<CharacterDetails>(variables).await
     }
 
-    pub async fn get_user_anime_list(
+    /// A staff member with one page each of their voice and production roles
+    pub async fn get_staff(
         &self,
-        user_id: i32,
-        status: Option<user_anime_list::MediaListStatus>,
-    ) -> Result<user_anime_list::ResponseData, AppError> {
-        let variables = user_anime_list::Variables {
-            user_id: Some(user_id.into()),
-            status,
+        id: i32,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> Result<staff_details::ResponseData, AppError> {
+        let variables = staff_details::Variables {
+            id: Some(id.into()),
+            page: page.map(|p| p.into()),
+            per_page: per_page.map(|pp| pp.into()),
         };
-        self.execute_query::<UserAnimeList>(variables).await
+        self.execute_query::<StaffDetails>(variables).await
     }
 
-    pub async fn get_user_profile(
+    /// A studio with one page of its productions, newest first
+    pub async fn get_studio(
         &self,
-        name: String,
-    ) -> Result<user_profile::ResponseData, AppError> {
-        let variables = user_profile::Variables { name: Some(name) };
-        self.execute_query::<UserProfile>(variables).await
+        id: i32,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> Result<studio_details::ResponseData, AppError> {
+        let variables = studio_details::Variables {
+            id: Some(id.into()),
+            page: page.map(|p| p.into()),
+            per_page: per_page.map(|pp| pp.into()),
+        };
+        self.execute_query::<StudioDetails>(variables).await
     }
 
-    pub async fn update_media_list(
+    pub async fn search_anime(
         &self,
-        id: Option<i32>,
-        media_id: Option<i32>,
-        status: Option<update_media_list::MediaListStatus>,
-        score: Option<f64>,
-        progress: Option<i32>,
-    ) -> Result<update_media_list::ResponseData, AppError> {
-        // This mutation requires authentication
-        if let Ok(None) = self.get_current_token().await {
-            return Err(AppError::ApiError(
-                "Authentication required for this operation".into(),
-            ));
-        }
-
-        let variables = update_media_list::Variables {
-            id: id.map(|i| i.into()),
-            media_id: media_id.map(|i| i.into()),
-            status,
-            score,
-            progress: progress.map(|i| i.into()),
-        };
-        self.execute_query::<UpdateMediaList>(variables).await
+        search: String,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> Result<anime_search::ResponseData, AppError> {
+        self.search_media(search, MediaType::ANIME, page, per_page)
+            .await
     }
 
-    pub async fn get_viewer(&self) -> Result<viewer::ResponseData, AppError> {
-        // This query requires authentication
-        if let Ok(None) = self.get_current_token().await {
-            return Err(AppError::ApiError(
-                "Authentication required for this operation".into(),
-            ));
-        }
-
-        let variables = viewer::Variables {};
-        self.execute_query::<Viewer>(variables).await
+    pub async fn search_manga(
+        &self,
+        search: String,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> Result<anime_search::ResponseData, AppError> {
+        self.search_media(search, MediaType::MANGA, page, per_page)
+            .await
     }
 
-    pub async fn is_authenticated(&self) -> bool {
-        match self.get_current_token().await {
-            Ok(Some(_)) => true,
-            _ => false,
+    pub async fn search_media(
+        &self,
+        search: String,
+        media_type: MediaType,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> Result<anime_search::ResponseData, AppError> {
+        let filters = SearchFilters {
+            query: search,
+            ..Default::default()
+        };
+        self.search_media_filtered(&filters, media_type, page, per_page)
+            .await
+    }
+
+    /// Search with every `Page.media` filter the UI exposes. The query text is
+    /// optional, so an empty query with filters browses the catalogue.
+    pub async fn search_media_filtered(
+        &self,
+        filters: &SearchFilters,
+        media_type: MediaType,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> Result<anime_search::ResponseData, AppError> {
+        let query = filters.query.trim();
+        let strings = |values: &[String]| {
+            if values.is_empty() {
+                None
+            } else {
+                Some(values.iter().cloned().map(Some).collect())
+            }
+        };
+
+        // AniList's range arguments are exclusive, the filters are inclusive
+        let (length_min, length_max) = (
+            filters.length_min.map(|n| (n - 1).into()),
+            filters.length_max.map(|n| (n + 1).into()),
+        );
+        let is_manga = media_type == MediaType::MANGA;
+
+        // Sorting by relevance needs a query to match against
+        let sort = match filters.sort {
+            Some(MediaSort::SEARCH_MATCH) if query.is_empty() => MediaSort::POPULARITY_DESC,
+            Some(sort) => sort,
+            None => MediaSort::POPULARITY_DESC,
+        };
+
+        let variables = anime_search::Variables {
+            search: (!query.is_empty()).then(|| query.to_string()),
+            type_: Some(media_type.into()),
+            genres: strings(&filters.genres),
+            excluded_genres: strings(&filters.excluded_genres),
+            tags: strings(&filters.tags),
+            // Fuzzy dates are YYYYMMDD, so whole years fit between these bounds
+            start_date_greater: filters.year_from.map(|y| y as FuzzyDateInt * 10000),
+            start_date_lesser: filters.year_to.map(|y| (y as FuzzyDateInt + 1) * 10000),
+            season: filters.season.map(Into::into),
+            formats: if filters.formats.is_empty() {
+                None
+            } else {
+                Some(filters.formats.iter().map(|f| Some((*f).into())).collect())
+            },
+            status: filters.status.map(Into::into),
+            average_score_greater: filters.score_min.map(|s| (s - 1).into()),
+            average_score_lesser: filters.score_max.map(|s| (s + 1).into()),
+            episodes_greater: if is_manga { None } else { length_min },
+            episodes_lesser: if is_manga { None } else { length_max },
+            chapters_greater: if is_manga { length_min } else { None },
+            chapters_lesser: if is_manga { length_max } else { None },
+            country_of_origin: filters.country.clone(),
+            on_list: filters.on_list,
+            is_adult: if filters.include_adult {
+                None
+            } else {
+                Some(false)
+            },
+            sort: Some(vec![Some(sort.into())]),
+            page: page.map(|p| p.into()),
+            per_page: per_page.map(|pp| pp.into()),
+        };
+        self.execute_query::<AnimeSearch>(variables).await
+    }
+
+    /// Anime airing in the given season; an empty `formats` means all formats
+    pub async fn get_seasonal_anime(
+        &self,
+        season: MediaSeason,
+        season_year: i32,
+        formats: Vec<MediaFormat>,
+        sort: MediaSort,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> Result<seasonal_anime::ResponseData, AppError> {
+        let variables = seasonal_anime::Variables {
+            season: Some(season.into()),
+            season_year: Some(season_year.into()),
+            formats: if formats.is_empty() {
+                None
+            } else {
+                Some(formats.into_iter().map(|f| Some(f.into())).collect())
+            },
+            sort: Some(vec![Some(sort.into())]),
+            page: page.map(|p| p.into()),
+            per_page: per_page.map(|pp| pp.into()),
+        };
+        self.execute_query::<SeasonalAnime>(variables).await
+    }
+
+    /// Episodes airing between two unix timestamps, ordered by airing time
+    pub async fn get_airing_schedule(
+        &self,
+        airing_after: i64,
+        airing_before: i64,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> Result<airing_schedule::ResponseData, AppError> {
+        let variables = airing_schedule::Variables {
+            airing_at_greater: Some(airing_after),
+            airing_at_lesser: Some(airing_before),
+            page: page.map(|p| p.into()),
+            per_page: per_page.map(|pp| pp.into()),
+        };
+        self.execute_query::<AiringSchedule>(variables).await
+    }
+
+    pub async fn get_user_anime_list(
+        &self,
+        user_id: i32,
+        status: Option<user_anime_list::MediaListStatus>,
+    ) -> Result<user_anime_list::ResponseData, AppError> {
+        self.get_user_media_list(user_id, MediaType::ANIME, status)
+            .await
+    }
+
+    pub async fn get_user_manga_list(
+        &self,
+        user_id: i32,
+        status: Option<user_anime_list::MediaListStatus>,
+    ) -> Result<user_anime_list::ResponseData, AppError> {
+        self.get_user_media_list(user_id, MediaType::MANGA, status)
+            .await
+    }
+
+    pub async fn get_user_media_list(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+        status: Option<user_anime_list::MediaListStatus>,
+    ) -> Result<user_anime_list::ResponseData, AppError> {
+        let variables = user_anime_list::Variables {
+            user_id: Some(user_id.into()),
+            type_: Some(media_type.into()),
+            status,
+        };
+        self.execute_query::<UserAnimeList>(variables).await
+    }
+
+    /// `(MyAnimeList id, AniList id, title)` for each of the MyAnimeList ids
+    /// AniList knows, 50 ids per request
+    pub async fn get_media_by_mal_ids(
+        &self,
+        ids: &[i32],
+        media_type: MediaType,
+    ) -> Result<Vec<(i32, i32, MediaTitle)>, AppError> {
+        let mut found = Vec::new();
+
+        for chunk in ids.chunks(50) {
+            let variables = media_by_mal_id::Variables {
+                ids: Some(chunk.iter().map(|id| Some((*id).into())).collect()),
+                type_: Some(media_type.into()),
+                per_page: Some(50),
+            };
+            let media = self
+                .execute_query::<MediaByMalId>(variables)
+                .await?
+                .page
+                .and_then(|page| page.media)
+                .unwrap_or_default();
+
+            found.extend(media.into_iter().flatten().filter_map(|media| {
+                Some((
+                    media.id_mal? as i32,
+                    media.id as i32,
+                    media.title.map(MediaTitle::from).unwrap_or_default(),
+                ))
+            }));
+        }
+
+        Ok(found)
+    }
+
+    pub async fn get_user_profile(
+        &self,
+        name: String,
+    ) -> Result<user_profile::ResponseData, AppError> {
+        let variables = user_profile::Variables { name: Some(name) };
+        self.execute_query::<UserProfile>(variables).await
+    }
+
+    pub async fn update_media_list(
+        &self,
+        id: Option<i32>,
+        media_id: Option<i32>,
+        status: Option<update_media_list::MediaListStatus>,
+        score: Option<f64>,
+        progress: Option<i32>,
+        progress_volumes: Option<i32>,
+    ) -> Result<update_media_list::ResponseData, AppError> {
+        let variables = update_media_list::Variables {
+            id: id.map(|i| i.into()),
+            media_id: media_id.map(|i| i.into()),
+            status,
+            score,
+            score_raw: None,
+            progress: progress.map(|i| i.into()),
+            progress_volumes: progress_volumes.map(|i| i.into()),
+            notes: None,
+            started_at: None,
+            completed_at: None,
+            repeat: None,
+            private: None,
+            hidden_from_status_lists: None,
+            custom_lists: None,
+            advanced_scores: None,
+        };
+        self.save_media_list_entry(variables).await
+    }
+
+    /// Save every field of a list edit, including notes, dates and custom lists
+    pub async fn save_list_entry(
+        &self,
+        update: &ListUpdate,
+    ) -> Result<update_media_list::ResponseData, AppError> {
+        let details = &update.details;
+        let date = |date: Option<FuzzyDate>| {
+            date.map(|d| update_media_list::FuzzyDateInput {
+                year: d.year.map(|y| y.into()),
+                month: d.month.map(|m| m.into()),
+                day: d.day.map(|d| d.into()),
+            })
+        };
+
+        let variables = update_media_list::Variables {
+            id: update.entry_id.map(|i| i.into()),
+            media_id: Some(update.media_id.into()),
+            status: update.status.as_deref().and_then(parse_status),
+            // Scores are kept on the 100-point scale whatever the user's format
+            score: None,
+            score_raw: update.score.map(|s| s.round() as i64),
+            progress: update.progress.map(|i| i.into()),
+            progress_volumes: update.progress_volumes.map(|i| i.into()),
+            notes: details.notes.clone(),
+            started_at: date(details.started_at),
+            completed_at: date(details.completed_at),
+            repeat: details.repeat.map(|r| r.into()),
+            private: details.private,
+            hidden_from_status_lists: details.hidden_from_status_lists,
+            custom_lists: details
+                .custom_lists
+                .as_ref()
+                .map(|lists| lists.iter().cloned().map(Some).collect()),
+            advanced_scores: details
+                .advanced_scores
+                .as_ref()
+                .map(|scores| scores.iter().copied().map(Some).collect()),
+        };
+        self.save_media_list_entry(variables).await
+    }
+
+    async fn save_media_list_entry(
+        &self,
+        variables: update_media_list::Variables,
+    ) -> Result<update_media_list::ResponseData, AppError> {
+        // This mutation requires authentication
+        if let Ok(None) = self.get_current_token().await {
+            return Err(AppError::ApiError(
+                "Authentication required for this operation".into(),
+            ));
+        }
+
+        self.execute_query::<UpdateMediaList>(variables).await
+    }
+
+    /// Remove an entry from the viewer's list, failing if AniList didn't delete it
+    pub async fn delete_media_list_entry(&self, id: i32) -> Result<(), AppError> {
+        // This mutation requires authentication
+        if let Ok(None) = self.get_current_token().await {
+            return Err(AppError::ApiError(
+                "Authentication required for this operation".into(),
+            ));
+        }
+
+        let variables = delete_media_list_entry::Variables {
+            id: Some(id.into()),
+        };
+        let data = self
+            .execute_query::<DeleteMediaListEntry>(variables)
+            .await?;
+
+        match data.delete_media_list_entry.and_then(|d| d.deleted) {
+            Some(true) => Ok(()),
+            _ => Err(AppError::ApiError(format!(
+                "List entry {} could not be deleted",
+                id
+            ))),
+        }
+    }
+
+    /// Set the status and/or score of many entries in a single request
+    pub async fn update_media_list_entries(
+        &self,
+        ids: &[i32],
+        status: Option<&str>,
+        score: Option<f64>,
+    ) -> Result<(), AppError> {
+        // This mutation requires authentication
+        if let Ok(None) = self.get_current_token().await {
+            return Err(AppError::ApiError(
+                "Authentication required for this operation".into(),
+            ));
+        }
+
+        let status = match status {
+            Some(status) => Some(
+                entries_status(status)
+                    .ok_or_else(|| AppError::ApiError(format!("Unknown list status {}", status)))?,
+            ),
+            None => None,
+        };
+        let variables = update_media_list_entries::Variables {
+            ids: Some(ids.iter().map(|id| Some((*id).into())).collect()),
+            status,
+            // Scores are kept on the 100-point scale whatever the user's format
+            score_raw: score.map(|s| s.round() as i64),
+        };
+        self.execute_query::<UpdateMediaListEntries>(variables)
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the custom lists of many entries, `BATCH_SIZE` entries per request
+    pub async fn save_custom_lists(&self, entries: &[(i32, Vec<String>)]) -> Result<(), AppError> {
+        // This mutation requires authentication
+        if let Ok(None) = self.get_current_token().await {
+            return Err(AppError::ApiError(
+                "Authentication required for this operation".into(),
+            ));
+        }
+
+        for chunk in entries.chunks(BATCH_SIZE) {
+            let mut declarations = Vec::new();
+            let mut fields = Vec::new();
+            let mut variables = serde_json::Map::new();
+            for (i, (id, lists)) in chunk.iter().enumerate() {
+                declarations.push(format!("$lists{}: [String]", i));
+                fields.push(format!(
+                    "e{}: SaveMediaListEntry(id: {}, customLists: $lists{}) {{ id }}",
+                    i, id, i
+                ));
+                variables.insert(format!("lists{}", i), serde_json::json!(lists));
+            }
+
+            let body = aliased_mutation(
+                "SaveCustomLists",
+                &declarations,
+                &fields,
+                Json::Object(variables),
+            );
+            self.execute::<Json>(body).await?;
+        }
+        Ok(())
+    }
+
+    /// Remove many entries from the viewer's list, `BATCH_SIZE` entries per
+    /// request, failing if AniList didn't delete one of them
+    pub async fn delete_media_list_entries(&self, ids: &[i32]) -> Result<(), AppError> {
+        // This mutation requires authentication
+        if let Ok(None) = self.get_current_token().await {
+            return Err(AppError::ApiError(
+                "Authentication required for this operation".into(),
+            ));
+        }
+
+        for chunk in ids.chunks(BATCH_SIZE) {
+            let fields: Vec<String> = chunk
+                .iter()
+                .enumerate()
+                .map(|(i, id)| format!("e{}: DeleteMediaListEntry(id: {}) {{ deleted }}", i, id))
+                .collect();
+
+            let body = aliased_mutation("DeleteMediaListEntries", &[], &fields, Json::Null);
+            let data: Json = self.execute(body).await?;
+            for (i, id) in chunk.iter().enumerate() {
+                if data[format!("e{}", i)]["deleted"].as_bool() != Some(true) {
+                    return Err(AppError::ApiError(format!(
+                        "List entry {} could not be deleted",
+                        id
+                    )));
+                }
+            }
         }
+        Ok(())
+    }
+
+    /// Favourite the target, or unfavourite it if it already is one
+    pub async fn toggle_favourite(
+        &self,
+        target: FavouriteTarget,
+    ) -> Result<toggle_favourite::ResponseData, AppError> {
+        // This mutation requires authentication
+        if let Ok(None) = self.get_current_token().await {
+            return Err(AppError::ApiError(
+                "Authentication required for this operation".into(),
+            ));
+        }
+
+        let mut variables = toggle_favourite::Variables {
+            anime_id: None,
+            manga_id: None,
+            character_id: None,
+            staff_id: None,
+            studio_id: None,
+        };
+        match target {
+            FavouriteTarget::Anime(id) => variables.anime_id = Some(id.into()),
+            FavouriteTarget::Manga(id) => variables.manga_id = Some(id.into()),
+            FavouriteTarget::Character(id) => variables.character_id = Some(id.into()),
+            FavouriteTarget::Staff(id) => variables.staff_id = Some(id.into()),
+            FavouriteTarget::Studio(id) => variables.studio_id = Some(id.into()),
+        }
+        self.execute_once::<ToggleFavourite>(variables).await
+    }
+
+    pub async fn get_viewer(&self) -> Result<viewer::ResponseData, AppError> {
+        // This query requires authentication
+        if let Ok(None) = self.get_current_token().await {
+            return Err(AppError::ApiError(
+                "Authentication required for this operation".into(),
+            ));
+        }
+
+        let variables = viewer::Variables {};
+        self.execute_query::<Viewer>(variables).await
+    }
+
+    /// One page of the viewer's notifications of the given types, newest
+    /// first. `mark_read` resets the unread count.
+    pub async fn get_notifications(
+        &self,
+        types: &[NotificationType],
+        page: Option<i32>,
+        per_page: Option<i32>,
+        mark_read: bool,
+    ) -> Result<(Vec<Notification>, bool), AppError> {
+        let variables = notifications::Variables {
+            page: page.map(|p| p.into()),
+            per_page: per_page.map(|pp| pp.into()),
+            types: Some(types.iter().map(|t| Some((*t).into())).collect()),
+            reset_count: Some(mark_read),
+        };
+        let page = self
+            .execute_query::<Notifications>(variables)
+            .await?
+            .page
+            .ok_or_else(|| AppError::ApiError("No notifications returned".into()))?;
+
+        let has_next_page = page
+            .page_info
+            .and_then(|info| info.has_next_page)
+            .unwrap_or(false);
+        let notifications = page
+            .notifications
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .filter_map(convert_notification)
+            .collect();
+
+        Ok((notifications, has_next_page))
+    }
+
+    pub async fn get_unread_notification_count(&self) -> Result<i32, AppError> {
+        let viewer = self
+            .execute_query::<UnreadNotificationCount>(unread_notification_count::Variables)
+            .await?
+            .viewer
+            .ok_or_else(|| AppError::ApiError("Could not get user information".into()))?;
+
+        Ok(viewer.unread_notification_count.unwrap_or(0) as i32)
+    }
+
+    /// One page of activity, newest first
+    pub async fn get_activities(
+        &self,
+        query: &ActivityQuery,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> Result<(Vec<Activity>, bool), AppError> {
+        use activity_feed::ActivityType;
+
+        let types = match (query.id, query.kind) {
+            (Some(_), _) => None,
+            (None, Some(ActivityKind::Text)) => Some(vec![Some(ActivityType::TEXT)]),
+            (None, Some(ActivityKind::List)) => Some(vec![
+                Some(ActivityType::ANIME_LIST),
+                Some(ActivityType::MANGA_LIST),
+            ]),
+            (None, Some(ActivityKind::Message)) => Some(vec![Some(ActivityType::MESSAGE)]),
+            (None, None) => Some(vec![
+                Some(ActivityType::TEXT),
+                Some(ActivityType::ANIME_LIST),
+                Some(ActivityType::MANGA_LIST),
+            ]),
+        };
+        // Like the site, the global feed skips list updates nobody replied to
+        let quiet_global_feed = query.id.is_none()
+            && query.user_id.is_none()
+            && !query.following_only
+            && query.kind.is_none();
+
+        let variables = activity_feed::Variables {
+            page: page.map(|p| p.into()),
+            per_page: per_page.map(|pp| pp.into()),
+            id: query.id.map(|id| id.into()),
+            user_id: query.user_id.map(|id| id.into()),
+            is_following: query.following_only.then_some(true),
+            types,
+            has_replies_or_type_text: quiet_global_feed.then_some(true),
+        };
+        let page = self
+            .execute_query::<ActivityFeed>(variables)
+            .await?
+            .page
+            .ok_or_else(|| AppError::ApiError("No activity returned".into()))?;
+
+        let has_next_page = page
+            .page_info
+            .and_then(|info| info.has_next_page)
+            .unwrap_or(false);
+        let activities = page
+            .activities
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .map(convert_activity)
+            .collect();
+
+        Ok((activities, has_next_page))
+    }
+
+    /// The replies to an activity, oldest first
+    pub async fn get_activity_replies(
+        &self,
+        activity_id: i32,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> Result<(Vec<ActivityReply>, bool), AppError> {
+        let variables = activity_replies::Variables {
+            activity_id: Some(activity_id.into()),
+            page: page.map(|p| p.into()),
+            per_page: per_page.map(|pp| pp.into()),
+        };
+        let page = self
+            .execute_query::<ActivityReplies>(variables)
+            .await?
+            .page
+            .ok_or_else(|| AppError::ApiError("No replies returned".into()))?;
+
+        let has_next_page = page
+            .page_info
+            .and_then(|info| info.has_next_page)
+            .unwrap_or(false);
+        let replies = page
+            .activity_replies
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .map(ActivityReply::from)
+            .collect();
+
+        Ok((replies, has_next_page))
+    }
+
+    /// Post a status to the viewer's feed and return its id
+    pub async fn post_text_activity(&self, text: String) -> Result<i32, AppError> {
+        // This mutation requires authentication
+        if let Ok(None) = self.get_current_token().await {
+            return Err(AppError::ApiError(
+                "Authentication required for this operation".into(),
+            ));
+        }
+
+        let variables = save_text_activity::Variables { text: Some(text) };
+        self.execute_once::<SaveTextActivity>(variables)
+            .await?
+            .save_text_activity
+            .map(|activity| activity.id as i32)
+            .ok_or_else(|| AppError::ApiError("Activity was not saved".into()))
+    }
+
+    pub async fn reply_to_activity(
+        &self,
+        activity_id: i32,
+        text: String,
+    ) -> Result<ActivityReply, AppError> {
+        // This mutation requires authentication
+        if let Ok(None) = self.get_current_token().await {
+            return Err(AppError::ApiError(
+                "Authentication required for this operation".into(),
+            ));
+        }
+
+        let variables = save_activity_reply::Variables {
+            activity_id: Some(activity_id.into()),
+            text: Some(text),
+        };
+        self.execute_once::<SaveActivityReply>(variables)
+            .await?
+            .save_activity_reply
+            .map(ActivityReply::from)
+            .ok_or_else(|| AppError::ApiError("Reply was not saved".into()))
+    }
+
+    /// Like or unlike, returning whether it is now liked and the new count
+    pub async fn toggle_like(&self, target: LikeTarget) -> Result<(bool, i32), AppError> {
+        use toggle_like::ToggleLikeToggleLikeV2 as Likeable;
+
+        // This mutation requires authentication
+        if let Ok(None) = self.get_current_token().await {
+            return Err(AppError::ApiError(
+                "Authentication required for this operation".into(),
+            ));
+        }
+
+        let (id, type_) = match target {
+            LikeTarget::Activity(id) => (id, toggle_like::LikeableType::ACTIVITY),
+            LikeTarget::ActivityReply(id) => (id, toggle_like::LikeableType::ACTIVITY_REPLY),
+        };
+        let variables = toggle_like::Variables {
+            id: Some(id.into()),
+            type_: Some(type_),
+        };
+        let liked = self
+            .execute_once::<ToggleLike>(variables)
+            .await?
+            .toggle_like_v2
+            .ok_or_else(|| AppError::ApiError("Like was not saved".into()))?;
+
+        match liked {
+            Likeable::ListActivity(a) => Ok((a.is_liked.unwrap_or(false), a.like_count as i32)),
+            Likeable::TextActivity(a) => Ok((a.is_liked.unwrap_or(false), a.like_count as i32)),
+            Likeable::MessageActivity(a) => Ok((a.is_liked.unwrap_or(false), a.like_count as i32)),
+            Likeable::ActivityReply(r) => Ok((r.is_liked.unwrap_or(false), r.like_count as i32)),
+            _ => Err(AppError::ApiError("Unexpected like target".into())),
+        }
+    }
+
+    /// Follow the user, or unfollow them if the viewer already does.
+    /// Returns whether the viewer follows them afterwards.
+    pub async fn toggle_follow(&self, user_id: i32) -> Result<bool, AppError> {
+        // This mutation requires authentication
+        if let Ok(None) = self.get_current_token().await {
+            return Err(AppError::ApiError(
+                "Authentication required for this operation".into(),
+            ));
+        }
+
+        let variables = toggle_follow::Variables {
+            user_id: Some(user_id.into()),
+        };
+        let user = self
+            .execute_once::<ToggleFollow>(variables)
+            .await?
+            .toggle_follow
+            .ok_or_else(|| AppError::ApiError("Follow was not saved".into()))?;
+
+        Ok(user.is_following.unwrap_or(false))
+    }
+
+    /// One page of a user's followers or the users they follow, by name
+    pub async fn get_user_follows(
+        &self,
+        user_id: i32,
+        list: FollowList,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> Result<(Vec<FollowUser>, bool), AppError> {
+        let variables = user_follows::Variables {
+            user_id: user_id.into(),
+            followers: list == FollowList::Followers,
+            page: page.map(|p| p.into()),
+            per_page: per_page.map(|pp| pp.into()),
+        };
+        let page = self
+            .execute_query::<UserFollows>(variables)
+            .await?
+            .page
+            .ok_or_else(|| AppError::ApiError("No page in response".into()))?;
+
+        let has_next_page = page
+            .page_info
+            .and_then(|info| info.has_next_page)
+            .unwrap_or(false);
+        let users = match list {
+            FollowList::Followers => page.followers,
+            FollowList::Following => page.following,
+        }
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .map(|user| FollowUser {
+            id: user.id as i32,
+            name: user.name,
+            avatar_url: user.avatar.and_then(|a| a.medium).unwrap_or_default(),
+            is_following: user.is_following.unwrap_or(false),
+        })
+        .collect();
+
+        Ok((users, has_next_page))
+    }
+
+    /// The viewer's id and their list and display preferences
+    pub async fn get_viewer_preferences(&self) -> Result<(i32, ViewerPreferences), AppError> {
+        let viewer = self
+            .get_viewer()
+            .await?
+            .viewer
+            .ok_or_else(|| AppError::ApiError("Could not get user information".into()))?;
+
+        let options = viewer.options.as_ref();
+        let title_language = match options.and_then(|o| o.title_language.as_ref()) {
+            Some(viewer::UserTitleLanguage::ENGLISH) => TitleLanguage::ENGLISH,
+            Some(viewer::UserTitleLanguage::NATIVE) => TitleLanguage::NATIVE,
+            Some(viewer::UserTitleLanguage::ROMAJI_STYLISED) => TitleLanguage::ROMAJI_STYLISED,
+            Some(viewer::UserTitleLanguage::ENGLISH_STYLISED) => TitleLanguage::ENGLISH_STYLISED,
+            Some(viewer::UserTitleLanguage::NATIVE_STYLISED) => TitleLanguage::NATIVE_STYLISED,
+            _ => TitleLanguage::ROMAJI,
+        };
+        let display_adult_content = options
+            .and_then(|o| o.display_adult_content)
+            .unwrap_or(false);
+
+        let score_format = match viewer
+            .media_list_options
+            .as_ref()
+            .and_then(|options| options.score_format.as_ref())
+        {
+            Some(viewer::ScoreFormat::POINT_100) => ScoreFormat::POINT_100,
+            Some(viewer::ScoreFormat::POINT_10) => ScoreFormat::POINT_10,
+            Some(viewer::ScoreFormat::POINT_5) => ScoreFormat::POINT_5,
+            Some(viewer::ScoreFormat::POINT_3) => ScoreFormat::POINT_3,
+            _ => ScoreFormat::POINT_10_DECIMAL,
+        };
+
+        Ok((
+            viewer.id as i32,
+            ViewerPreferences {
+                score_format,
+                title_language,
+                display_adult_content,
+            },
+        ))
+    }
+
+    pub async fn is_authenticated(&self) -> bool {
+        matches!(self.get_current_token().await, Ok(Some(_)))
     }
 
     pub async fn logout(&self) -> Result<(), AppError> {
@@ -298,3 +1465,462 @@ impl AniListClient {
         Ok(())
     }
 }
+
+fn convert_activity(activity: activity_feed::ActivityFeedPageActivities) -> Activity {
+    use activity_feed::ActivityFeedPageActivities as Union;
+
+    match activity {
+        Union::TextActivity(a) => Activity {
+            id: a.id as i32,
+            kind: ActivityKind::Text,
+            user: a.user.map(|u| u.name).unwrap_or_default(),
+            created_at: a.created_at,
+            text: a.text.as_deref().map(plain_description).unwrap_or_default(),
+            status: None,
+            progress: None,
+            media: None,
+            reply_count: a.reply_count as i32,
+            like_count: a.like_count as i32,
+            is_liked: a.is_liked.unwrap_or(false),
+        },
+        Union::ListActivity(a) => Activity {
+            id: a.id as i32,
+            kind: ActivityKind::List,
+            user: a.user.map(|u| u.name).unwrap_or_default(),
+            created_at: a.created_at,
+            text: String::new(),
+            status: a.status,
+            progress: a.progress,
+            media: a.media.map(|media| {
+                let media_type = match media.type_ {
+                    Some(activity_feed::MediaType::MANGA) => MediaType::MANGA,
+                    _ => MediaType::ANIME,
+                };
+                let title = media.title.map(MediaTitle::from).unwrap_or_default();
+                (media.id as i32, media_type, title)
+            }),
+            reply_count: a.reply_count as i32,
+            like_count: a.like_count as i32,
+            is_liked: a.is_liked.unwrap_or(false),
+        },
+        Union::MessageActivity(a) => Activity {
+            id: a.id as i32,
+            kind: ActivityKind::Message,
+            user: a.messenger.map(|u| u.name).unwrap_or_default(),
+            created_at: a.created_at,
+            text: a
+                .message
+                .as_deref()
+                .map(plain_description)
+                .unwrap_or_default(),
+            status: None,
+            progress: None,
+            media: None,
+            reply_count: a.reply_count as i32,
+            like_count: a.like_count as i32,
+            is_liked: a.is_liked.unwrap_or(false),
+        },
+    }
+}
+
+fn convert_notification(
+    notification: notifications::NotificationsPageNotifications,
+) -> Option<Notification> {
+    use notifications::NotificationsPageNotifications as Union;
+
+    let base = |id: i64, notification_type, created_at: Option<i64>| Notification {
+        id: id as i32,
+        notification_type,
+        created_at: created_at.unwrap_or(0),
+        user: None,
+        media: None,
+        episode: None,
+        activity_id: None,
+        context: String::new(),
+        reason: None,
+        deleted_titles: Vec::new(),
+    };
+    let media = |media: notifications::NotificationMedia| {
+        let media_type = match media.type_ {
+            Some(notifications::MediaType::MANGA) => MediaType::MANGA,
+            _ => MediaType::ANIME,
+        };
+        let title = media.title.map(MediaTitle::from).unwrap_or_default();
+        (media.id as i32, media_type, title)
+    };
+
+    // Every activity notification has the same shape
+    macro_rules! activity {
+        ($n:ident, $notification_type:ident) => {
+            Notification {
+                user: $n.user.map(|u| u.name),
+                activity_id: Some($n.activity_id as i32),
+                context: $n.context.unwrap_or_default(),
+                ..base($n.id, NotificationType::$notification_type, $n.created_at)
+            }
+        };
+    }
+
+    let notification = match notification {
+        Union::AiringNotification(n) => Notification {
+            media: n.media.map(media),
+            episode: Some(n.episode as i32),
+            ..base(n.id, NotificationType::AIRING, n.created_at)
+        },
+        Union::FollowingNotification(n) => Notification {
+            user: n.user.map(|u| u.name),
+            context: n.context.unwrap_or_default(),
+            ..base(n.id, NotificationType::FOLLOWING, n.created_at)
+        },
+        Union::ActivityMessageNotification(n) => activity!(n, ACTIVITY_MESSAGE),
+        Union::ActivityMentionNotification(n) => activity!(n, ACTIVITY_MENTION),
+        Union::ActivityReplyNotification(n) => activity!(n, ACTIVITY_REPLY),
+        Union::ActivityReplySubscribedNotification(n) => activity!(n, ACTIVITY_REPLY_SUBSCRIBED),
+        Union::ActivityLikeNotification(n) => activity!(n, ACTIVITY_LIKE),
+        Union::ActivityReplyLikeNotification(n) => activity!(n, ACTIVITY_REPLY_LIKE),
+        Union::RelatedMediaAdditionNotification(n) => Notification {
+            media: n.media.map(media),
+            context: n.context.unwrap_or_default(),
+            ..base(n.id, NotificationType::RELATED_MEDIA_ADDITION, n.created_at)
+        },
+        Union::MediaDataChangeNotification(n) => Notification {
+            media: n.media.map(media),
+            context: n.context.unwrap_or_default(),
+            reason: n.reason,
+            ..base(n.id, NotificationType::MEDIA_DATA_CHANGE, n.created_at)
+        },
+        Union::MediaMergeNotification(n) => Notification {
+            media: n.media.map(media),
+            context: n.context.unwrap_or_default(),
+            reason: n.reason,
+            deleted_titles: n
+                .deleted_media_titles
+                .unwrap_or_default()
+                .into_iter()
+                .flatten()
+                .collect(),
+            ..base(n.id, NotificationType::MEDIA_MERGE, n.created_at)
+        },
+        Union::MediaDeletionNotification(n) => Notification {
+            context: n.context.unwrap_or_default(),
+            reason: n.reason,
+            deleted_titles: n.deleted_media_title.into_iter().collect(),
+            ..base(n.id, NotificationType::MEDIA_DELETION, n.created_at)
+        },
+        // Forum notifications are not requested
+        _ => return None,
+    };
+
+    Some(notification)
+}
+
+// Entries per aliased mutation, well under AniList's query complexity limit
+const BATCH_SIZE: usize = 25;
+
+// One request running the same mutation for several entries, each field
+// aliased `e0`, `e1`, ... so the results can be told apart
+fn aliased_mutation(
+    name: &str,
+    declarations: &[String],
+    fields: &[String],
+    variables: Json,
+) -> Json {
+    let declarations = if declarations.is_empty() {
+        String::new()
+    } else {
+        format!("({})", declarations.join(", "))
+    };
+    serde_json::json!({
+        "operationName": name,
+        "query": format!("mutation {}{} {{ {} }}", name, declarations, fields.join(" ")),
+        "variables": variables,
+    })
+}
+
+fn entries_status(status: &str) -> Option<update_media_list_entries::MediaListStatus> {
+    use update_media_list_entries::MediaListStatus;
+
+    match status {
+        "CURRENT" => Some(MediaListStatus::CURRENT),
+        "PLANNING" => Some(MediaListStatus::PLANNING),
+        "COMPLETED" => Some(MediaListStatus::COMPLETED),
+        "DROPPED" => Some(MediaListStatus::DROPPED),
+        "PAUSED" => Some(MediaListStatus::PAUSED),
+        "REPEATING" => Some(MediaListStatus::REPEATING),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::{NotificationGroup, NotificationLink};
+    use crate::api::transport::MockTransport;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn search_page() -> serde_json::Value {
+        json!({
+            "Page": {
+                "pageInfo": {
+                    "total": 1,
+                    "currentPage": 1,
+                    "lastPage": 1,
+                    "hasNextPage": false,
+                    "perPage": 5
+                },
+                "media": [{
+                    "id": 16498,
+                    "title": {
+                        "romaji": "Shingeki no Kyojin",
+                        "english": "Attack on Titan",
+                        "native": null
+                    },
+                    "coverImage": { "medium": null },
+                    "format": "TV",
+                    "episodes": 25,
+                    "seasonYear": 2013,
+                    "averageScore": 85,
+                    "popularity": 1000
+                }]
+            }
+        })
+    }
+
+    fn client_with(transport: Arc<MockTransport>) -> AniListClient {
+        AniListClient::new()
+            .with_transport(transport)
+            .with_retry_policy(RetryPolicy {
+                max_retries: 2,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(5),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_search_against_mock_transport() {
+        let transport = Arc::new(MockTransport::new().with_data("AnimeSearch", search_page()));
+        let client = client_with(transport.clone());
+
+        let data = client
+            .search_anime("Shingeki".to_string(), Some(1), Some(5))
+            .await
+            .expect("search should succeed");
+
+        let media = data.page.unwrap().media.unwrap();
+        assert_eq!(media.len(), 1);
+        assert_eq!(media[0].as_ref().unwrap().id, 16498);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].operation_name(), Some("AnimeSearch"));
+        assert_eq!(requests[0].body["variables"]["search"], "Shingeki");
+        assert!(requests[0].bearer_token.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_filtered_search_maps_inclusive_ranges() {
+        let transport = Arc::new(MockTransport::new().with_data("AnimeSearch", search_page()));
+        let client = client_with(transport.clone());
+
+        let filters = SearchFilters {
+            genres: vec!["Action".to_string()],
+            year_from: Some(2010),
+            year_to: Some(2015),
+            score_min: Some(70),
+            length_max: Some(13),
+            formats: vec![MediaFormat::TV],
+            sort: Some(MediaSort::SEARCH_MATCH),
+            ..Default::default()
+        };
+        client
+            .search_media_filtered(&filters, MediaType::ANIME, Some(1), Some(5))
+            .await
+            .expect("search should succeed");
+
+        let variables = &transport.requests()[0].body["variables"];
+        assert!(variables["search"].is_null());
+        assert_eq!(variables["genres"], json!(["Action"]));
+        assert_eq!(variables["startDateGreater"], 20100000);
+        assert_eq!(variables["startDateLesser"], 20160000);
+        assert_eq!(variables["averageScoreGreater"], 69);
+        assert_eq!(variables["episodesLesser"], 14);
+        assert!(variables["chaptersLesser"].is_null());
+        assert_eq!(variables["formats"], json!(["TV"]));
+        // Relevance needs a query, so an empty one falls back to popularity
+        assert_eq!(variables["sort"], json!(["POPULARITY_DESC"]));
+    }
+
+    #[tokio::test]
+    async fn test_transient_errors_are_retried() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_response("AnimeSearch", StatusCode::BAD_GATEWAY, json!({}))
+                .with_response("AnimeSearch", StatusCode::TOO_MANY_REQUESTS, json!({}))
+                .with_data("AnimeSearch", search_page()),
+        );
+        let client = client_with(transport.clone());
+
+        assert!(client
+            .search_anime("Shingeki".to_string(), None, None)
+            .await
+            .is_ok());
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_toggles_are_not_retried_on_server_errors() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_response("ToggleFollow", StatusCode::BAD_GATEWAY, json!({}))
+                .with_data(
+                    "ToggleFollow",
+                    json!({ "ToggleFollow": { "id": 1, "isFollowing": true } }),
+                ),
+        );
+        let client = AniListClient::with_token("token".to_string())
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy {
+                max_retries: 2,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(5),
+            });
+
+        // The follow may have gone through, so sending it again could undo it
+        assert!(client.toggle_follow(1).await.is_err());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_notifications_are_read_from_the_union() {
+        let transport = Arc::new(MockTransport::new().with_data(
+            "Notifications",
+            json!({
+                "Page": {
+                    "pageInfo": { "hasNextPage": true },
+                    "notifications": [
+                        {
+                            "__typename": "AiringNotification",
+                            "id": 1,
+                            "episode": 12,
+                            "createdAt": 1700000000,
+                            "media": {
+                                "id": 16498,
+                                "type": "ANIME",
+                                "title": { "romaji": "Shingeki no Kyojin", "english": null, "native": null }
+                            }
+                        },
+                        {
+                            "__typename": "ActivityLikeNotification",
+                            "id": 2,
+                            "activityId": 555,
+                            "context": " liked your activity.",
+                            "createdAt": 1700000100,
+                            "user": { "id": 7, "name": "Levi" }
+                        },
+                        { "__typename": "ThreadLikeNotification" }
+                    ]
+                }
+            }),
+        ));
+        let client = client_with(transport.clone());
+
+        let (notifications, has_next_page) = client
+            .get_notifications(NotificationGroup::Airing.types(), Some(1), Some(25), true)
+            .await
+            .expect("notifications should load");
+
+        assert!(has_next_page);
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(
+            notifications[0].message(TitleLanguage::ROMAJI),
+            "Episode 12 of Shingeki no Kyojin aired."
+        );
+        assert_eq!(
+            notifications[0].link(),
+            Some(NotificationLink::Media(16498, MediaType::ANIME))
+        );
+        assert_eq!(
+            notifications[1].message(TitleLanguage::ROMAJI),
+            "Levi liked your activity."
+        );
+        assert_eq!(
+            notifications[1].link(),
+            Some(NotificationLink::Activity(555))
+        );
+
+        let variables = &transport.requests()[0].body["variables"];
+        assert_eq!(variables["types"], json!(["AIRING"]));
+        assert_eq!(variables["resetCount"], true);
+    }
+
+    #[tokio::test]
+    async fn test_activity_feed_variables_and_list_summaries() {
+        let transport = Arc::new(MockTransport::new().with_data(
+            "ActivityFeed",
+            json!({
+                "Page": {
+                    "pageInfo": { "hasNextPage": false },
+                    "activities": [{
+                        "__typename": "ListActivity",
+                        "id": 9,
+                        "status": "watched episode",
+                        "progress": "3 - 4",
+                        "replyCount": 1,
+                        "likeCount": 2,
+                        "isLiked": true,
+                        "createdAt": 1700000000,
+                        "user": { "id": 7, "name": "Levi" },
+                        "media": {
+                            "id": 16498,
+                            "type": "ANIME",
+                            "title": { "romaji": "Shingeki no Kyojin", "english": null, "native": null }
+                        }
+                    }]
+                }
+            }),
+        ));
+        let client = client_with(transport.clone());
+
+        let (activities, _) = client
+            .get_activities(&ActivityQuery::default(), Some(1), Some(20))
+            .await
+            .expect("feed should load");
+
+        assert_eq!(activities.len(), 1);
+        assert_eq!(activities[0].user, "Levi");
+        assert_eq!(
+            activities[0].summary(TitleLanguage::ROMAJI),
+            "watched episode 3 - 4 of Shingeki no Kyojin"
+        );
+
+        // The global feed skips bare list updates, a followed feed does not
+        let variables = &transport.requests()[0].body["variables"];
+        assert_eq!(variables["hasRepliesOrTypeText"], true);
+        assert!(variables["isFollowing"].is_null());
+
+        let following = ActivityQuery {
+            following_only: true,
+            ..Default::default()
+        };
+        client.get_activities(&following, None, None).await.unwrap();
+        let variables = &transport.requests()[1].body["variables"];
+        assert!(variables["hasRepliesOrTypeText"].is_null());
+        assert_eq!(variables["isFollowing"], true);
+    }
+
+    #[tokio::test]
+    async fn test_graphql_errors_are_reported() {
+        let transport = Arc::new(MockTransport::new().with_response(
+            "AnimeDetails",
+            StatusCode::OK,
+            json!({ "data": null, "errors": [{ "message": "Not Found." }] }),
+        ));
+        let client = client_with(transport);
+
+        match client.get_anime_details(1).await {
+            Err(AppError::ApiError(message)) => assert_eq!(message, "Not Found."),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+}
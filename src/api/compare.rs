@@ -0,0 +1,187 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::api::client::{user_anime_list, AniListClient};
+use crate::api::models::MediaTitle;
+use crate::utils::error::AppError;
+
+// Scores are compared on the 100-point scale the list query returns
+pub const HIGH_SCORE: f64 = 80.0;
+
+/// One title from a user's anime list, reduced to what a comparison needs
+#[derive(Debug, Clone)]
+pub struct ComparedEntry {
+    pub media_id: i32,
+    pub title: MediaTitle,
+    pub status: String,
+    // 0 when unscored
+    pub score: f64,
+}
+
+/// A title on both lists
+#[derive(Debug, Clone)]
+pub struct SharedTitle {
+    pub media_id: i32,
+    pub title: MediaTitle,
+    pub our_status: String,
+    pub their_status: String,
+    pub our_score: f64,
+    pub their_score: f64,
+}
+
+impl SharedTitle {
+    /// Their score minus ours, when both of us scored it
+    pub fn difference(&self) -> Option<f64> {
+        (self.our_score > 0.0 && self.their_score > 0.0)
+            .then_some(self.their_score - self.our_score)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ListComparison {
+    // Biggest disagreements first
+    pub shared: Vec<SharedTitle>,
+    // Titles they scored highly that we haven't watched, best first
+    pub recommendations: Vec<ComparedEntry>,
+}
+
+impl ListComparison {
+    /// How much higher they score than us on average, over titles both scored
+    pub fn mean_difference(&self) -> Option<f64> {
+        let differences: Vec<f64> = self.shared.iter().filter_map(|s| s.difference()).collect();
+        if differences.is_empty() {
+            return None;
+        }
+        Some(differences.iter().sum::<f64>() / differences.len() as f64)
+    }
+}
+
+/// Compare the viewer's anime list with another user's
+pub async fn compare_anime_lists(
+    client: &AniListClient,
+    viewer_id: i32,
+    user_id: i32,
+) -> Result<ListComparison, AppError> {
+    let ours = list_entries(client.get_user_anime_list(viewer_id, None).await?);
+    let theirs = list_entries(client.get_user_anime_list(user_id, None).await?);
+    Ok(compare_lists(&ours, &theirs))
+}
+
+pub fn compare_lists(ours: &[ComparedEntry], theirs: &[ComparedEntry]) -> ListComparison {
+    let ours: HashMap<i32, &ComparedEntry> = ours.iter().map(|e| (e.media_id, e)).collect();
+
+    let mut shared = Vec::new();
+    let mut recommendations = Vec::new();
+    for entry in theirs {
+        match ours.get(&entry.media_id) {
+            // Planning to watch it is not having seen it
+            Some(our) if our.status != "PLANNING" => shared.push(SharedTitle {
+                media_id: entry.media_id,
+                title: entry.title.clone(),
+                our_status: our.status.clone(),
+                their_status: entry.status.clone(),
+                our_score: our.score,
+                their_score: entry.score,
+            }),
+            _ if entry.score >= HIGH_SCORE => recommendations.push(entry.clone()),
+            _ => {}
+        }
+    }
+
+    // Unscored titles have no difference and go last
+    shared.sort_by(|a, b| {
+        let a_diff = a.difference().map(f64::abs).unwrap_or(-1.0);
+        let b_diff = b.difference().map(f64::abs).unwrap_or(-1.0);
+        b_diff.total_cmp(&a_diff).then(a.media_id.cmp(&b.media_id))
+    });
+    recommendations.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.media_id.cmp(&b.media_id))
+    });
+
+    ListComparison {
+        shared,
+        recommendations,
+    }
+}
+
+fn list_entries(data: user_anime_list::ResponseData) -> Vec<ComparedEntry> {
+    // Custom lists repeat entries that are already in a status list
+    let mut seen = HashSet::new();
+    data.media_list_collection
+        .and_then(|collection| collection.lists)
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .flat_map(|list| list.entries.unwrap_or_default())
+        .flatten()
+        .filter(|entry| seen.insert(entry.media_id))
+        .map(|entry| ComparedEntry {
+            media_id: entry.media_id as i32,
+            title: entry
+                .media
+                .and_then(|m| m.title)
+                .map(MediaTitle::from)
+                .unwrap_or_default(),
+            status: entry
+                .status
+                .map_or("UNKNOWN".to_string(), |s| format!("{:?}", s)),
+            score: entry.score.unwrap_or(0.0),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(media_id: i32, status: &str, score: f64) -> ComparedEntry {
+        ComparedEntry {
+            media_id,
+            title: MediaTitle {
+                romaji: Some(format!("Show {}", media_id)),
+                english: None,
+                native: None,
+                ..Default::default()
+            },
+            status: status.to_string(),
+            score,
+        }
+    }
+
+    #[test]
+    fn test_compare_lists() {
+        let ours = vec![
+            entry(1, "COMPLETED", 70.0),
+            entry(2, "COMPLETED", 90.0),
+            entry(3, "CURRENT", 0.0),
+            entry(4, "PLANNING", 0.0),
+        ];
+        let theirs = vec![
+            entry(1, "COMPLETED", 90.0),
+            entry(2, "COMPLETED", 85.0),
+            entry(3, "COMPLETED", 60.0),
+            entry(4, "COMPLETED", 95.0),
+            entry(5, "COMPLETED", 80.0),
+            entry(6, "DROPPED", 40.0),
+        ];
+
+        let comparison = compare_lists(&ours, &theirs);
+
+        let shared: Vec<(i32, Option<f64>)> = comparison
+            .shared
+            .iter()
+            .map(|s| (s.media_id, s.difference()))
+            .collect();
+        assert_eq!(shared, vec![(1, Some(20.0)), (2, Some(-5.0)), (3, None)]);
+        assert_eq!(comparison.mean_difference(), Some(7.5));
+
+        // Planned but unwatched titles count as unseen; low scores are left out
+        let recommended: Vec<i32> = comparison
+            .recommendations
+            .iter()
+            .map(|e| e.media_id)
+            .collect();
+        assert_eq!(recommended, vec![4, 5]);
+    }
+}
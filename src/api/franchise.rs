@@ -0,0 +1,192 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::api::client::{media_relations, AniListClient};
+use crate::api::models::{FuzzyDate, MediaTitle, MediaType};
+use crate::utils::error::AppError;
+
+// Enough for long-running franchises without walking into unrelated shows
+pub const MAX_FRANCHISE_ENTRIES: usize = 40;
+
+/// One media in a franchise timeline
+#[derive(Debug, Clone)]
+pub struct FranchiseEntry {
+    pub id: i32,
+    pub media_type: MediaType,
+    pub title: MediaTitle,
+    pub format: Option<String>,
+    pub start_date: FuzzyDate,
+    // How the walk reached this media ("SEQUEL", "SIDE_STORY"...), None for
+    // the media the walk started from
+    pub relation: Option<String>,
+    // The viewer's list status, if the media is on their list
+    pub list_status: Option<String>,
+}
+
+/// Walk the SEQUEL/PREQUEL/SIDE_STORY relations out from a media and return
+/// everything reached in watch order. Relations into another media type
+/// (a manga's anime adaptation's sequel, say) are not followed.
+pub async fn walk_franchise(
+    client: &AniListClient,
+    media_id: i32,
+    max_entries: usize,
+) -> Result<Vec<FranchiseEntry>, AppError> {
+    let mut queue = VecDeque::from([(media_id, None)]);
+    let mut seen = HashSet::from([media_id]);
+    let mut media_type = None;
+    let mut entries = Vec::new();
+
+    while let Some((id, relation)) = queue.pop_front() {
+        if entries.len() >= max_entries {
+            break;
+        }
+
+        let Some(media) = client.get_media_relations(id).await?.media else {
+            if relation.is_none() {
+                return Err(AppError::ApiError("Media not found".into()));
+            }
+            continue;
+        };
+
+        let this_type = match media.type_ {
+            Some(media_relations::MediaType::MANGA) => MediaType::MANGA,
+            _ => MediaType::ANIME,
+        };
+        let root_type = *media_type.get_or_insert(this_type);
+
+        let edges = media
+            .relations
+            .and_then(|r| r.edges)
+            .unwrap_or_default()
+            .into_iter()
+            .flatten();
+        for edge in edges {
+            let (Some(relation_type), Some(node)) = (edge.relation_type, edge.node) else {
+                continue;
+            };
+            let follow = matches!(
+                relation_type,
+                media_relations::MediaRelation::SEQUEL
+                    | media_relations::MediaRelation::PREQUEL
+                    | media_relations::MediaRelation::SIDE_STORY
+            );
+            let same_type = match node.type_ {
+                Some(media_relations::MediaType::MANGA) => root_type == MediaType::MANGA,
+                _ => root_type == MediaType::ANIME,
+            };
+            let node_id = node.id as i32;
+            if follow && same_type && seen.insert(node_id) {
+                queue.push_back((node_id, Some(format!("{:?}", relation_type))));
+            }
+        }
+
+        entries.push(FranchiseEntry {
+            id: media.id as i32,
+            media_type: this_type,
+            title: media.title.map(MediaTitle::from).unwrap_or_default(),
+            format: media.format.map(|f| format!("{:?}", f)),
+            start_date: media
+                .start_date
+                .map(|date| FuzzyDate {
+                    year: date.year.map(|y| y as i32),
+                    month: date.month.map(|m| m as i32),
+                    day: date.day.map(|d| d as i32),
+                })
+                .unwrap_or_default(),
+            relation,
+            list_status: media
+                .media_list_entry
+                .and_then(|entry| entry.status)
+                .map(|status| format!("{:?}", status)),
+        });
+    }
+
+    sort_watch_order(&mut entries);
+    Ok(entries)
+}
+
+/// Release order, with anything not yet dated at the end
+pub fn sort_watch_order(entries: &mut [FranchiseEntry]) {
+    entries.sort_by_key(|entry| {
+        let date = &entry.start_date;
+        (
+            date.year.unwrap_or(i32::MAX),
+            date.month.unwrap_or(13),
+            date.day.unwrap_or(32),
+            entry.id,
+        )
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::transport::MockTransport;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+
+    fn media(id: i32, year: i32, status: Value, relations: Value) -> Value {
+        json!({
+            "Media": {
+                "id": id,
+                "type": "ANIME",
+                "format": "TV",
+                "title": { "romaji": format!("Show {}", id), "english": null, "native": null },
+                "startDate": { "year": year, "month": 4, "day": null },
+                "mediaListEntry": status,
+                "relations": { "edges": relations }
+            }
+        })
+    }
+
+    fn edge(relation: &str, id: i32, media_type: &str) -> Value {
+        json!({ "relationType": relation, "node": { "id": id, "type": media_type } })
+    }
+
+    #[tokio::test]
+    async fn test_walk_follows_story_relations_in_release_order() {
+        // 1 -> sequel 2 -> side story 3, released between the two seasons.
+        // The manga source and a character crossover are not followed.
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_data(
+                    "MediaRelations",
+                    media(
+                        1,
+                        2013,
+                        json!({ "status": "COMPLETED" }),
+                        json!([
+                            edge("SEQUEL", 2, "ANIME"),
+                            edge("SOURCE", 10, "MANGA"),
+                            edge("CHARACTER", 11, "ANIME"),
+                        ]),
+                    ),
+                )
+                .with_data(
+                    "MediaRelations",
+                    media(
+                        2,
+                        2017,
+                        Value::Null,
+                        json!([edge("PREQUEL", 1, "ANIME"), edge("SIDE_STORY", 3, "ANIME")]),
+                    ),
+                )
+                .with_data(
+                    "MediaRelations",
+                    media(3, 2014, Value::Null, json!([edge("PARENT", 2, "ANIME")])),
+                ),
+        );
+        let client = AniListClient::new().with_transport(transport.clone());
+
+        let entries = walk_franchise(&client, 1, MAX_FRANCHISE_ENTRIES)
+            .await
+            .expect("walk should succeed");
+
+        let ids: Vec<i32> = entries.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![1, 3, 2]);
+        assert_eq!(entries[0].relation, None);
+        assert_eq!(entries[0].list_status.as_deref(), Some("COMPLETED"));
+        assert_eq!(entries[1].relation.as_deref(), Some("SIDE_STORY"));
+        assert_eq!(entries[2].relation.as_deref(), Some("SEQUEL"));
+        assert_eq!(transport.requests().len(), 3);
+    }
+}
@@ -1,6 +1,11 @@
 pub mod auth;
 pub mod client;
+pub mod compare;
+pub mod franchise;
 pub mod models;
+pub mod my_list;
+pub mod rate_limit;
+pub mod transport;
 
 // Re-export commonly used types
 pub use client::AniListClient;
@@ -14,11 +14,81 @@ pub struct Avatar {
     pub medium: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MediaTitle {
     pub romaji: Option<String>,
     pub english: Option<String>,
     pub native: Option<String>,
+    // As styled by the publisher, e.g. "Re:ZERO", for the `*_STYLISED` languages
+    #[serde(default)]
+    pub romaji_stylised: Option<String>,
+    #[serde(default)]
+    pub english_stylised: Option<String>,
+    #[serde(default)]
+    pub native_stylised: Option<String>,
+}
+
+impl MediaTitle {
+    /// The title in the given language, falling back to the unstylised title
+    /// in that language, then romaji, then English, then native
+    pub fn preferred(&self, language: TitleLanguage) -> String {
+        let preferred: &[&Option<String>] = match language {
+            TitleLanguage::ROMAJI => &[&self.romaji],
+            TitleLanguage::ENGLISH => &[&self.english],
+            TitleLanguage::NATIVE => &[&self.native],
+            TitleLanguage::ROMAJI_STYLISED => &[&self.romaji_stylised, &self.romaji],
+            TitleLanguage::ENGLISH_STYLISED => &[&self.english_stylised, &self.english],
+            TitleLanguage::NATIVE_STYLISED => &[&self.native_stylised, &self.native],
+        };
+
+        preferred
+            .iter()
+            .copied()
+            .chain([&self.romaji, &self.english, &self.native])
+            .flatten()
+            .find(|title| !title.trim().is_empty())
+            .cloned()
+            .unwrap_or_else(|| "Unknown Title".to_string())
+    }
+}
+
+/// Which title to show, from the viewer's AniList settings unless overridden
+/// in Settings
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TitleLanguage {
+    #[default]
+    ROMAJI,
+    ENGLISH,
+    NATIVE,
+    ROMAJI_STYLISED,
+    ENGLISH_STYLISED,
+    NATIVE_STYLISED,
+}
+
+impl TitleLanguage {
+    pub const ALL: [TitleLanguage; 6] = [
+        TitleLanguage::ROMAJI,
+        TitleLanguage::ENGLISH,
+        TitleLanguage::NATIVE,
+        TitleLanguage::ROMAJI_STYLISED,
+        TitleLanguage::ENGLISH_STYLISED,
+        TitleLanguage::NATIVE_STYLISED,
+    ];
+}
+
+impl std::fmt::Display for TitleLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TitleLanguage::ROMAJI => "Romaji",
+            TitleLanguage::ENGLISH => "English",
+            TitleLanguage::NATIVE => "Native",
+            TitleLanguage::ROMAJI_STYLISED => "Romaji (stylised)",
+            TitleLanguage::ENGLISH_STYLISED => "English (stylised)",
+            TitleLanguage::NATIVE_STYLISED => "Native (stylised)",
+        };
+        write!(f, "{}", name)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +103,8 @@ pub struct Media {
     pub title: MediaTitle,
     pub description: Option<String>,
     pub episodes: Option<i32>,
+    pub chapters: Option<i32>,
+    pub volumes: Option<i32>,
     pub duration: Option<i32>,
     pub genres: Option<Vec<String>>,
     pub average_score: Option<f64>,
@@ -47,13 +119,14 @@ pub struct MediaListEntry {
     pub id: i32,
     pub media_id: i32,
     pub status: String,
-    pub score: Option<f64>,
+    pub score: Option<f64>, // 0-100, see ScoreFormat
     pub progress: Option<i32>,
+    pub progress_volumes: Option<i32>,
     pub updated_at: i64, // Unix timestamp
     pub media: Option<Media>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MediaType {
     ANIME,
     MANGA,
@@ -68,3 +141,766 @@ pub enum MediaListStatus {
     PAUSED,
     REPEATING,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MediaSeason {
+    WINTER,
+    SPRING,
+    SUMMER,
+    FALL,
+}
+
+impl MediaSeason {
+    pub const ALL: [MediaSeason; 4] = [
+        MediaSeason::WINTER,
+        MediaSeason::SPRING,
+        MediaSeason::SUMMER,
+        MediaSeason::FALL,
+    ];
+
+    // The season airing in the given month (1-12)
+    pub fn from_month(month: u32) -> Self {
+        match month {
+            1..=3 => MediaSeason::WINTER,
+            4..=6 => MediaSeason::SPRING,
+            7..=9 => MediaSeason::SUMMER,
+            _ => MediaSeason::FALL,
+        }
+    }
+
+    pub fn next(self, year: i32) -> (Self, i32) {
+        match self {
+            MediaSeason::WINTER => (MediaSeason::SPRING, year),
+            MediaSeason::SPRING => (MediaSeason::SUMMER, year),
+            MediaSeason::SUMMER => (MediaSeason::FALL, year),
+            MediaSeason::FALL => (MediaSeason::WINTER, year + 1),
+        }
+    }
+
+    pub fn previous(self, year: i32) -> (Self, i32) {
+        match self {
+            MediaSeason::WINTER => (MediaSeason::FALL, year - 1),
+            MediaSeason::SPRING => (MediaSeason::WINTER, year),
+            MediaSeason::SUMMER => (MediaSeason::SPRING, year),
+            MediaSeason::FALL => (MediaSeason::SUMMER, year),
+        }
+    }
+}
+
+impl std::fmt::Display for MediaSeason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaSeason::WINTER => write!(f, "Winter"),
+            MediaSeason::SPRING => write!(f, "Spring"),
+            MediaSeason::SUMMER => write!(f, "Summer"),
+            MediaSeason::FALL => write!(f, "Fall"),
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MediaFormat {
+    TV,
+    TV_SHORT,
+    MOVIE,
+    SPECIAL,
+    OVA,
+    ONA,
+    MUSIC,
+    MANGA,
+    NOVEL,
+    ONE_SHOT,
+}
+
+impl MediaFormat {
+    pub const ANIME_FORMATS: [MediaFormat; 7] = [
+        MediaFormat::TV,
+        MediaFormat::TV_SHORT,
+        MediaFormat::MOVIE,
+        MediaFormat::SPECIAL,
+        MediaFormat::OVA,
+        MediaFormat::ONA,
+        MediaFormat::MUSIC,
+    ];
+
+    pub const MANGA_FORMATS: [MediaFormat; 3] = [
+        MediaFormat::MANGA,
+        MediaFormat::NOVEL,
+        MediaFormat::ONE_SHOT,
+    ];
+
+    pub fn for_media_type(media_type: MediaType) -> &'static [MediaFormat] {
+        match media_type {
+            MediaType::ANIME => &Self::ANIME_FORMATS,
+            MediaType::MANGA => &Self::MANGA_FORMATS,
+        }
+    }
+}
+
+impl std::fmt::Display for MediaFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaFormat::TV => write!(f, "TV"),
+            MediaFormat::TV_SHORT => write!(f, "TV Short"),
+            MediaFormat::MOVIE => write!(f, "Movie"),
+            MediaFormat::SPECIAL => write!(f, "Special"),
+            MediaFormat::OVA => write!(f, "OVA"),
+            MediaFormat::ONA => write!(f, "ONA"),
+            MediaFormat::MUSIC => write!(f, "Music"),
+            MediaFormat::MANGA => write!(f, "Manga"),
+            MediaFormat::NOVEL => write!(f, "Light Novel"),
+            MediaFormat::ONE_SHOT => write!(f, "One Shot"),
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MediaStatus {
+    FINISHED,
+    RELEASING,
+    NOT_YET_RELEASED,
+    CANCELLED,
+    HIATUS,
+}
+
+impl MediaStatus {
+    pub const ALL: [MediaStatus; 5] = [
+        MediaStatus::RELEASING,
+        MediaStatus::FINISHED,
+        MediaStatus::NOT_YET_RELEASED,
+        MediaStatus::CANCELLED,
+        MediaStatus::HIATUS,
+    ];
+}
+
+impl std::fmt::Display for MediaStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaStatus::FINISHED => write!(f, "Finished"),
+            MediaStatus::RELEASING => write!(f, "Releasing"),
+            MediaStatus::NOT_YET_RELEASED => write!(f, "Not Yet Released"),
+            MediaStatus::CANCELLED => write!(f, "Cancelled"),
+            MediaStatus::HIATUS => write!(f, "Hiatus"),
+        }
+    }
+}
+
+// The sort orders offered in the UI, a subset of AniList's MediaSort
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MediaSort {
+    SEARCH_MATCH,
+    POPULARITY_DESC,
+    SCORE_DESC,
+    TRENDING_DESC,
+    FAVOURITES_DESC,
+    START_DATE,
+    START_DATE_DESC,
+    TITLE_ROMAJI,
+}
+
+impl MediaSort {
+    pub const ALL: [MediaSort; 8] = [
+        MediaSort::SEARCH_MATCH,
+        MediaSort::POPULARITY_DESC,
+        MediaSort::SCORE_DESC,
+        MediaSort::TRENDING_DESC,
+        MediaSort::FAVOURITES_DESC,
+        MediaSort::START_DATE,
+        MediaSort::START_DATE_DESC,
+        MediaSort::TITLE_ROMAJI,
+    ];
+}
+
+impl std::fmt::Display for MediaSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaSort::SEARCH_MATCH => write!(f, "Relevance"),
+            MediaSort::POPULARITY_DESC => write!(f, "Popularity"),
+            MediaSort::SCORE_DESC => write!(f, "Score"),
+            MediaSort::TRENDING_DESC => write!(f, "Trending"),
+            MediaSort::FAVOURITES_DESC => write!(f, "Favourites"),
+            MediaSort::START_DATE => write!(f, "Start date"),
+            MediaSort::START_DATE_DESC => write!(f, "Newest"),
+            MediaSort::TITLE_ROMAJI => write!(f, "Title"),
+        }
+    }
+}
+
+/// Filters for `AniListClient::search_media_filtered`; the default matches
+/// everything, so an empty query with no filters browses by popularity.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchFilters {
+    pub query: String,
+    pub genres: Vec<String>,
+    pub excluded_genres: Vec<String>,
+    pub tags: Vec<String>,
+    // Inclusive range of start years
+    pub year_from: Option<i32>,
+    pub year_to: Option<i32>,
+    pub season: Option<MediaSeason>,
+    pub formats: Vec<MediaFormat>,
+    pub status: Option<MediaStatus>,
+    // Inclusive average score range, 0-100
+    pub score_min: Option<i32>,
+    pub score_max: Option<i32>,
+    // Inclusive episode range, applied to chapters for manga
+    pub length_min: Option<i32>,
+    pub length_max: Option<i32>,
+    // ISO 3166-1 alpha-2 code, e.g. "JP" or "KR"
+    pub country: Option<String>,
+    // Some(true) for entries on the viewer's list, Some(false) for the rest
+    pub on_list: Option<bool>,
+    pub sort: Option<MediaSort>,
+    // Adult media is only searched when the viewer opts in
+    pub include_adult: bool,
+}
+
+impl SearchFilters {
+    /// Whether anything besides the text query narrows the results
+    pub fn is_filtered(&self) -> bool {
+        let unfiltered = SearchFilters {
+            query: self.query.clone(),
+            sort: self.sort,
+            include_adult: self.include_adult,
+            ..Default::default()
+        };
+        *self != unfiltered
+    }
+}
+
+/// A possibly incomplete date, as AniList stores start and completion dates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzyDate {
+    pub year: Option<i32>,
+    pub month: Option<i32>,
+    pub day: Option<i32>,
+}
+
+impl FuzzyDate {
+    pub fn is_empty(&self) -> bool {
+        self.year.is_none() && self.month.is_none() && self.day.is_none()
+    }
+
+    /// The `FuzzyDateInt` form, YYYYMMDD with zeroes for unknown parts
+    pub fn to_int(self) -> i64 {
+        self.year.unwrap_or(0) as i64 * 10000
+            + self.month.unwrap_or(0) as i64 * 100
+            + self.day.unwrap_or(0) as i64
+    }
+
+    pub fn from_int(value: i64) -> Self {
+        let part = |n: i64| if n == 0 { None } else { Some(n as i32) };
+        Self {
+            year: part(value / 10000),
+            month: part(value / 100 % 100),
+            day: part(value % 100),
+        }
+    }
+}
+
+impl std::fmt::Display for FuzzyDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.year, self.month, self.day) {
+            (Some(y), Some(m), Some(d)) => write!(f, "{}-{:02}-{:02}", y, m, d),
+            (Some(y), Some(m), None) => write!(f, "{}-{:02}", y, m),
+            (Some(y), None, _) => write!(f, "{}", y),
+            _ => write!(f, "Not set"),
+        }
+    }
+}
+
+/// List entry fields beyond status, score and progress. `None` leaves the
+/// value on AniList untouched; an empty `FuzzyDate` clears a date.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ListEntryDetails {
+    pub notes: Option<String>,
+    pub started_at: Option<FuzzyDate>,
+    pub completed_at: Option<FuzzyDate>,
+    pub repeat: Option<i32>,
+    pub private: Option<bool>,
+    pub hidden_from_status_lists: Option<bool>,
+    // Names of the custom lists the entry belongs to
+    pub custom_lists: Option<Vec<String>>,
+    // One score per advanced scoring category, in the user's category order
+    pub advanced_scores: Option<Vec<f64>>,
+}
+
+impl ListEntryDetails {
+    /// Take the fields `edit` sets, keeping the rest
+    pub fn merge(&mut self, edit: &ListEntryDetails) {
+        let edit = edit.clone();
+        self.notes = edit.notes.or(self.notes.take());
+        self.started_at = edit.started_at.or(self.started_at);
+        self.completed_at = edit.completed_at.or(self.completed_at);
+        self.repeat = edit.repeat.or(self.repeat);
+        self.private = edit.private.or(self.private);
+        self.hidden_from_status_lists = edit
+            .hidden_from_status_lists
+            .or(self.hidden_from_status_lists);
+        self.custom_lists = edit.custom_lists.or(self.custom_lists.take());
+        self.advanced_scores = edit.advanced_scores.or(self.advanced_scores.take());
+    }
+}
+
+/// Something that can be added to or removed from the viewer's favourites
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FavouriteTarget {
+    Anime(i32),
+    Manga(i32),
+    Character(i32),
+    Staff(i32),
+    Studio(i32),
+}
+
+impl FavouriteTarget {
+    pub fn media(media_type: MediaType, id: i32) -> Self {
+        match media_type {
+            MediaType::ANIME => FavouriteTarget::Anime(id),
+            MediaType::MANGA => FavouriteTarget::Manga(id),
+        }
+    }
+}
+
+/// How the viewer scores their list. Scores are stored and sent on AniList's
+/// 100-point scale and only converted to this format for display and editing.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScoreFormat {
+    POINT_100,
+    #[default]
+    POINT_10_DECIMAL,
+    POINT_10,
+    POINT_5,
+    POINT_3,
+}
+
+impl ScoreFormat {
+    /// The highest score in this format
+    pub fn max(self) -> f64 {
+        match self {
+            ScoreFormat::POINT_100 => 100.0,
+            ScoreFormat::POINT_10_DECIMAL | ScoreFormat::POINT_10 => 10.0,
+            ScoreFormat::POINT_5 => 5.0,
+            ScoreFormat::POINT_3 => 3.0,
+        }
+    }
+
+    pub fn step(self) -> f64 {
+        match self {
+            ScoreFormat::POINT_10_DECIMAL => 0.1,
+            _ => 1.0,
+        }
+    }
+
+    /// Convert a 0-100 score to this format, rounding like AniList does
+    pub fn from_raw(self, raw: f64) -> f64 {
+        match self {
+            ScoreFormat::POINT_100 => raw.round(),
+            ScoreFormat::POINT_10_DECIMAL => raw.round() / 10.0,
+            ScoreFormat::POINT_10 => (raw / 10.0).round(),
+            ScoreFormat::POINT_5 if raw > 0.0 => (raw / 20.0).round().max(1.0),
+            ScoreFormat::POINT_3 if raw >= 61.0 => 3.0,
+            ScoreFormat::POINT_3 if raw >= 36.0 => 2.0,
+            ScoreFormat::POINT_3 if raw > 0.0 => 1.0,
+            ScoreFormat::POINT_5 | ScoreFormat::POINT_3 => 0.0,
+        }
+    }
+
+    /// Convert a score in this format back to 0-100
+    pub fn to_raw(self, score: f64) -> f64 {
+        match self {
+            ScoreFormat::POINT_100 => score.round(),
+            ScoreFormat::POINT_10_DECIMAL | ScoreFormat::POINT_10 => (score * 10.0).round(),
+            ScoreFormat::POINT_5 => score.round() * 20.0,
+            ScoreFormat::POINT_3 => match score.round() as i32 {
+                0 => 0.0,
+                1 => 35.0,
+                2 => 60.0,
+                _ => 85.0,
+            },
+        }
+    }
+
+    /// Render a 0-100 score, e.g. "7.5", "★★★☆☆" or ":)"
+    pub fn display(self, raw: f64) -> String {
+        let score = self.from_raw(raw);
+        if score <= 0.0 {
+            return "Not scored".to_string();
+        }
+
+        match self {
+            ScoreFormat::POINT_100 | ScoreFormat::POINT_10 => format!("{}", score),
+            ScoreFormat::POINT_10_DECIMAL => format!("{:.1}", score),
+            ScoreFormat::POINT_5 => {
+                let stars = score as usize;
+                format!("{}{}", "★".repeat(stars), "☆".repeat(5 - stars))
+            }
+            ScoreFormat::POINT_3 => match score as i32 {
+                1 => ":(".to_string(),
+                2 => ":|".to_string(),
+                _ => ":)".to_string(),
+            },
+        }
+    }
+}
+
+/// The notification types the inbox reads. Forum notifications are left out.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotificationType {
+    AIRING,
+    FOLLOWING,
+    ACTIVITY_MESSAGE,
+    ACTIVITY_MENTION,
+    ACTIVITY_REPLY,
+    ACTIVITY_REPLY_SUBSCRIBED,
+    ACTIVITY_LIKE,
+    ACTIVITY_REPLY_LIKE,
+    RELATED_MEDIA_ADDITION,
+    MEDIA_DATA_CHANGE,
+    MEDIA_MERGE,
+    MEDIA_DELETION,
+}
+
+impl NotificationType {
+    pub fn group(self) -> NotificationGroup {
+        match self {
+            NotificationType::AIRING => NotificationGroup::Airing,
+            NotificationType::FOLLOWING => NotificationGroup::Follows,
+            NotificationType::ACTIVITY_MESSAGE
+            | NotificationType::ACTIVITY_MENTION
+            | NotificationType::ACTIVITY_REPLY
+            | NotificationType::ACTIVITY_REPLY_SUBSCRIBED
+            | NotificationType::ACTIVITY_LIKE
+            | NotificationType::ACTIVITY_REPLY_LIKE => NotificationGroup::Activity,
+            NotificationType::RELATED_MEDIA_ADDITION
+            | NotificationType::MEDIA_DATA_CHANGE
+            | NotificationType::MEDIA_MERGE
+            | NotificationType::MEDIA_DELETION => NotificationGroup::Media,
+        }
+    }
+}
+
+/// How the inbox groups and filters notifications
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationGroup {
+    Airing,
+    Activity,
+    Follows,
+    Media,
+}
+
+impl NotificationGroup {
+    pub const ALL: [NotificationGroup; 4] = [
+        NotificationGroup::Airing,
+        NotificationGroup::Activity,
+        NotificationGroup::Follows,
+        NotificationGroup::Media,
+    ];
+
+    pub fn types(self) -> &'static [NotificationType] {
+        match self {
+            NotificationGroup::Airing => &[NotificationType::AIRING],
+            NotificationGroup::Activity => &[
+                NotificationType::ACTIVITY_MESSAGE,
+                NotificationType::ACTIVITY_MENTION,
+                NotificationType::ACTIVITY_REPLY,
+                NotificationType::ACTIVITY_REPLY_SUBSCRIBED,
+                NotificationType::ACTIVITY_LIKE,
+                NotificationType::ACTIVITY_REPLY_LIKE,
+            ],
+            NotificationGroup::Follows => &[NotificationType::FOLLOWING],
+            NotificationGroup::Media => &[
+                NotificationType::RELATED_MEDIA_ADDITION,
+                NotificationType::MEDIA_DATA_CHANGE,
+                NotificationType::MEDIA_MERGE,
+                NotificationType::MEDIA_DELETION,
+            ],
+        }
+    }
+}
+
+impl std::fmt::Display for NotificationGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationGroup::Airing => write!(f, "Airing"),
+            NotificationGroup::Activity => write!(f, "Activity"),
+            NotificationGroup::Follows => write!(f, "Follows"),
+            NotificationGroup::Media => write!(f, "Media Updates"),
+        }
+    }
+}
+
+/// Where a notification leads when opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationLink {
+    Media(i32, MediaType),
+    User(String),
+    Activity(i32),
+}
+
+/// One notification, kept in pieces so the message can be built in the
+/// viewer's title language
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: i32,
+    pub notification_type: NotificationType,
+    pub created_at: i64, // Unix timestamp
+    pub user: Option<String>,
+    pub media: Option<(i32, MediaType, MediaTitle)>,
+    pub episode: Option<i32>,
+    pub activity_id: Option<i32>,
+    // AniList's text following the user or media name, e.g. " liked your activity."
+    pub context: String,
+    pub reason: Option<String>,
+    // Titles removed by a merge or deletion
+    pub deleted_titles: Vec<String>,
+}
+
+impl Notification {
+    pub fn message(&self, title_language: TitleLanguage) -> String {
+        let title = self
+            .media
+            .as_ref()
+            .map(|(_, _, title)| title.preferred(title_language));
+
+        match self.notification_type {
+            NotificationType::AIRING => format!(
+                "Episode {} of {} aired.",
+                self.episode.unwrap_or(0),
+                title.unwrap_or_default()
+            ),
+            NotificationType::MEDIA_DELETION => {
+                format!("{}{}", self.deleted_titles.join(", "), self.context)
+            }
+            _ => match self.notification_type.group() {
+                NotificationGroup::Media => {
+                    format!("{}{}", title.unwrap_or_default(), self.context)
+                }
+                _ => format!(
+                    "{}{}",
+                    self.user.as_deref().unwrap_or("Someone"),
+                    self.context
+                ),
+            },
+        }
+    }
+
+    pub fn link(&self) -> Option<NotificationLink> {
+        match self.notification_type.group() {
+            NotificationGroup::Airing | NotificationGroup::Media => self
+                .media
+                .as_ref()
+                .map(|(id, media_type, _)| NotificationLink::Media(*id, *media_type)),
+            NotificationGroup::Activity => self.activity_id.map(NotificationLink::Activity),
+            NotificationGroup::Follows => self.user.clone().map(NotificationLink::User),
+        }
+    }
+}
+
+/// The kinds of activity in a feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+    Text,
+    List,
+    Message,
+}
+
+impl std::fmt::Display for ActivityKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActivityKind::Text => write!(f, "Status"),
+            ActivityKind::List => write!(f, "List"),
+            ActivityKind::Message => write!(f, "Message"),
+        }
+    }
+}
+
+/// Which activities `AniListClient::get_activities` reads; the default is
+/// the global feed of statuses and list updates
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActivityQuery {
+    // A single activity, the other filters are ignored
+    pub id: Option<i32>,
+    pub user_id: Option<i32>,
+    pub following_only: bool,
+    // None reads statuses and list updates
+    pub kind: Option<ActivityKind>,
+}
+
+/// A status post, list update or message
+#[derive(Debug, Clone)]
+pub struct Activity {
+    pub id: i32,
+    pub kind: ActivityKind,
+    // Who posted it, or sent it for messages
+    pub user: String,
+    pub created_at: i64, // Unix timestamp
+    // The post itself, empty for list updates
+    pub text: String,
+    // For list updates, e.g. "watched episode" and "5"
+    pub status: Option<String>,
+    pub progress: Option<String>,
+    pub media: Option<(i32, MediaType, MediaTitle)>,
+    pub reply_count: i32,
+    pub like_count: i32,
+    pub is_liked: bool,
+}
+
+impl Activity {
+    /// What the user did, to follow their name: "watched episode 5 of ..."
+    /// for list updates and the post for everything else
+    pub fn summary(&self, title_language: TitleLanguage) -> String {
+        let Some((_, _, title)) = &self.media else {
+            return self.text.clone();
+        };
+        let status = self.status.as_deref().unwrap_or("updated");
+        let title = title.preferred(title_language);
+
+        match self.progress.as_deref().filter(|p| !p.is_empty()) {
+            Some(progress) => format!("{} {} of {}", status, progress, title),
+            None => format!("{} {}", status, title),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActivityReply {
+    pub id: i32,
+    pub user: String,
+    pub text: String,
+    pub created_at: i64, // Unix timestamp
+    pub like_count: i32,
+    pub is_liked: bool,
+}
+
+/// Something the viewer can like
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LikeTarget {
+    Activity(i32),
+    ActivityReply(i32),
+}
+
+/// Which side of a user's social graph to list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowList {
+    Followers,
+    Following,
+}
+
+impl std::fmt::Display for FollowList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FollowList::Followers => write!(f, "Followers"),
+            FollowList::Following => write!(f, "Following"),
+        }
+    }
+}
+
+/// A user in a followers or following list
+#[derive(Debug, Clone, PartialEq)]
+pub struct FollowUser {
+    pub id: i32,
+    pub name: String,
+    pub avatar_url: String,
+    // Whether the viewer follows them
+    pub is_following: bool,
+}
+
+/// How the list screen lays out a tab
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ListLayout {
+    Grid,
+    Table,
+    #[default]
+    Detailed,
+}
+
+impl ListLayout {
+    pub const ALL: [ListLayout; 3] = [ListLayout::Grid, ListLayout::Table, ListLayout::Detailed];
+}
+
+impl std::fmt::Display for ListLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListLayout::Grid => write!(f, "Grid"),
+            ListLayout::Table => write!(f, "Table"),
+            ListLayout::Detailed => write!(f, "Detailed"),
+        }
+    }
+}
+
+/// List and display preferences from the viewer's AniList settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ViewerPreferences {
+    #[serde(default)]
+    pub score_format: ScoreFormat,
+    #[serde(default)]
+    pub title_language: TitleLanguage,
+    // Adult media is left out of search and browsing unless this is set
+    #[serde(default)]
+    pub display_adult_content: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scores_round_trip_through_every_format() {
+        for format in [
+            ScoreFormat::POINT_100,
+            ScoreFormat::POINT_10_DECIMAL,
+            ScoreFormat::POINT_10,
+            ScoreFormat::POINT_5,
+            ScoreFormat::POINT_3,
+        ] {
+            let steps = (format.max() / format.step()).round() as i32;
+            for step in 0..=steps {
+                let score = (step as f64 * format.step() * 10.0).round() / 10.0;
+                let raw = format.to_raw(score);
+                assert!((0.0..=100.0).contains(&raw), "{:?} {}", format, score);
+                assert_eq!(format.from_raw(raw), score, "{:?} {}", format, score);
+            }
+        }
+
+        // Scores set in one format show up sensibly in another
+        assert_eq!(ScoreFormat::POINT_5.from_raw(85.0), 4.0);
+        assert_eq!(ScoreFormat::POINT_3.from_raw(85.0), 3.0);
+        assert_eq!(ScoreFormat::POINT_10_DECIMAL.display(85.0), "8.5");
+        assert_eq!(ScoreFormat::POINT_5.display(60.0), "★★★☆☆");
+        assert_eq!(ScoreFormat::POINT_3.display(0.0), "Not scored");
+    }
+
+    #[test]
+    fn test_preferred_title_falls_back_when_missing() {
+        let title = MediaTitle {
+            romaji: Some("Shingeki no Kyojin".to_string()),
+            english: Some(String::new()),
+            native: Some("進撃の巨人".to_string()),
+            romaji_stylised: Some("Shingeki no Kyojin!".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(title.preferred(TitleLanguage::NATIVE), "進撃の巨人");
+        assert_eq!(
+            title.preferred(TitleLanguage::ROMAJI_STYLISED),
+            "Shingeki no Kyojin!"
+        );
+        assert_eq!(title.preferred(TitleLanguage::ROMAJI), "Shingeki no Kyojin");
+        assert_eq!(
+            title.preferred(TitleLanguage::NATIVE_STYLISED),
+            "進撃の巨人"
+        );
+        assert_eq!(
+            title.preferred(TitleLanguage::ENGLISH_STYLISED),
+            "Shingeki no Kyojin"
+        );
+
+        let untitled = MediaTitle::default();
+        assert_eq!(untitled.preferred(TitleLanguage::ROMAJI), "Unknown Title");
+    }
+}
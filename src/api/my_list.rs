@@ -0,0 +1,555 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashSet};
+
+use crate::api::client::{user_anime_list, Json};
+use crate::api::models::{
+    FuzzyDate, MediaFormat, MediaListEntry, MediaTitle, MediaType, TitleLanguage,
+};
+
+/// The status tabs, in the order AniList shows them
+pub const STATUSES: [&str; 6] = [
+    "CURRENT",
+    "REPEATING",
+    "PLANNING",
+    "COMPLETED",
+    "PAUSED",
+    "DROPPED",
+];
+
+pub fn status_label(status: &str, media_type: MediaType) -> &'static str {
+    match (status, media_type) {
+        ("CURRENT", MediaType::ANIME) => "Watching",
+        ("CURRENT", MediaType::MANGA) => "Reading",
+        ("REPEATING", MediaType::ANIME) => "Rewatching",
+        ("REPEATING", MediaType::MANGA) => "Rereading",
+        ("PLANNING", _) => "Planning",
+        ("COMPLETED", _) => "Completed",
+        ("PAUSED", _) => "Paused",
+        ("DROPPED", _) => "Dropped",
+        _ => "Other",
+    }
+}
+
+/// One entry of the viewer's list with everything the list screen sorts and
+/// filters on
+#[derive(Debug, Clone)]
+pub struct ListItem {
+    pub entry_id: i32,
+    pub media_id: i32,
+    pub title: MediaTitle,
+    pub cover_url: Option<String>,
+    pub status: String,
+    // 0-100, 0 when unscored
+    pub score: f64,
+    pub progress: i32,
+    pub progress_volumes: i32,
+    // Episodes or chapters, when known
+    pub total: Option<i32>,
+    pub format: Option<String>,
+    pub genres: Vec<String>,
+    pub year: Option<i32>,
+    // The custom lists the entry is in
+    pub custom_lists: Vec<String>,
+    pub hidden_from_status_lists: bool,
+    pub started_at: FuzzyDate,
+    pub updated_at: i64, // Unix timestamp
+    pub next_airing_at: Option<i64>,
+    pub next_episode: Option<i32>,
+}
+
+/// The viewer's whole list of one media type
+#[derive(Debug, Clone, Default)]
+pub struct MyList {
+    pub items: Vec<ListItem>,
+    // In the order the user arranged them on AniList
+    pub custom_lists: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ListTab {
+    Status(String),
+    Custom(String),
+}
+
+impl ListTab {
+    /// A stable name to remember settings of the tab by, e.g. `ANIME/CURRENT`
+    pub fn key(&self, media_type: MediaType) -> String {
+        match self {
+            ListTab::Status(status) => format!("{:?}/{}", media_type, status),
+            ListTab::Custom(name) => format!("{:?}/custom/{}", media_type, name),
+        }
+    }
+
+    fn contains(&self, item: &ListItem) -> bool {
+        match self {
+            ListTab::Status(status) => item.status == *status && !item.hidden_from_status_lists,
+            ListTab::Custom(name) => item.custom_lists.contains(name),
+        }
+    }
+}
+
+impl Default for ListTab {
+    fn default() -> Self {
+        ListTab::Status("CURRENT".to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListSort {
+    Title,
+    Score,
+    Progress,
+    #[default]
+    UpdatedAt,
+    StartedAt,
+    Airing,
+}
+
+impl ListSort {
+    pub const ALL: [ListSort; 6] = [
+        ListSort::Title,
+        ListSort::Score,
+        ListSort::Progress,
+        ListSort::UpdatedAt,
+        ListSort::StartedAt,
+        ListSort::Airing,
+    ];
+}
+
+impl std::fmt::Display for ListSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListSort::Title => write!(f, "Title"),
+            ListSort::Score => write!(f, "Score"),
+            ListSort::Progress => write!(f, "Progress"),
+            ListSort::UpdatedAt => write!(f, "Last Updated"),
+            ListSort::StartedAt => write!(f, "Start Date"),
+            ListSort::Airing => write!(f, "Airing Time"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListFilter {
+    pub format: Option<MediaFormat>,
+    pub genre: Option<String>,
+    pub year: Option<i32>,
+    // Matched against every title, ignoring case
+    pub text: String,
+}
+
+impl ListFilter {
+    pub fn matches(&self, item: &ListItem) -> bool {
+        if let Some(format) = self.format {
+            if item.format.as_deref() != Some(format!("{:?}", format).as_str()) {
+                return false;
+            }
+        }
+        if let Some(genre) = &self.genre {
+            if !item.genres.contains(genre) {
+                return false;
+            }
+        }
+        if self.year.is_some() && item.year != self.year {
+            return false;
+        }
+
+        let text = self.text.trim().to_lowercase();
+        text.is_empty()
+            || [&item.title.romaji, &item.title.english, &item.title.native]
+                .into_iter()
+                .flatten()
+                .any(|title| title.to_lowercase().contains(&text))
+    }
+}
+
+/// A change made to several selected entries at once
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkEdit {
+    Status(String),
+    // 0-100
+    Score(f64),
+    // Out of `from` when it is set, otherwise just into `to`
+    MoveToList { from: Option<String>, to: String },
+    Delete,
+}
+
+impl BulkEdit {
+    /// The custom lists `item` ends up in, for moves
+    pub fn custom_lists_for(&self, item: &ListItem) -> Option<Vec<String>> {
+        let BulkEdit::MoveToList { from, to } = self else {
+            return None;
+        };
+
+        let mut lists: Vec<String> = item
+            .custom_lists
+            .iter()
+            .filter(|list| Some(*list) != from.as_ref() && *list != to)
+            .cloned()
+            .collect();
+        lists.push(to.clone());
+        Some(lists)
+    }
+}
+
+impl MyList {
+    pub fn from_response(data: user_anime_list::ResponseData) -> Self {
+        let lists: Vec<_> = data
+            .media_list_collection
+            .and_then(|collection| collection.lists)
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let custom_lists = lists
+            .iter()
+            .filter(|list| list.is_custom_list == Some(true))
+            .filter_map(|list| list.name.clone())
+            .collect();
+
+        // Custom lists repeat entries that are already in a status list
+        let mut seen = HashSet::new();
+        let items = lists
+            .into_iter()
+            .flat_map(|list| list.entries.unwrap_or_default())
+            .flatten()
+            .filter(|entry| seen.insert(entry.id))
+            .map(list_item)
+            .collect();
+
+        Self {
+            items,
+            custom_lists,
+        }
+    }
+
+    /// The list as stored locally, which has no formats, years, custom lists
+    /// or airing times
+    pub fn from_entries(entries: Vec<MediaListEntry>) -> Self {
+        let items = entries
+            .into_iter()
+            .filter_map(|entry| {
+                let media = entry.media?;
+                Some(ListItem {
+                    entry_id: entry.id,
+                    media_id: entry.media_id,
+                    title: media.title,
+                    cover_url: media.cover_image.and_then(|img| img.large.or(img.medium)),
+                    status: entry.status,
+                    score: entry.score.unwrap_or(0.0),
+                    progress: entry.progress.unwrap_or(0),
+                    progress_volumes: entry.progress_volumes.unwrap_or(0),
+                    total: media.episodes.or(media.chapters),
+                    format: media.format,
+                    genres: media.genres.unwrap_or_default(),
+                    year: None,
+                    custom_lists: Vec::new(),
+                    hidden_from_status_lists: false,
+                    started_at: FuzzyDate::default(),
+                    updated_at: entry.updated_at,
+                    next_airing_at: None,
+                    next_episode: None,
+                })
+            })
+            .collect();
+
+        Self {
+            items,
+            custom_lists: Vec::new(),
+        }
+    }
+
+    pub fn item(&self, media_id: i32) -> Option<&ListItem> {
+        self.items.iter().find(|item| item.media_id == media_id)
+    }
+
+    pub fn item_mut(&mut self, media_id: i32) -> Option<&mut ListItem> {
+        self.items.iter_mut().find(|item| item.media_id == media_id)
+    }
+
+    /// Apply an edit that was saved for the given media, so the list shows it
+    /// without being fetched again
+    pub fn apply(&mut self, media_ids: &HashSet<i32>, edit: &BulkEdit, updated_at: i64) {
+        if *edit == BulkEdit::Delete {
+            self.items
+                .retain(|item| !media_ids.contains(&item.media_id));
+            return;
+        }
+
+        for item in self
+            .items
+            .iter_mut()
+            .filter(|item| media_ids.contains(&item.media_id))
+        {
+            match edit {
+                BulkEdit::Status(status) => item.status = status.clone(),
+                BulkEdit::Score(score) => item.score = *score,
+                BulkEdit::MoveToList { .. } => {
+                    item.custom_lists = edit.custom_lists_for(item).unwrap_or_default()
+                }
+                BulkEdit::Delete => {}
+            }
+            item.updated_at = updated_at;
+        }
+    }
+
+    /// Every status tab, then the custom lists
+    pub fn tabs(&self) -> Vec<ListTab> {
+        STATUSES
+            .iter()
+            .map(|status| ListTab::Status(status.to_string()))
+            .chain(self.custom_lists.iter().cloned().map(ListTab::Custom))
+            .collect()
+    }
+
+    pub fn count(&self, tab: &ListTab) -> usize {
+        self.items.iter().filter(|item| tab.contains(item)).count()
+    }
+
+    pub fn genres(&self) -> Vec<String> {
+        let genres: BTreeSet<&String> = self.items.iter().flat_map(|i| &i.genres).collect();
+        genres.into_iter().cloned().collect()
+    }
+
+    /// Release years on the list, newest first
+    pub fn years(&self) -> Vec<i32> {
+        let years: BTreeSet<i32> = self.items.iter().filter_map(|i| i.year).collect();
+        years.into_iter().rev().collect()
+    }
+
+    /// The entries of `tab` that pass `filter`, in `sort` order. Ties and
+    /// entries missing the sorted value go by title.
+    pub fn view(
+        &self,
+        tab: &ListTab,
+        filter: &ListFilter,
+        sort: ListSort,
+        language: TitleLanguage,
+    ) -> Vec<&ListItem> {
+        let mut items: Vec<&ListItem> = self
+            .items
+            .iter()
+            .filter(|item| tab.contains(item) && filter.matches(item))
+            .collect();
+
+        let title = |item: &ListItem| item.title.preferred(language).to_lowercase();
+        items.sort_by_cached_key(|item| title(item));
+        items.sort_by(|a, b| match sort {
+            ListSort::Title => Ordering::Equal,
+            ListSort::Score => b.score.total_cmp(&a.score),
+            ListSort::Progress => b.progress.cmp(&a.progress),
+            ListSort::UpdatedAt => b.updated_at.cmp(&a.updated_at),
+            // Unknown dates go last
+            ListSort::StartedAt => (!b.started_at.is_empty(), b.started_at.to_int())
+                .cmp(&(!a.started_at.is_empty(), a.started_at.to_int())),
+            ListSort::Airing => match (a.next_airing_at, b.next_airing_at) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        });
+
+        items
+    }
+}
+
+fn list_item(entry: user_anime_list::UserAnimeListMediaListCollectionListsEntries) -> ListItem {
+    let media = entry.media;
+    let year = media.as_ref().and_then(|m| {
+        m.season_year
+            .or(m.start_date.as_ref().and_then(|d| d.year))
+            .map(|y| y as i32)
+    });
+    let next_airing = media.as_ref().and_then(|m| m.next_airing_episode.as_ref());
+
+    ListItem {
+        entry_id: entry.id as i32,
+        media_id: entry.media_id as i32,
+        title: media
+            .as_ref()
+            .and_then(|m| m.title.clone())
+            .map(MediaTitle::from)
+            .unwrap_or_default(),
+        cover_url: media
+            .as_ref()
+            .and_then(|m| m.cover_image.as_ref())
+            .and_then(|img| img.large.clone().or(img.medium.clone())),
+        status: entry
+            .status
+            .map_or("UNKNOWN".to_string(), |s| format!("{:?}", s)),
+        score: entry.score.unwrap_or(0.0),
+        progress: entry.progress.unwrap_or(0) as i32,
+        progress_volumes: entry.progress_volumes.unwrap_or(0) as i32,
+        total: media
+            .as_ref()
+            .and_then(|m| m.episodes.or(m.chapters))
+            .map(|t| t as i32),
+        format: media
+            .as_ref()
+            .and_then(|m| m.format.as_ref())
+            .map(|f| format!("{:?}", f)),
+        genres: media
+            .as_ref()
+            .and_then(|m| m.genres.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .collect(),
+        year,
+        custom_lists: enabled_custom_lists(entry.custom_lists.as_ref()),
+        hidden_from_status_lists: entry.hidden_from_status_lists.unwrap_or(false),
+        started_at: entry
+            .started_at
+            .map(|d| FuzzyDate {
+                year: d.year.map(|y| y as i32),
+                month: d.month.map(|m| m as i32),
+                day: d.day.map(|d| d as i32),
+            })
+            .unwrap_or_default(),
+        updated_at: entry.updated_at.unwrap_or(0),
+        next_airing_at: next_airing.map(|n| n.airing_at),
+        next_episode: next_airing.map(|n| n.episode as i32),
+    }
+}
+
+/// The custom lists an entry is on; `customLists(asArray: true)` is a list of
+/// `{ name, enabled }`
+pub fn enabled_custom_lists(lists: Option<&Json>) -> Vec<String> {
+    lists
+        .and_then(Json::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|list| list["enabled"].as_bool() == Some(true))
+        .filter_map(|list| list["name"].as_str().map(str::to_string))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(media_id: i32, title: &str, status: &str) -> ListItem {
+        ListItem {
+            entry_id: media_id * 10,
+            media_id,
+            title: MediaTitle {
+                romaji: Some(title.to_string()),
+                english: None,
+                native: None,
+                ..Default::default()
+            },
+            cover_url: None,
+            status: status.to_string(),
+            score: 0.0,
+            progress: 0,
+            progress_volumes: 0,
+            total: None,
+            format: Some("TV".to_string()),
+            genres: Vec::new(),
+            year: None,
+            custom_lists: Vec::new(),
+            hidden_from_status_lists: false,
+            started_at: FuzzyDate::default(),
+            updated_at: 0,
+            next_airing_at: None,
+            next_episode: None,
+        }
+    }
+
+    #[test]
+    fn test_view_filters_and_sorts_a_tab() {
+        let mut frieren = item(1, "Sousou no Frieren", "CURRENT");
+        frieren.score = 90.0;
+        frieren.year = Some(2023);
+        frieren.genres = vec!["Fantasy".to_string()];
+        frieren.next_airing_at = Some(2000);
+        frieren.custom_lists = vec!["Favourites".to_string()];
+        let mut apothecary = item(2, "Kusuriya no Hitorigoto", "CURRENT");
+        apothecary.score = 90.0;
+        apothecary.year = Some(2023);
+        apothecary.genres = vec!["Mystery".to_string()];
+        apothecary.next_airing_at = Some(1000);
+        let mut movie = item(3, "Kimi no Na wa.", "CURRENT");
+        movie.format = Some("MOVIE".to_string());
+        movie.year = Some(2016);
+        movie.score = 95.0;
+        // Only in a custom list
+        let mut hidden = item(4, "Hidden", "CURRENT");
+        hidden.hidden_from_status_lists = true;
+        hidden.custom_lists = vec!["Favourites".to_string()];
+        let planned = item(5, "Planned", "PLANNING");
+
+        let list = MyList {
+            items: vec![frieren, apothecary, movie, hidden, planned],
+            custom_lists: vec!["Favourites".to_string()],
+        };
+        let ids = |items: Vec<&ListItem>| items.iter().map(|i| i.media_id).collect::<Vec<_>>();
+        let current = ListTab::Status("CURRENT".to_string());
+        let language = TitleLanguage::ROMAJI;
+
+        // Equal scores fall back to the title
+        assert_eq!(
+            ids(list.view(&current, &ListFilter::default(), ListSort::Score, language)),
+            vec![3, 2, 1]
+        );
+        assert_eq!(
+            ids(list.view(&current, &ListFilter::default(), ListSort::Airing, language)),
+            vec![2, 1, 3]
+        );
+        assert_eq!(list.count(&ListTab::Custom("Favourites".to_string())), 2);
+
+        let tv_2023 = ListFilter {
+            format: Some(MediaFormat::TV),
+            year: Some(2023),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(list.view(&current, &tv_2023, ListSort::Title, language)),
+            vec![2, 1]
+        );
+        let search = ListFilter {
+            genre: Some("Fantasy".to_string()),
+            text: "frieren".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(list.view(&current, &search, ListSort::Title, language)),
+            vec![1]
+        );
+        assert_eq!(list.years(), vec![2023, 2016]);
+    }
+
+    #[test]
+    fn test_bulk_edits_apply_to_the_selected_items() {
+        let mut favourite = item(1, "Favourite", "CURRENT");
+        favourite.custom_lists = vec!["Favourites".to_string(), "Rewatch".to_string()];
+        let mut list = MyList {
+            items: vec![
+                favourite,
+                item(2, "Other", "CURRENT"),
+                item(3, "Kept", "CURRENT"),
+            ],
+            custom_lists: vec!["Favourites".to_string(), "Rewatch".to_string()],
+        };
+        let selected: HashSet<i32> = [1, 2].into();
+
+        list.apply(&selected, &BulkEdit::Status("PAUSED".to_string()), 100);
+        assert_eq!(list.item(1).unwrap().status, "PAUSED");
+        assert_eq!(list.item(2).unwrap().updated_at, 100);
+        assert_eq!(list.item(3).unwrap().status, "CURRENT");
+
+        let moved = BulkEdit::MoveToList {
+            from: Some("Favourites".to_string()),
+            to: "Rewatch".to_string(),
+        };
+        list.apply(&selected, &moved, 200);
+        assert_eq!(list.item(1).unwrap().custom_lists, vec!["Rewatch"]);
+        assert_eq!(list.item(2).unwrap().custom_lists, vec!["Rewatch"]);
+
+        list.apply(&selected, &BulkEdit::Delete, 300);
+        assert_eq!(list.items.len(), 1);
+        assert!(list.item(3).is_some());
+    }
+}
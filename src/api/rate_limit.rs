@@ -0,0 +1,294 @@
+use log::{debug, warn};
+use rand::Rng;
+use reqwest::header::HeaderMap;
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+// AniList allows 90 requests per minute per client
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 90;
+
+/// Controls how transient failures (429, 5xx, connection errors) are retried
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff with full jitter for the given (zero-based) attempt
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self.backoff_ceiling(attempt);
+        let millis = rand::thread_rng().gen_range(0..=ceiling.as_millis() as u64);
+        Duration::from_millis(millis)
+    }
+
+    fn backoff_ceiling(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+/// Rate limit information reported by AniList in the response headers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitHeaders {
+    pub limit: Option<u32>,
+    pub remaining: Option<u32>,
+    pub reset: Option<Duration>,
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimitHeaders {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let parse = |name: &str| -> Option<u64> {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+
+        // X-RateLimit-Reset is a unix timestamp, convert it to a delay from now
+        let reset = parse("x-ratelimit-reset").map(|timestamp| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            Duration::from_secs(timestamp.saturating_sub(now))
+        });
+
+        Self {
+            limit: parse("x-ratelimit-limit").map(|v| v as u32),
+            remaining: parse("x-ratelimit-remaining").map(|v| v as u32),
+            reset,
+            retry_after: parse("retry-after").map(Duration::from_secs),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RateLimitState {
+    limit: u32,
+    window: Duration,
+    sent: VecDeque<Instant>,
+    blocked_until: Option<Instant>,
+}
+
+/// Paces outgoing requests so the per-minute budget is never exceeded.
+///
+/// Callers that find the budget spent sleep without holding the state and
+/// check again when they wake, so a new `Retry-After` applies to them too.
+#[derive(Debug)]
+pub struct RateLimiter {
+    state: Mutex<RateLimitState>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            state: Mutex::new(RateLimitState {
+                limit: limit.max(1),
+                window,
+                sent: VecDeque::new(),
+                blocked_until: None,
+            }),
+        }
+    }
+
+    /// Wait until a request may be sent and reserve a slot for it
+    pub async fn acquire(&self) {
+        loop {
+            // The lock is only held to check the budget, never while sleeping,
+            // so `update` and `block` get through while callers wait
+            let until = match Self::reserve(&mut *self.state.lock().await, Instant::now()) {
+                Ok(()) => return,
+                Err(until) => until,
+            };
+            tokio::time::sleep_until(until).await;
+        }
+    }
+
+    // Take a slot at `now`, or say when it is worth checking again
+    fn reserve(state: &mut RateLimitState, now: Instant) -> Result<(), Instant> {
+        // Server told us to back off
+        if let Some(until) = state.blocked_until {
+            if until > now {
+                debug!("Rate limited, waiting {:?}", until - now);
+                return Err(until);
+            }
+            state.blocked_until = None;
+        }
+
+        // Forget requests that fell out of the window
+        let window = state.window;
+        while let Some(sent_at) = state.sent.front() {
+            if now.duration_since(*sent_at) >= window {
+                state.sent.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        // Local budget: never send more than `limit` requests per window
+        if state.sent.len() as u32 >= state.limit {
+            if let Some(oldest) = state.sent.front() {
+                let until = *oldest + window;
+                debug!("Request budget used up, waiting {:?}", until - now);
+                return Err(until);
+            }
+        }
+
+        state.sent.push_back(now);
+        Ok(())
+    }
+
+    /// Sync our view of the budget with what the server reported
+    pub async fn update(&self, headers: &RateLimitHeaders) {
+        let mut state = self.state.lock().await;
+
+        if let Some(limit) = headers.limit {
+            state.limit = limit.max(1);
+        }
+
+        // The server's count is authoritative; pause until the reset if it is spent
+        if headers.remaining == Some(0) {
+            let delay = headers.reset.unwrap_or(state.window);
+            Self::block_for(&mut state, delay);
+        }
+
+        if let Some(retry_after) = headers.retry_after {
+            warn!("AniList asked us to retry after {:?}", retry_after);
+            Self::block_for(&mut state, retry_after);
+        }
+    }
+
+    /// Pause every queued request for at least `delay`
+    pub async fn block(&self, delay: Duration) {
+        let mut state = self.state.lock().await;
+        Self::block_for(&mut state, delay);
+    }
+
+    fn block_for(state: &mut RateLimitState, delay: Duration) {
+        let until = Instant::now() + delay;
+        if state.blocked_until.is_none_or(|current| current < until) {
+            state.blocked_until = Some(until);
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_REQUESTS_PER_MINUTE, Duration::from_secs(60))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_backoff_is_bounded() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        };
+
+        assert_eq!(policy.backoff_ceiling(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff_ceiling(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff_ceiling(10), Duration::from_secs(1));
+        assert_eq!(policy.backoff_ceiling(64), Duration::from_secs(1));
+
+        for attempt in 0..8 {
+            assert!(policy.backoff(attempt) <= policy.backoff_ceiling(attempt));
+        }
+    }
+
+    #[test]
+    fn test_parse_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", HeaderValue::from_static("90"));
+        headers.insert("X-RateLimit-Remaining", HeaderValue::from_static("0"));
+        headers.insert("Retry-After", HeaderValue::from_static("30"));
+
+        let parsed = RateLimitHeaders::from_headers(&headers);
+        assert_eq!(parsed.limit, Some(90));
+        assert_eq!(parsed.remaining, Some(0));
+        assert_eq!(parsed.retry_after, Some(Duration::from_secs(30)));
+        assert_eq!(parsed.reset, None);
+
+        // Missing or malformed headers are ignored
+        let mut headers = HeaderMap::new();
+        headers.insert("Retry-After", HeaderValue::from_static("soon"));
+        assert_eq!(
+            RateLimitHeaders::from_headers(&headers),
+            RateLimitHeaders::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_paces_requests() {
+        let limiter = RateLimiter::new(2, Duration::from_millis(200));
+        let start = Instant::now();
+
+        // The first two requests go through immediately
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        // The third has to wait for the window to roll over
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_honours_retry_after() {
+        let limiter = RateLimiter::new(10, Duration::from_secs(60));
+        let start = Instant::now();
+
+        limiter
+            .update(&RateLimitHeaders {
+                retry_after: Some(Duration::from_millis(150)),
+                ..Default::default()
+            })
+            .await;
+
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[tokio::test]
+    async fn test_waiting_callers_dont_hold_the_limiter() {
+        let limiter = std::sync::Arc::new(RateLimiter::new(1, Duration::from_millis(300)));
+        limiter.acquire().await;
+
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Reaches the state while the other caller sleeps, and holds it back
+        let start = Instant::now();
+        limiter.block(Duration::from_millis(500)).await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        waiting.await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(500));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::utils::error::AppError;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, StatusCode};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+pub const DEFAULT_ENDPOINT: &str = "https://graphql.anilist.co";
+
+/// A GraphQL request ready to be sent
+#[derive(Debug, Clone)]
+pub struct TransportRequest {
+    pub body: Value,
+    pub bearer_token: Option<String>,
+}
+
+impl TransportRequest {
+    pub fn operation_name(&self) -> Option<&str> {
+        self.body
+            .get("operationName")
+            .and_then(|name| name.as_str())
+    }
+}
+
+/// The raw HTTP response; decoding is left to the client
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<TransportResponse, AppError>> + Send + 'a>>;
+
+/// Sends GraphQL requests on behalf of `AniListClient` and `AuthManager`.
+///
+/// Failures that are worth retrying (connection resets, timeouts) should be
+/// reported as `AppError::NetworkError`.
+pub trait Transport: Debug + Send + Sync {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_>;
+}
+
+/// Talks to a real GraphQL server over HTTP
+#[derive(Debug, Clone)]
+pub struct HttpTransport {
+    client: Client,
+    endpoint: String,
+}
+
+impl HttpTransport {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(),
+            endpoint: endpoint.into(),
+        }
+    }
+
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+}
+
+impl Default for HttpTransport {
+    fn default() -> Self {
+        Self::new(DEFAULT_ENDPOINT)
+    }
+}
+
+impl Transport for HttpTransport {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let mut request_builder = self.client.post(&self.endpoint).json(&request.body);
+
+            if let Some(token) = &request.bearer_token {
+                request_builder =
+                    request_builder.header("Authorization", format!("Bearer {}", token));
+            }
+
+            let response = request_builder.send().await?;
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?.to_vec();
+
+            Ok(TransportResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+/// In-process stand-in for the AniList API that replays canned responses.
+///
+/// Responses are keyed by GraphQL operation name (`AnimeDetails`,
+/// `AnimeSearch`, ...) and served in the order they were added. The last
+/// response for an operation is repeated once the queue runs dry.
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: Mutex<HashMap<String, VecDeque<TransportResponse>>>,
+    requests: Mutex<Vec<TransportRequest>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a successful response with the given `data` payload
+    pub fn with_data(self, operation: &str, data: Value) -> Self {
+        self.with_response(
+            operation,
+            StatusCode::OK,
+            serde_json::json!({ "data": data }),
+        )
+    }
+
+    /// Queue an arbitrary response, e.g. a 429 or a GraphQL error body
+    pub fn with_response(self, operation: &str, status: StatusCode, body: Value) -> Self {
+        self.push_response(
+            operation,
+            TransportResponse {
+                status,
+                headers: HeaderMap::new(),
+                body: body.to_string().into_bytes(),
+            },
+        );
+        self
+    }
+
+    pub fn push_response(&self, operation: &str, response: TransportResponse) {
+        if let Ok(mut responses) = self.responses.lock() {
+            responses
+                .entry(operation.to_string())
+                .or_default()
+                .push_back(response);
+        }
+    }
+
+    /// Every request received so far, oldest first
+    pub fn requests(&self) -> Vec<TransportRequest> {
+        self.requests
+            .lock()
+            .map(|requests| requests.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        let operation = request.operation_name().unwrap_or_default().to_string();
+
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.push(request);
+        }
+
+        let response = match self.responses.lock() {
+            Ok(mut responses) => match responses.get_mut(&operation) {
+                Some(queue) if queue.len() > 1 => queue.pop_front(),
+                Some(queue) => queue.front().cloned(),
+                None => None,
+            },
+            Err(_) => None,
+        };
+
+        Box::pin(async move {
+            response.ok_or_else(|| {
+                AppError::ApiError(format!("No mock response for operation '{}'", operation))
+            })
+        })
+    }
+}
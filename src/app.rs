@@ -1,5 +1,6 @@
 use crate::api::auth::AuthManager;
 use crate::api::client::AniListClient;
+use crate::api::transport::HttpTransport;
 use crate::data::database::Database;
 use crate::ui::AniListApp;
 use crate::utils::config::{load_config, Config};
@@ -33,9 +34,11 @@ impl App {
         let db = Database::new()?;
         info!("Database initialized");
 
-        // Create auth manager
-        let auth_manager = AuthManager::new(config.auth_config.clone(), db.clone());
-        debug!("Auth manager created");
+        // Create auth manager, talking to the configured GraphQL endpoint
+        let transport = Arc::new(HttpTransport::new(config.api_endpoint.clone()));
+        let auth_manager =
+            AuthManager::new(config.auth_config.clone(), db.clone()).with_transport(transport);
+        debug!("Auth manager created (endpoint: {})", config.api_endpoint);
 
         // Initialize API client with auth manager
         let client = AniListClient::with_auth_manager(auth_manager.clone());
@@ -57,7 +60,7 @@ impl App {
         self.auth_manager.clone()
     }
 
-    pub fn _get_database(&self) -> &Database {
+    pub fn get_database(&self) -> &Database {
         &self.db
     }
 
@@ -19,12 +19,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         redirect_uri: config.auth_config.redirect_uri.clone(),
     };
 
-    // Initialize auth manager
-    let auth_manager = AuthManager::new(auth_config);
-
     // Initialize database for token storage
     let db = Database::new()?;
 
+    // Initialize auth manager
+    let auth_manager = AuthManager::new(auth_config, db.clone());
+
     // Try to authenticate
     println!("Starting authentication process...");
     println!("This will open a browser window. Please sign in to AniList when prompted.");
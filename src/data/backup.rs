@@ -0,0 +1,308 @@
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use log::info;
+use rusqlite::backup::{Backup, Progress};
+use rusqlite::{params, Connection, DatabaseName, OpenFlags};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::data::migrations::{self, SCHEMA_VERSION};
+use crate::utils::error::AppError;
+
+// anilist-20240101T120000123-scheduled.db
+const PREFIX: &str = "anilist-";
+const EXTENSION: &str = "db";
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%3f";
+
+/// Snapshots taken before schema upgrades that are kept
+pub const PRE_MIGRATION_KEEP: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupReason {
+    Scheduled,
+    Manual,
+    PreMigration,
+    PreRestore,
+}
+
+impl BackupReason {
+    const ALL: [BackupReason; 4] = [
+        BackupReason::Scheduled,
+        BackupReason::Manual,
+        BackupReason::PreMigration,
+        BackupReason::PreRestore,
+    ];
+
+    // The tag in the file name
+    fn tag(self) -> &'static str {
+        match self {
+            BackupReason::Scheduled => "scheduled",
+            BackupReason::Manual => "manual",
+            BackupReason::PreMigration => "pre-migration",
+            BackupReason::PreRestore => "pre-restore",
+        }
+    }
+}
+
+impl std::fmt::Display for BackupReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackupReason::Scheduled => write!(f, "scheduled"),
+            BackupReason::Manual => write!(f, "manual"),
+            BackupReason::PreMigration => write!(f, "before upgrade"),
+            BackupReason::PreRestore => write!(f, "before restore"),
+        }
+    }
+}
+
+/// A copy of the database in the backup folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+    pub reason: BackupReason,
+    pub size: u64,
+}
+
+impl Snapshot {
+    // Only files this module wrote, told apart by their name
+    fn from_path(path: PathBuf) -> Option<Self> {
+        if path.extension()? != EXTENSION {
+            return None;
+        }
+        let stem = path.file_stem()?.to_str()?.strip_prefix(PREFIX)?;
+        let (timestamp, tag) = stem.split_once('-')?;
+
+        let reason = BackupReason::ALL
+            .into_iter()
+            .find(|reason| reason.tag() == tag)?;
+        let created_at = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+        let size = fs::metadata(&path).ok()?.len();
+
+        Some(Self {
+            created_at: Utc.from_utc_datetime(&created_at),
+            path,
+            reason,
+            size,
+        })
+    }
+}
+
+impl std::fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let size = if self.size >= 1024 * 1024 {
+            format!("{:.1} MB", self.size as f64 / (1024.0 * 1024.0))
+        } else {
+            format!("{} KB", self.size.div_ceil(1024))
+        };
+        write!(
+            f,
+            "{} ({}, {})",
+            self.created_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M"),
+            self.reason,
+            size
+        )
+    }
+}
+
+/// The folder snapshots of the database at `db_path` are kept in
+pub fn backup_dir(db_path: &Path) -> PathBuf {
+    db_path.with_file_name("backups")
+}
+
+/// Copy the database into `dir` with SQLite's online backup API, which is safe
+/// while the app keeps using it. Login tokens are left out unless
+/// `include_auth` is set.
+pub fn create(
+    conn: &Connection,
+    dir: &Path,
+    reason: BackupReason,
+    include_auth: bool,
+) -> Result<Snapshot, AppError> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!(
+        "{}{}-{}.{}",
+        PREFIX,
+        Utc::now().format(TIMESTAMP_FORMAT),
+        reason.tag(),
+        EXTENSION
+    ));
+    // Written under another name so a half-written copy is never listed
+    let partial = path.with_extension("partial");
+
+    let result = (|| -> Result<(), AppError> {
+        let mut copy = Connection::open(&partial)?;
+        Backup::new(conn, &mut copy)?.run_to_completion(
+            256,
+            Duration::ZERO,
+            None::<fn(Progress)>,
+        )?;
+
+        if !include_auth && migrations::table_exists(&copy, "user_auth")? {
+            copy.execute("DELETE FROM user_auth", [])?;
+            // Don't leave the tokens behind in free pages
+            copy.execute_batch("VACUUM")?;
+        }
+        Ok(())
+    })();
+    if let Err(e) = result {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    fs::rename(&partial, &path)?;
+
+    info!("Backed up database to {:?}", path);
+    Snapshot::from_path(path)
+        .ok_or_else(|| AppError::DatabaseError("Backup was not written".into()))
+}
+
+/// Snapshots in `dir`, newest first
+pub fn list(dir: &Path) -> Result<Vec<Snapshot>, AppError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots: Vec<Snapshot> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| Snapshot::from_path(entry.path()))
+        .collect();
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.created_at));
+    Ok(snapshots)
+}
+
+/// Delete all but the `keep` newest snapshots taken for `reason`, returning
+/// how many went. Each kind is rotated on its own, so frequent scheduled
+/// snapshots never push out the one taken before an upgrade.
+pub fn rotate(dir: &Path, reason: BackupReason, keep: usize) -> Result<usize, AppError> {
+    let old = list(dir)?
+        .into_iter()
+        .filter(|snapshot| snapshot.reason == reason)
+        .skip(keep.max(1));
+
+    let mut removed = 0;
+    for snapshot in old {
+        fs::remove_file(&snapshot.path)?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Whether the newest snapshot is older than `interval`
+pub fn is_due(dir: &Path, interval: chrono::Duration) -> Result<bool, AppError> {
+    Ok(list(dir)?
+        .first()
+        .is_none_or(|newest| Utc::now() - newest.created_at >= interval))
+}
+
+/// Replace the database with `snapshot` and upgrade it to the current schema.
+/// The current login is kept when the snapshot has none.
+pub fn restore(conn: &mut Connection, snapshot: &Path) -> Result<(), AppError> {
+    // Check the snapshot before touching the database
+    let version = {
+        let source = Connection::open_with_flags(snapshot, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        migrations::schema_version(&source)?
+    };
+    if version == 0 || version > SCHEMA_VERSION {
+        return Err(AppError::DatabaseError(format!(
+            "{} is not a backup this version of the app can restore",
+            snapshot.display()
+        )));
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT user_id, access_token, refresh_token, expires_at, updated_at FROM user_auth",
+    )?;
+    let auth = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i32>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    drop(stmt);
+
+    conn.restore(DatabaseName::Main, snapshot, None::<fn(Progress)>)?;
+    migrations::migrate(conn)?;
+
+    let restored_auth: i64 =
+        conn.query_row("SELECT COUNT(*) FROM user_auth", [], |row| row.get(0))?;
+    if restored_auth == 0 {
+        for (user_id, access_token, refresh_token, expires_at, updated_at) in auth {
+            conn.execute(
+                "INSERT INTO user_auth
+                    (user_id, access_token, refresh_token, expires_at, updated_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![user_id, access_token, refresh_token, expires_at, updated_at],
+            )?;
+        }
+    }
+
+    info!("Restored database from {:?}", snapshot);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anime_count(conn: &Connection) -> i64 {
+        conn.query_row("SELECT COUNT(*) FROM cached_anime", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_snapshots_rotate_and_restore_without_tokens() {
+        let dir = std::env::temp_dir().join(format!("anilist-backup-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrations::migrate(&mut conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO cached_anime (id, title, genres, updated_at)
+                VALUES (1, 'Cowboy Bebop', '', '');
+             INSERT INTO user_auth (user_id, access_token, updated_at)
+                VALUES (7, 'secret-token', '');",
+        )
+        .unwrap();
+
+        let snapshot = create(&conn, &dir, BackupReason::Manual, false).unwrap();
+        assert_eq!(snapshot.reason, BackupReason::Manual);
+        let copy = Connection::open(&snapshot.path).unwrap();
+        assert_eq!(anime_count(&copy), 1);
+        let tokens: i64 = copy
+            .query_row("SELECT COUNT(*) FROM user_auth", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(tokens, 0);
+        drop(copy);
+
+        for _ in 0..3 {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            create(&conn, &dir, BackupReason::Scheduled, false).unwrap();
+        }
+        assert_eq!(list(&dir).unwrap().len(), 4);
+        assert!(!is_due(&dir, chrono::Duration::hours(1)).unwrap());
+        // The older manual snapshot isn't counted against the scheduled ones
+        assert_eq!(rotate(&dir, BackupReason::Scheduled, 2).unwrap(), 1);
+        assert_eq!(rotate(&dir, BackupReason::Manual, 2).unwrap(), 0);
+        let kept = list(&dir).unwrap();
+        assert_eq!(kept.len(), 3);
+        assert_eq!(kept[2].reason, BackupReason::Manual);
+
+        conn.execute("DELETE FROM cached_anime", []).unwrap();
+        restore(&mut conn, &kept[0].path).unwrap();
+        assert_eq!(anime_count(&conn), 1);
+        // Still logged in
+        let token: String = conn
+            .query_row("SELECT access_token FROM user_auth", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(token, "secret-token");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -3,6 +3,7 @@ use chrono::{DateTime, Utc};
 use log::{debug, trace};
 use std::collections::HashMap;
 
+#[derive(Debug)]
 pub struct MediaCache {
     media: HashMap<i32, (Media, DateTime<Utc>)>,
     max_age_seconds: i64,
@@ -71,6 +72,7 @@ impl MediaCache {
     }
 }
 
+#[derive(Debug)]
 pub struct ListCache {
     entries: HashMap<(i32, String), (Vec<MediaListEntry>, DateTime<Utc>)>,
     max_age_seconds: i64,
@@ -160,7 +162,6 @@ impl ListCache {
 mod tests {
     use super::*;
     use crate::api::models::{Media, MediaCoverImage, MediaTitle};
-    use chrono::Duration;
     use std::thread::sleep;
     use std::time::Duration as StdDuration;
 
@@ -175,9 +176,12 @@ mod tests {
                 romaji: Some("Test Anime".to_string()),
                 english: Some("Test Anime EN".to_string()),
                 native: None,
+                ..Default::default()
             },
             description: Some("Test description".to_string()),
             episodes: Some(12),
+            chapters: None,
+            volumes: None,
             duration: Some(24),
             genres: Some(vec!["Action".to_string(), "Adventure".to_string()]),
             average_score: Some(8.5),
@@ -218,9 +222,12 @@ mod tests {
                 romaji: Some("Test".to_string()),
                 english: None,
                 native: None,
+                ..Default::default()
             },
             description: None,
             episodes: None,
+            chapters: None,
+            volumes: None,
             duration: None,
             genres: None,
             average_score: None,
@@ -246,6 +253,7 @@ mod tests {
             status: "CURRENT".to_string(),
             score: Some(8.0),
             progress: Some(5),
+            progress_volumes: None,
             updated_at: chrono::Utc::now().timestamp(),
             media: None,
         };
@@ -0,0 +1,585 @@
+use chrono::{DateTime, Utc};
+use log::debug;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::api::client::{anime_details, AniListClient};
+use crate::api::models::{Media, MediaCoverImage, MediaListEntry, MediaTitle, MediaType};
+use crate::api::my_list::MyList;
+use crate::data::cache::{ListCache, MediaCache};
+use crate::data::database::{CachedAnime, CachedManga, Database};
+use crate::data::sync::{anime_media, manga_media, ListSync};
+use crate::utils::error::AppError;
+
+/// How long each kind of resource is served before it is revalidated, in seconds
+#[derive(Debug, Clone, Copy)]
+pub struct CacheTtls {
+    pub media: i64,
+    pub list: i64,
+}
+
+impl Default for CacheTtls {
+    fn default() -> Self {
+        Self {
+            // Titles, covers and scores rarely change
+            media: 24 * 60 * 60,
+            list: 5 * 60,
+        }
+    }
+}
+
+/// Where a cached value was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheSource {
+    Memory,
+    Database,
+    Network,
+}
+
+/// A value read through the cache layer
+#[derive(Debug, Clone)]
+pub struct Cached<T> {
+    pub value: T,
+    pub source: CacheSource,
+    // Older than its TTL: fine to show, but it should be refreshed
+    pub stale: bool,
+}
+
+impl<T> Cached<T> {
+    fn fresh(value: T, source: CacheSource) -> Self {
+        Self {
+            value,
+            source,
+            stale: false,
+        }
+    }
+}
+
+// Whole lists as last fetched, with the time they were fetched
+type Collections = HashMap<(i32, MediaType), (MyList, DateTime<Utc>)>;
+
+// Full details responses, with the time they were fetched
+type Details = HashMap<(i32, MediaType), (anime_details::ResponseData, DateTime<Utc>)>;
+
+/// Read-through cache between the screens and `AniListClient`: memory first,
+/// then SQLite, then the network. Clones share the same caches.
+#[derive(Debug, Clone)]
+pub struct CacheLayer {
+    client: AniListClient,
+    db: Arc<Mutex<Database>>,
+    list_sync: ListSync,
+    media: Arc<Mutex<MediaCache>>,
+    lists: Arc<Mutex<ListCache>>,
+    collections: Arc<Mutex<Collections>>,
+    details: Arc<Mutex<Details>>,
+    ttls: CacheTtls,
+}
+
+impl CacheLayer {
+    pub fn new(client: AniListClient, db: Arc<Mutex<Database>>, ttls: CacheTtls) -> Self {
+        Self {
+            client,
+            list_sync: ListSync::new(db.clone()),
+            db,
+            media: Arc::new(Mutex::new(MediaCache::new(ttls.media))),
+            lists: Arc::new(Mutex::new(ListCache::new(ttls.list))),
+            collections: Arc::new(Mutex::new(HashMap::new())),
+            details: Arc::new(Mutex::new(HashMap::new())),
+            ttls,
+        }
+    }
+
+    /// The user whose login is stored in the database, if any
+    pub fn stored_user_id(&self) -> Option<i32> {
+        self.list_sync.stored_user_id()
+    }
+
+    pub fn last_synced(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+    ) -> Result<Option<DateTime<Utc>>, AppError> {
+        self.list_sync.last_synced(user_id, media_type)
+    }
+
+    /// A media from memory or SQLite, without going to the network
+    pub fn peek_media(
+        &self,
+        id: i32,
+        media_type: MediaType,
+    ) -> Result<Option<Cached<Media>>, AppError> {
+        if let Some(media) = lock(&self.media)?.get(id) {
+            return Ok(Some(Cached::fresh(media.clone(), CacheSource::Memory)));
+        }
+
+        let stored = {
+            let db = lock(&self.db)?;
+            match media_type {
+                MediaType::ANIME => db
+                    .get_cached_anime(id)?
+                    .map(|anime| (anime.updated_at, anime_media(anime))),
+                MediaType::MANGA => db
+                    .get_cached_manga(id)?
+                    .map(|manga| (manga.updated_at, manga_media(manga))),
+            }
+        };
+        let Some((updated_at, media)) = stored else {
+            return Ok(None);
+        };
+
+        let stale = age(updated_at) > self.ttls.media;
+        if !stale {
+            lock(&self.media)?.add(media.clone());
+        }
+        Ok(Some(Cached {
+            value: media,
+            source: CacheSource::Database,
+            stale,
+        }))
+    }
+
+    /// The full details of a media from memory. A copy older than the media
+    /// TTL is returned as stale.
+    pub fn peek_media_details(
+        &self,
+        id: i32,
+        media_type: MediaType,
+    ) -> Result<Option<Cached<anime_details::ResponseData>>, AppError> {
+        Ok(lock(&self.details)?
+            .get(&(id, media_type))
+            .map(|(data, fetched_at)| Cached {
+                value: data.clone(),
+                source: CacheSource::Memory,
+                stale: age(*fetched_at) > self.ttls.media,
+            }))
+    }
+
+    /// The full details of a media, fetching them if they aren't in memory. A
+    /// stale copy is returned as is; call `refresh_media_details` to revalidate it.
+    pub async fn media_details(
+        &self,
+        id: i32,
+        media_type: MediaType,
+    ) -> Result<Cached<anime_details::ResponseData>, AppError> {
+        if let Some(cached) = self.peek_media_details(id, media_type)? {
+            return Ok(cached);
+        }
+
+        Ok(Cached::fresh(
+            self.refresh_media_details(id, media_type).await?,
+            CacheSource::Network,
+        ))
+    }
+
+    /// Fetch the full details of a media from AniList. The basics are stored
+    /// on the way so the next `peek_media` has them.
+    pub async fn refresh_media_details(
+        &self,
+        id: i32,
+        media_type: MediaType,
+    ) -> Result<anime_details::ResponseData, AppError> {
+        let data = self.client.get_media_details(id, media_type).await?;
+
+        if let Some(media) = data.media.clone() {
+            self.store_media(media_from_details(media), media_type)?;
+        }
+        lock(&self.details)?.insert((id, media_type), (data.clone(), Utc::now()));
+        Ok(data)
+    }
+
+    /// A list from memory or SQLite, without going to the network. `None`
+    /// until the list has been synced once.
+    pub fn peek_list(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+        status: Option<&str>,
+    ) -> Result<Option<Cached<Vec<MediaListEntry>>>, AppError> {
+        let key = list_key(media_type, status);
+        if let Some(entries) = lock(&self.lists)?.get(user_id, &key) {
+            return Ok(Some(Cached::fresh(entries.clone(), CacheSource::Memory)));
+        }
+
+        let Some(last_synced) = self.list_sync.last_synced(user_id, media_type)? else {
+            return Ok(None);
+        };
+        let entries = self.list_sync.cached_list(user_id, media_type, status)?;
+
+        let stale = age(last_synced) > self.ttls.list;
+        if !stale {
+            lock(&self.lists)?.add(user_id, key, entries.clone());
+        }
+        Ok(Some(Cached {
+            value: entries,
+            source: CacheSource::Database,
+            stale,
+        }))
+    }
+
+    /// A list from the first layer that has it, syncing it if it was never
+    /// synced. A stale copy is returned as is; call `refresh_list` to revalidate it.
+    pub async fn list(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+        status: Option<&str>,
+    ) -> Result<Cached<Vec<MediaListEntry>>, AppError> {
+        if let Some(cached) = self.peek_list(user_id, media_type, status)? {
+            return Ok(cached);
+        }
+
+        Ok(Cached::fresh(
+            self.refresh_list(user_id, media_type, status).await?,
+            CacheSource::Network,
+        ))
+    }
+
+    /// Sync the whole list from AniList and read back the requested part
+    pub async fn refresh_list(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+        status: Option<&str>,
+    ) -> Result<Vec<MediaListEntry>, AppError> {
+        self.refresh_my_list(user_id, media_type).await?;
+
+        let entries = self.list_sync.cached_list(user_id, media_type, status)?;
+        lock(&self.lists)?.add(user_id, list_key(media_type, status), entries.clone());
+        Ok(entries)
+    }
+
+    /// The whole list from memory, or else the copy stored in SQLite. The
+    /// stored copy has no formats, years or custom lists, so it is always stale.
+    pub fn peek_my_list(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+    ) -> Result<Option<Cached<MyList>>, AppError> {
+        if let Some((list, fetched_at)) = lock(&self.collections)?.get(&(user_id, media_type)) {
+            if age(*fetched_at) <= self.ttls.list {
+                return Ok(Some(Cached::fresh(list.clone(), CacheSource::Memory)));
+            }
+        }
+
+        if self.list_sync.last_synced(user_id, media_type)?.is_none() {
+            return Ok(None);
+        }
+        let entries = self.list_sync.cached_list(user_id, media_type, None)?;
+        Ok(Some(Cached {
+            value: MyList::from_entries(entries),
+            source: CacheSource::Database,
+            stale: true,
+        }))
+    }
+
+    /// Fetch the whole list from AniList, storing it in SQLite and memory
+    pub async fn refresh_my_list(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+    ) -> Result<MyList, AppError> {
+        let data = self
+            .client
+            .get_user_media_list(user_id, media_type, None)
+            .await?;
+        self.list_sync.store(user_id, media_type, &data)?;
+
+        // Every status of this list was just replaced
+        lock(&self.lists)?.invalidate_all_for_user(user_id);
+
+        let list = MyList::from_response(data);
+        lock(&self.collections)?.insert((user_id, media_type), (list.clone(), Utc::now()));
+        Ok(list)
+    }
+
+    /// Forget everything in memory about a list entry after it was edited
+    pub fn invalidate(&self, user_id: Option<i32>, media_id: i32) {
+        debug!("Invalidating cached lists and media {}", media_id);
+        if let (Some(user_id), Ok(mut lists)) = (user_id, self.lists.lock()) {
+            lists.invalidate_all_for_user(user_id);
+        }
+        if let (Some(user_id), Ok(mut collections)) = (user_id, self.collections.lock()) {
+            collections.retain(|(id, _), _| *id != user_id);
+        }
+        if let Ok(mut media) = self.media.lock() {
+            media.invalidate(media_id);
+        }
+        if let Ok(mut details) = self.details.lock() {
+            details.retain(|(id, _), _| *id != media_id);
+        }
+    }
+
+    /// Drop the in-memory layer, e.g. after the database cache was cleared
+    pub fn clear(&self) {
+        if let Ok(mut lists) = self.lists.lock() {
+            lists.clear();
+        }
+        if let Ok(mut collections) = self.collections.lock() {
+            collections.clear();
+        }
+        if let Ok(mut media) = self.media.lock() {
+            media.clear();
+        }
+        if let Ok(mut details) = self.details.lock() {
+            details.clear();
+        }
+    }
+
+    fn store_media(&self, media: Media, media_type: MediaType) -> Result<(), AppError> {
+        {
+            let db = lock(&self.db)?;
+            match media_type {
+                MediaType::ANIME => db.cache_anime(&stored_anime(&media))?,
+                MediaType::MANGA => db.cache_manga(&stored_manga(&media))?,
+            }
+        }
+        lock(&self.media)?.add(media);
+        Ok(())
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> Result<MutexGuard<'_, T>, AppError> {
+    mutex
+        .lock()
+        .map_err(|_| AppError::UnknownError("Cache lock poisoned".to_string()))
+}
+
+fn age(updated_at: DateTime<Utc>) -> i64 {
+    Utc::now().signed_duration_since(updated_at).num_seconds()
+}
+
+// `ListCache` is keyed by status, so the media type goes in front of it
+fn list_key(media_type: MediaType, status: Option<&str>) -> String {
+    format!("{:?}/{}", media_type, status.unwrap_or("ALL"))
+}
+
+fn media_from_details(media: anime_details::AnimeDetailsMedia) -> Media {
+    Media {
+        id: media.id as i32,
+        title: media.title.map(MediaTitle::from).unwrap_or_default(),
+        description: media.description,
+        episodes: media.episodes.map(|e| e as i32),
+        chapters: media.chapters.map(|c| c as i32),
+        volumes: media.volumes.map(|v| v as i32),
+        duration: media.duration.map(|d| d as i32),
+        genres: media
+            .genres
+            .map(|genres| genres.into_iter().flatten().collect()),
+        average_score: media.average_score.map(|s| s as f64),
+        cover_image: media.cover_image.map(|img| MediaCoverImage {
+            large: img.large,
+            medium: img.medium,
+        }),
+        banner_image: media.banner_image,
+        status: media.status.map(|s| format!("{:?}", s)),
+        format: media.format.map(|f| format!("{:?}", f)),
+    }
+}
+
+fn stored_anime(media: &Media) -> CachedAnime {
+    CachedAnime {
+        id: media.id,
+        title: media
+            .title
+            .romaji
+            .clone()
+            .unwrap_or_else(|| "Unknown".to_string()),
+        english_title: media.title.english.clone(),
+        native_title: media.title.native.clone(),
+        description: media.description.clone(),
+        episodes: media.episodes,
+        duration: media.duration,
+        genres: media.genres.clone().unwrap_or_default().join(","),
+        average_score: media.average_score,
+        cover_image: media
+            .cover_image
+            .as_ref()
+            .and_then(|img| img.large.clone().or(img.medium.clone())),
+        banner_image: media.banner_image.clone(),
+        updated_at: Utc::now(),
+        id_mal: None,
+        romaji_stylised: media.title.romaji_stylised.clone(),
+        english_stylised: media.title.english_stylised.clone(),
+        native_stylised: media.title.native_stylised.clone(),
+    }
+}
+
+fn stored_manga(media: &Media) -> CachedManga {
+    CachedManga {
+        id: media.id,
+        title: media
+            .title
+            .romaji
+            .clone()
+            .unwrap_or_else(|| "Unknown".to_string()),
+        english_title: media.title.english.clone(),
+        native_title: media.title.native.clone(),
+        description: media.description.clone(),
+        chapters: media.chapters,
+        volumes: media.volumes,
+        genres: media.genres.clone().unwrap_or_default().join(","),
+        average_score: media.average_score,
+        cover_image: media
+            .cover_image
+            .as_ref()
+            .and_then(|img| img.large.clone().or(img.medium.clone())),
+        banner_image: media.banner_image.clone(),
+        updated_at: Utc::now(),
+        romaji_stylised: media.title.romaji_stylised.clone(),
+        english_stylised: media.title.english_stylised.clone(),
+        native_stylised: media.title.native_stylised.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::ListEntryDetails;
+    use crate::api::transport::MockTransport;
+    use crate::data::database::ListUpdate;
+    use crate::data::outbox::Outbox;
+    use serde_json::json;
+
+    fn collection() -> serde_json::Value {
+        json!({
+            "MediaListCollection": {
+                "lists": [{ "name": "Watching", "status": "CURRENT", "entries": [{
+                    "id": 1,
+                    "mediaId": 10,
+                    "status": "CURRENT",
+                    "score": 0,
+                    "progress": 3,
+                    "progressVolumes": null,
+                    "updatedAt": 1700000000,
+                    "media": {
+                        "id": 10,
+                        "title": { "romaji": "Anime 10", "english": null, "native": null },
+                        "coverImage": { "large": null, "medium": null },
+                        "bannerImage": null,
+                        "episodes": 12,
+                        "chapters": null,
+                        "volumes": null,
+                        "duration": 24,
+                        "genres": ["Action"],
+                        "averageScore": 80,
+                        "nextAiringEpisode": null,
+                        "format": "TV",
+                        "status": "FINISHED"
+                    }
+                }] }]
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_reads_fall_through_and_edits_invalidate() {
+        let db = Database::in_memory().unwrap();
+        db.save_auth(7, "token", None, None).unwrap();
+        let db = Arc::new(Mutex::new(db));
+
+        let transport = Arc::new(MockTransport::new().with_data("UserAnimeList", collection()));
+        let client = AniListClient::new().with_transport(transport.clone());
+        let cache = CacheLayer::new(client, db.clone(), CacheTtls::default());
+
+        let first = cache
+            .list(7, MediaType::ANIME, Some("CURRENT"))
+            .await
+            .unwrap();
+        assert_eq!(first.source, CacheSource::Network);
+        assert_eq!(first.value.len(), 1);
+        let second = cache
+            .list(7, MediaType::ANIME, Some("CURRENT"))
+            .await
+            .unwrap();
+        assert_eq!(second.source, CacheSource::Memory);
+        assert_eq!(transport.requests().len(), 1);
+
+        // The sync stored the media as well
+        let media = cache.peek_media(10, MediaType::ANIME).unwrap().unwrap();
+        assert_eq!(media.source, CacheSource::Database);
+        assert!(!media.stale);
+        assert_eq!(
+            cache
+                .peek_media(10, MediaType::ANIME)
+                .unwrap()
+                .unwrap()
+                .source,
+            CacheSource::Memory
+        );
+
+        // An edit drops the copies in memory; the database already has it
+        let outbox = Outbox::new(db).with_cache(cache.clone());
+        outbox
+            .queue(ListUpdate {
+                entry_id: Some(1),
+                media_id: 10,
+                media_type: MediaType::ANIME,
+                status: Some("COMPLETED".to_string()),
+                score: None,
+                progress: Some(12),
+                progress_volumes: None,
+                details: ListEntryDetails::default(),
+            })
+            .unwrap();
+
+        let current = cache
+            .list(7, MediaType::ANIME, Some("CURRENT"))
+            .await
+            .unwrap();
+        assert_eq!(current.source, CacheSource::Database);
+        assert!(current.value.is_empty());
+        let all = cache.list(7, MediaType::ANIME, None).await.unwrap();
+        assert_eq!(all.value[0].status, "COMPLETED");
+        assert_eq!(
+            cache
+                .peek_media(10, MediaType::ANIME)
+                .unwrap()
+                .unwrap()
+                .source,
+            CacheSource::Database
+        );
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fresh_details_skip_the_network() {
+        let db = Arc::new(Mutex::new(Database::in_memory().unwrap()));
+        let details = json!({ "Media": {
+            "id": 10,
+            "title": { "romaji": "Anime 10", "english": null, "native": null },
+            "isFavourite": false
+        } });
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_data("AnimeDetails", details.clone())
+                .with_data("AnimeDetails", details),
+        );
+        let client = AniListClient::new().with_transport(transport.clone());
+        let cache = CacheLayer::new(client, db, CacheTtls::default());
+
+        let first = cache.media_details(10, MediaType::ANIME).await.unwrap();
+        assert_eq!(first.source, CacheSource::Network);
+        let second = cache.media_details(10, MediaType::ANIME).await.unwrap();
+        assert_eq!(second.source, CacheSource::Memory);
+        assert!(!second.stale);
+        assert_eq!(transport.requests().len(), 1);
+
+        // The basics were stored for the preview
+        assert!(cache.peek_media(10, MediaType::ANIME).unwrap().is_some());
+
+        // Past the TTL the copy is still served, flagged for a refresh
+        let expired = CacheLayer {
+            ttls: CacheTtls { media: -1, list: 0 },
+            ..cache.clone()
+        };
+        let stale = expired.media_details(10, MediaType::ANIME).await.unwrap();
+        assert!(stale.stale);
+        assert_eq!(transport.requests().len(), 1);
+
+        // An edit of the media drops the copy
+        cache.invalidate(None, 10);
+        let refetched = cache.media_details(10, MediaType::ANIME).await.unwrap();
+        assert_eq!(refetched.source, CacheSource::Network);
+        assert_eq!(transport.requests().len(), 2);
+    }
+}
@@ -1,8 +1,13 @@
 use chrono::{DateTime, Utc};
 use directories::ProjectDirs;
-use rusqlite::{params, Connection, Result as SqlResult};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqlResult};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::api::models::{ListEntryDetails, MediaType, ViewerPreferences};
+use crate::data::backup::{self, BackupReason, Snapshot};
+use crate::data::migrations;
+use crate::utils::error::AppError;
 
 // Models that will be saved in the database
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +24,15 @@ pub struct CachedAnime {
     pub cover_image: Option<String>,
     pub banner_image: Option<String>,
     pub updated_at: DateTime<Utc>,
+    // For exports to MyAnimeList; only known for anime synced with a list
+    pub id_mal: Option<i32>,
+    // Stylised titles, see `MediaTitle`
+    #[serde(default)]
+    pub romaji_stylised: Option<String>,
+    #[serde(default)]
+    pub english_stylised: Option<String>,
+    #[serde(default)]
+    pub native_stylised: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,28 +40,139 @@ pub struct CachedListEntry {
     pub id: i32,
     pub user_id: i32,
     pub media_id: i32,
-    pub status: String, // CURRENT, PLANNING, COMPLETED, etc.
-    pub score: Option<i32>,
+    pub status: String,     // CURRENT, PLANNING, COMPLETED, etc.
+    pub score: Option<f64>, // 0-100, see ScoreFormat
     pub progress: Option<i32>,
     pub updated_at: DateTime<Utc>,
+    // Notes, dates, repeats and custom lists, as last synced or edited
+    #[serde(default)]
+    pub details: ListEntryDetails,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedManga {
+    pub id: i32,
+    pub title: String,
+    pub english_title: Option<String>,
+    pub native_title: Option<String>,
+    pub description: Option<String>,
+    pub chapters: Option<i32>,
+    pub volumes: Option<i32>,
+    pub genres: String, // comma-separated
+    pub average_score: Option<f64>,
+    pub cover_image: Option<String>,
+    pub banner_image: Option<String>,
+    pub updated_at: DateTime<Utc>,
+    // Stylised titles, see `MediaTitle`
+    pub romaji_stylised: Option<String>,
+    pub english_stylised: Option<String>,
+    pub native_stylised: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedMangaListEntry {
+    pub id: i32,
+    pub user_id: i32,
+    pub media_id: i32,
+    pub status: String,        // CURRENT, PLANNING, COMPLETED, etc.
+    pub score: Option<f64>,    // 0-100, see ScoreFormat
+    pub progress: Option<i32>, // chapters read
+    pub progress_volumes: Option<i32>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A list edit made while offline (or while the network was down)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListUpdate {
+    pub entry_id: Option<i32>,
+    pub media_id: i32,
+    pub media_type: MediaType,
+    pub status: Option<String>,
+    pub score: Option<f64>, // 0-100, see ScoreFormat
+    pub progress: Option<i32>,
+    pub progress_volumes: Option<i32>,
+    pub details: ListEntryDetails,
+}
+
+/// Times AniList may reject a queued edit before it stops being sent
+pub const MAX_LIST_UPDATE_ATTEMPTS: i32 = 3;
+
+/// A `ListUpdate` waiting in the outbox to be sent to AniList
+#[derive(Debug, Clone)]
+pub struct PendingListUpdate {
+    pub id: i64,
+    pub update: ListUpdate,
+    pub created_at: DateTime<Utc>,
+    // Rejections by AniList; network failures don't count
+    pub attempts: i32,
+    pub last_error: Option<String>,
+}
+
+impl PendingListUpdate {
+    /// Rejected too often to be sent again until it is retried or discarded
+    pub fn is_failed(&self) -> bool {
+        self.attempts >= MAX_LIST_UPDATE_ATTEMPTS
+    }
 }
 
+/// User id, access token, refresh token and expiry of the stored login
+pub type StoredAuth = (i32, String, Option<String>, Option<DateTime<Utc>>);
+
 #[derive(Debug)]
 pub struct Database {
     conn: Connection,
 }
 
 impl Database {
-    pub fn new() -> SqlResult<Self> {
-        let db_path = Self::get_database_path()?;
-        let conn = Connection::open(db_path)?;
+    pub fn new() -> Result<Self, AppError> {
+        Self::open(&Self::get_database_path()?)
+    }
+
+    /// Open the database at `db_path`, creating or upgrading its tables
+    pub fn open(db_path: &Path) -> Result<Self, AppError> {
+        let mut conn = Connection::open(db_path)?;
+
+        // Snapshot an existing database before upgrading it
+        if migrations::needs_upgrade(&conn)? {
+            let dir = backup::backup_dir(db_path);
+            backup::create(&conn, &dir, BackupReason::PreMigration, false)?;
+            backup::rotate(&dir, BackupReason::PreMigration, backup::PRE_MIGRATION_KEEP)?;
+        }
+
+        // Create or upgrade the tables
+        migrations::migrate(&mut conn)?;
+
+        Ok(Self { conn })
+    }
 
-        // Create tables if they don't exist
-        Self::init_db(&conn)?;
+    /// Open a throwaway database that lives only as long as the connection
+    pub fn in_memory() -> Result<Self, AppError> {
+        let mut conn = Connection::open_in_memory()?;
+        migrations::migrate(&mut conn)?;
 
         Ok(Self { conn })
     }
 
+    /// Where snapshots of the database are kept
+    pub fn backup_dir() -> Result<PathBuf, AppError> {
+        Ok(backup::backup_dir(&Self::get_database_path()?))
+    }
+
+    /// Snapshot the database into `dir`, see `backup::create`
+    pub fn backup(
+        &self,
+        dir: &Path,
+        reason: BackupReason,
+        include_auth: bool,
+    ) -> Result<Snapshot, AppError> {
+        backup::create(&self.conn, dir, reason, include_auth)
+    }
+
+    /// Replace the database with a snapshot, see `backup::restore`
+    pub fn restore(&mut self, snapshot: &Path) -> Result<(), AppError> {
+        backup::restore(&mut self.conn, snapshot)
+    }
+
     fn get_database_path() -> SqlResult<PathBuf> {
         let proj_dirs = ProjectDirs::from("me", "camniel", "AniListClient").ok_or_else(|| {
             rusqlite::Error::InvalidPath("Could not determine project directory".into())
@@ -62,63 +187,17 @@ impl Database {
         Ok(data_dir.join("anilist.db"))
     }
 
-    fn init_db(conn: &Connection) -> SqlResult<()> {
-        // Create anime cache table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS cached_anime(
-                id INTEGER PRIMARY KEY,
-                title TEXT NOT NULL,
-                english_title TEXT,
-                native_title TEXT,
-                description TEXT,
-                episodes INTEGER,
-                duration INTEGER,
-                genres TEXT,
-                average_score REAL,
-                cover_image TEXT,
-                banner_image TEXT,
-                updated_at TEXT NOT NULL
-            )",
-            [],
-        )?;
-
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS cached_list_entries (
-                id INTEGER PRIMARY KEY,
-                user_id INTEGER NOT NULL,
-                media_id INTEGER NOT NULL,
-                status TEXT NOT NULL,
-                score REAL,
-                progress INTEGER,
-                updated_at TEXT NOT NULL,
-                FOREIGN KEY(media_id) REFERENCES cached_anime(id),
-                UNIQUE(user_id, media_id)
-            )",
-            [],
-        )?;
-
-        // Create user auth table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS user_auth (
-                user_id INTEGER PRIMARY KEY,
-                access_token TEXT NOT NULL,
-                refresh_token TEXT,
-                expires_at TEXT,
-                updated_at TEXT NOT NULL
-            )",
-            [],
-        )?;
-
-        Ok(())
-    }
-
     pub fn cache_anime(&self, anime: &CachedAnime) -> SqlResult<()> {
         self.conn.execute(
             "INSERT OR REPLACE INTO cached_anime (
                 id, title, english_title, native_title, description,
                 episodes, duration, genres, average_score,
-                cover_image, banner_image, updated_at
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                cover_image, banner_image, updated_at, id_mal,
+                romaji_stylised, english_stylised, native_stylised
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12,
+                    -- Details don't carry the MyAnimeList id, keep the synced one
+                    COALESCE(?13, (SELECT id_mal FROM cached_anime WHERE id = ?1)),
+                    ?14, ?15, ?16)",
             params![
                 anime.id,
                 anime.title,
@@ -131,7 +210,11 @@ impl Database {
                 anime.average_score,
                 anime.cover_image,
                 anime.banner_image,
-                anime.updated_at.to_rfc3339()
+                anime.updated_at.to_rfc3339(),
+                anime.id_mal,
+                anime.romaji_stylised,
+                anime.english_stylised,
+                anime.native_stylised
             ],
         )?;
 
@@ -143,7 +226,8 @@ impl Database {
         let mut stmt = self.conn.prepare(
             "SELECT id, title, english_title, native_title, description,
                 episodes, duration, genres, average_score,
-                cover_image, banner_image, updated_at
+                cover_image, banner_image, updated_at, id_mal,
+                romaji_stylised, english_stylised, native_stylised
                 FROM cached_anime
                 WHERE id = ?",
         )?;
@@ -172,6 +256,10 @@ impl Database {
                 cover_image: row.get(9)?,
                 banner_image: row.get(10)?,
                 updated_at,
+                id_mal: row.get(12)?,
+                romaji_stylised: row.get(13)?,
+                english_stylised: row.get(14)?,
+                native_stylised: row.get(15)?,
             })
         })?;
 
@@ -183,8 +271,8 @@ impl Database {
     pub fn save_list_entry(&self, entry: &CachedListEntry) -> SqlResult<()> {
         self.conn.execute(
             "INSERT OR REPLACE INTO cached_list_entries (
-                id, user_id, media_id, status, score, progress, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?)",
+                id, user_id, media_id, status, score, progress, updated_at, details
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
             params![
                 entry.id,
                 entry.user_id,
@@ -192,7 +280,8 @@ impl Database {
                 entry.status,
                 entry.score,
                 entry.progress,
-                entry.updated_at.to_rfc3339()
+                entry.updated_at.to_rfc3339(),
+                serde_json::to_string(&entry.details).ok()
             ],
         )?;
 
@@ -211,7 +300,8 @@ impl Database {
                 e.id, e.user_id, e.media_id, e.status, e.score, e.progress, e.updated_at,
                 a.id, a.title, a.english_title, a.native_title, a.description,
                 a.episodes, a.duration, a.genres, a.average_score,
-                a.cover_image, a.banner_image, a.updated_at
+                a.cover_image, a.banner_image, a.updated_at, a.id_mal,
+                a.romaji_stylised, a.english_stylised, a.native_stylised, e.details
                 FROM cached_list_entries e
                 JOIN cached_anime a ON e.media_id = a.id
                 WHERE e.user_id = ? AND e.status = ?
@@ -222,7 +312,8 @@ impl Database {
                 e.id, e.user_id, e.media_id, e.status, e.score, e.progress, e.updated_at,
                 a.id, a.title, a.english_title, a.native_title, a.description,
                 a.episodes, a.duration, a.genres, a.average_score,
-                a.cover_image, a.banner_image, a.updated_at
+                a.cover_image, a.banner_image, a.updated_at, a.id_mal,
+                a.romaji_stylised, a.english_stylised, a.native_stylised, e.details
                 FROM cached_list_entries e
                 JOIN cached_anime a ON e.media_id = a.id
                 WHERE e.user_id = ?
@@ -268,6 +359,10 @@ impl Database {
                 score: row.get(4)?,
                 progress: row.get(5)?,
                 updated_at: entry_updated_at,
+                details: row
+                    .get::<_, Option<String>>(23)?
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
             };
 
             let anime = CachedAnime {
@@ -283,6 +378,10 @@ impl Database {
                 cover_image: row.get(16)?,
                 banner_image: row.get(17)?,
                 updated_at: anime_updated_at,
+                id_mal: row.get(19)?,
+                romaji_stylised: row.get(20)?,
+                english_stylised: row.get(21)?,
+                native_stylised: row.get(22)?,
             };
 
             Ok((entry, anime))
@@ -292,6 +391,594 @@ impl Database {
         Ok(results)
     }
 
+    pub fn cache_manga(&self, manga: &CachedManga) -> SqlResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO cached_manga (
+                id, title, english_title, native_title, description,
+                chapters, volumes, genres, average_score,
+                cover_image, banner_image, updated_at,
+                romaji_stylised, english_stylised, native_stylised
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                manga.id,
+                manga.title,
+                manga.english_title,
+                manga.native_title,
+                manga.description,
+                manga.chapters,
+                manga.volumes,
+                manga.genres,
+                manga.average_score,
+                manga.cover_image,
+                manga.banner_image,
+                manga.updated_at.to_rfc3339(),
+                manga.romaji_stylised,
+                manga.english_stylised,
+                manga.native_stylised
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    // Get a cached manga
+    pub fn get_cached_manga(&self, id: i32) -> SqlResult<Option<CachedManga>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title, english_title, native_title, description,
+                chapters, volumes, genres, average_score,
+                cover_image, banner_image, updated_at,
+                romaji_stylised, english_stylised, native_stylised
+                FROM cached_manga
+                WHERE id = ?",
+        )?;
+
+        let manga_iter = stmt.query_map([id], |row| Self::manga_from_row(row, 0))?;
+
+        let manga = manga_iter.filter_map(Result::ok).next();
+        Ok(manga)
+    }
+
+    // Save or update a manga list entry
+    pub fn save_manga_list_entry(&self, entry: &CachedMangaListEntry) -> SqlResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO cached_manga_list_entries (
+                id, user_id, media_id, status, score, progress, progress_volumes, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                entry.id,
+                entry.user_id,
+                entry.media_id,
+                entry.status,
+                entry.score,
+                entry.progress,
+                entry.progress_volumes,
+                entry.updated_at.to_rfc3339()
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    // Get a user's manga list with cached manga details
+    pub fn get_user_manga_list(
+        &self,
+        user_id: i32,
+        status: Option<&str>,
+    ) -> SqlResult<Vec<(CachedMangaListEntry, CachedManga)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT
+            e.id, e.user_id, e.media_id, e.status, e.score, e.progress,
+            e.progress_volumes, e.updated_at,
+            m.id, m.title, m.english_title, m.native_title, m.description,
+            m.chapters, m.volumes, m.genres, m.average_score,
+            m.cover_image, m.banner_image, m.updated_at,
+            m.romaji_stylised, m.english_stylised, m.native_stylised
+            FROM cached_manga_list_entries e
+            JOIN cached_manga m ON e.media_id = m.id
+            WHERE e.user_id = ?1 AND (?2 IS NULL OR e.status = ?2)
+            ORDER BY e.updated_at DESC",
+        )?;
+
+        let list_iter = stmt.query_map(params![user_id, status], |row| {
+            let entry = CachedMangaListEntry {
+                id: row.get(0)?,
+                user_id: row.get(1)?,
+                media_id: row.get(2)?,
+                status: row.get(3)?,
+                score: row.get(4)?,
+                progress: row.get(5)?,
+                progress_volumes: row.get(6)?,
+                updated_at: Self::timestamp_from_row(row, 7)?,
+            };
+
+            Ok((entry, Self::manga_from_row(row, 8)?))
+        })?;
+
+        let results = list_iter.filter_map(Result::ok).collect();
+        Ok(results)
+    }
+
+    // Read a CachedManga whose columns start at `offset`
+    fn manga_from_row(row: &rusqlite::Row, offset: usize) -> SqlResult<CachedManga> {
+        Ok(CachedManga {
+            id: row.get(offset)?,
+            title: row.get(offset + 1)?,
+            english_title: row.get(offset + 2)?,
+            native_title: row.get(offset + 3)?,
+            description: row.get(offset + 4)?,
+            chapters: row.get(offset + 5)?,
+            volumes: row.get(offset + 6)?,
+            genres: row.get(offset + 7)?,
+            average_score: row.get(offset + 8)?,
+            cover_image: row.get(offset + 9)?,
+            banner_image: row.get(offset + 10)?,
+            updated_at: Self::timestamp_from_row(row, offset + 11)?,
+            romaji_stylised: row.get(offset + 12)?,
+            english_stylised: row.get(offset + 13)?,
+            native_stylised: row.get(offset + 14)?,
+        })
+    }
+
+    fn timestamp_from_row(row: &rusqlite::Row, index: usize) -> SqlResult<DateTime<Utc>> {
+        let value: String = row.get(index)?;
+        DateTime::parse_from_rfc3339(&value)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    index,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+    }
+
+    // Queue a list edit in the outbox, returning its id
+    pub fn enqueue_list_update(&self, update: &ListUpdate) -> SqlResult<i64> {
+        self.conn.execute(
+            "INSERT INTO pending_list_updates (
+                entry_id, media_id, media_type, status, score,
+                progress, progress_volumes, details, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                update.entry_id,
+                update.media_id,
+                format!("{:?}", update.media_type),
+                update.status,
+                update.score,
+                update.progress,
+                update.progress_volumes,
+                serde_json::to_string(&update.details).ok(),
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    // Get every queued list edit, oldest first
+    pub fn get_pending_list_updates(&self) -> SqlResult<Vec<PendingListUpdate>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, entry_id, media_id, media_type, status, score,
+                progress, progress_volumes, created_at, attempts, last_error, details
+                FROM pending_list_updates
+                ORDER BY id",
+        )?;
+
+        let pending_iter = stmt.query_map([], |row| {
+            let media_type: String = row.get(3)?;
+            let details: Option<String> = row.get(11)?;
+            Ok(PendingListUpdate {
+                id: row.get(0)?,
+                update: ListUpdate {
+                    entry_id: row.get(1)?,
+                    media_id: row.get(2)?,
+                    media_type: match media_type.as_str() {
+                        "MANGA" => MediaType::MANGA,
+                        _ => MediaType::ANIME,
+                    },
+                    status: row.get(4)?,
+                    score: row.get(5)?,
+                    progress: row.get(6)?,
+                    progress_volumes: row.get(7)?,
+                    details: details
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                },
+                created_at: Self::timestamp_from_row(row, 8)?,
+                attempts: row.get(9)?,
+                last_error: row.get(10)?,
+            })
+        })?;
+
+        pending_iter.collect()
+    }
+
+    pub fn remove_pending_list_update(&self, id: i64) -> SqlResult<()> {
+        self.conn
+            .execute("DELETE FROM pending_list_updates WHERE id = ?", [id])?;
+        Ok(())
+    }
+
+    // Remember why a queued edit could not be sent. Only rejections count as
+    // an attempt.
+    pub fn record_list_update_failure(
+        &self,
+        id: i64,
+        error: &str,
+        rejected: bool,
+    ) -> SqlResult<()> {
+        self.conn.execute(
+            "UPDATE pending_list_updates
+                SET attempts = attempts + ?, last_error = ?
+                WHERE id = ?",
+            params![rejected as i32, error, id],
+        )?;
+        Ok(())
+    }
+
+    // Give a failed edit a fresh set of attempts
+    pub fn reset_list_update_attempts(&self, id: i64) -> SqlResult<()> {
+        self.conn.execute(
+            "UPDATE pending_list_updates SET attempts = 0 WHERE id = ?",
+            [id],
+        )?;
+        Ok(())
+    }
+
+    // Apply a list edit to the cached copy of the user's list. Media we haven't
+    // cached are skipped; entries that don't exist on AniList yet get a
+    // placeholder id of -media_id.
+    pub fn apply_list_update(&self, user_id: i32, update: &ListUpdate) -> SqlResult<()> {
+        let entry_id = update.entry_id.unwrap_or(-update.media_id);
+        let now = Utc::now().to_rfc3339();
+
+        match update.media_type {
+            MediaType::ANIME => self.conn.execute(
+                "INSERT INTO cached_list_entries (
+                    id, user_id, media_id, status, score, progress, updated_at
+                )
+                SELECT ?1, ?2, ?3, COALESCE(?4, 'PLANNING'), ?5, ?6, ?7
+                WHERE EXISTS (SELECT 1 FROM cached_anime WHERE id = ?3)
+                ON CONFLICT(user_id, media_id) DO UPDATE SET
+                    status = COALESCE(?4, status),
+                    score = COALESCE(?5, score),
+                    progress = COALESCE(?6, progress),
+                    updated_at = ?7",
+                params![
+                    entry_id,
+                    user_id,
+                    update.media_id,
+                    update.status,
+                    update.score,
+                    update.progress,
+                    now
+                ],
+            )?,
+            MediaType::MANGA => self.conn.execute(
+                "INSERT INTO cached_manga_list_entries (
+                    id, user_id, media_id, status, score, progress, progress_volumes, updated_at
+                )
+                SELECT ?1, ?2, ?3, COALESCE(?4, 'PLANNING'), ?5, ?6, ?7, ?8
+                WHERE EXISTS (SELECT 1 FROM cached_manga WHERE id = ?3)
+                ON CONFLICT(user_id, media_id) DO UPDATE SET
+                    status = COALESCE(?4, status),
+                    score = COALESCE(?5, score),
+                    progress = COALESCE(?6, progress),
+                    progress_volumes = COALESCE(?7, progress_volumes),
+                    updated_at = ?8",
+                params![
+                    entry_id,
+                    user_id,
+                    update.media_id,
+                    update.status,
+                    update.score,
+                    update.progress,
+                    update.progress_volumes,
+                    now
+                ],
+            )?,
+        };
+
+        // Only anime entries keep their details, for the exports
+        if update.media_type == MediaType::ANIME && update.details != ListEntryDetails::default() {
+            let stored: Option<String> = self
+                .conn
+                .query_row(
+                    "SELECT details FROM cached_list_entries WHERE user_id = ?1 AND media_id = ?2",
+                    params![user_id, update.media_id],
+                    |row| row.get(0),
+                )
+                .optional()?
+                .flatten();
+            let mut details: ListEntryDetails = stored
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default();
+            details.merge(&update.details);
+
+            self.conn.execute(
+                "UPDATE cached_list_entries SET details = ?1 WHERE user_id = ?2 AND media_id = ?3",
+                params![
+                    serde_json::to_string(&details).ok(),
+                    user_id,
+                    update.media_id
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Swap the placeholder id of a locally created entry for the one AniList assigned
+    pub fn set_list_entry_id(
+        &self,
+        user_id: i32,
+        media_id: i32,
+        media_type: MediaType,
+        entry_id: i32,
+    ) -> SqlResult<()> {
+        let query = match media_type {
+            MediaType::ANIME => {
+                "UPDATE cached_list_entries SET id = ?1 WHERE user_id = ?2 AND media_id = ?3"
+            }
+            MediaType::MANGA => {
+                "UPDATE cached_manga_list_entries SET id = ?1 WHERE user_id = ?2 AND media_id = ?3"
+            }
+        };
+
+        self.conn
+            .execute(query, params![entry_id, user_id, media_id])?;
+        Ok(())
+    }
+
+    // Forget an entry that was deleted on AniList
+    pub fn remove_list_entry(
+        &self,
+        user_id: i32,
+        media_id: i32,
+        media_type: MediaType,
+    ) -> SqlResult<()> {
+        let query = match media_type {
+            MediaType::ANIME => {
+                "DELETE FROM cached_list_entries WHERE user_id = ?1 AND media_id = ?2"
+            }
+            MediaType::MANGA => {
+                "DELETE FROM cached_manga_list_entries WHERE user_id = ?1 AND media_id = ?2"
+            }
+        };
+
+        self.conn.execute(query, params![user_id, media_id])?;
+        Ok(())
+    }
+
+    // Drop every queued edit of one media, e.g. once its entry is deleted
+    pub fn remove_pending_list_updates_for(
+        &self,
+        media_id: i32,
+        media_type: MediaType,
+    ) -> SqlResult<()> {
+        self.conn.execute(
+            "DELETE FROM pending_list_updates WHERE media_id = ? AND media_type = ?",
+            params![media_id, format!("{:?}", media_type)],
+        )?;
+        Ok(())
+    }
+
+    /// Replace the cached anime list of `user_id` with a freshly fetched copy.
+    ///
+    /// Runs in one transaction: media and entries are upserted, entries missing
+    /// from `entries` are removed and queued offline edits are applied on top.
+    pub fn sync_anime_list(
+        &self,
+        user_id: i32,
+        entries: &[(CachedListEntry, CachedAnime)],
+    ) -> SqlResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        for (entry, anime) in entries {
+            tx.execute(
+                "INSERT INTO cached_anime (
+                    id, title, english_title, native_title, description,
+                    episodes, duration, genres, average_score,
+                    cover_image, banner_image, updated_at, id_mal,
+                    romaji_stylised, english_stylised, native_stylised
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
+                ON CONFLICT(id) DO UPDATE SET
+                    title = excluded.title,
+                    english_title = excluded.english_title,
+                    native_title = excluded.native_title,
+                    description = COALESCE(excluded.description, description),
+                    episodes = excluded.episodes,
+                    duration = excluded.duration,
+                    genres = excluded.genres,
+                    average_score = excluded.average_score,
+                    cover_image = excluded.cover_image,
+                    banner_image = excluded.banner_image,
+                    updated_at = excluded.updated_at,
+                    id_mal = COALESCE(excluded.id_mal, id_mal),
+                    romaji_stylised = excluded.romaji_stylised,
+                    english_stylised = excluded.english_stylised,
+                    native_stylised = excluded.native_stylised",
+                params![
+                    anime.id,
+                    anime.title,
+                    anime.english_title,
+                    anime.native_title,
+                    anime.description,
+                    anime.episodes,
+                    anime.duration,
+                    anime.genres,
+                    anime.average_score,
+                    anime.cover_image,
+                    anime.banner_image,
+                    anime.updated_at.to_rfc3339(),
+                    anime.id_mal,
+                    anime.romaji_stylised,
+                    anime.english_stylised,
+                    anime.native_stylised
+                ],
+            )?;
+
+            tx.execute(
+                "INSERT INTO cached_list_entries (
+                    id, user_id, media_id, status, score, progress, updated_at, details
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                ON CONFLICT(user_id, media_id) DO UPDATE SET
+                    id = excluded.id,
+                    status = excluded.status,
+                    score = excluded.score,
+                    progress = excluded.progress,
+                    updated_at = excluded.updated_at,
+                    details = excluded.details",
+                params![
+                    entry.id,
+                    user_id,
+                    entry.media_id,
+                    entry.status,
+                    entry.score,
+                    entry.progress,
+                    entry.updated_at.to_rfc3339(),
+                    serde_json::to_string(&entry.details).ok()
+                ],
+            )?;
+        }
+
+        let ids: Vec<i32> = entries.iter().map(|(entry, _)| entry.id).collect();
+        tx.execute(
+            "DELETE FROM cached_list_entries
+                WHERE user_id = ?1 AND id NOT IN (SELECT value FROM json_each(?2))",
+            params![user_id, serde_json::to_string(&ids).unwrap_or_default()],
+        )?;
+
+        self.finish_list_sync(user_id, MediaType::ANIME)?;
+        tx.commit()
+    }
+
+    /// Manga counterpart of `sync_anime_list`
+    pub fn sync_manga_list(
+        &self,
+        user_id: i32,
+        entries: &[(CachedMangaListEntry, CachedManga)],
+    ) -> SqlResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        for (entry, manga) in entries {
+            tx.execute(
+                "INSERT INTO cached_manga (
+                    id, title, english_title, native_title, description,
+                    chapters, volumes, genres, average_score,
+                    cover_image, banner_image, updated_at,
+                    romaji_stylised, english_stylised, native_stylised
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+                ON CONFLICT(id) DO UPDATE SET
+                    title = excluded.title,
+                    english_title = excluded.english_title,
+                    native_title = excluded.native_title,
+                    description = COALESCE(excluded.description, description),
+                    chapters = excluded.chapters,
+                    volumes = excluded.volumes,
+                    genres = excluded.genres,
+                    average_score = excluded.average_score,
+                    cover_image = excluded.cover_image,
+                    banner_image = excluded.banner_image,
+                    updated_at = excluded.updated_at,
+                    romaji_stylised = excluded.romaji_stylised,
+                    english_stylised = excluded.english_stylised,
+                    native_stylised = excluded.native_stylised",
+                params![
+                    manga.id,
+                    manga.title,
+                    manga.english_title,
+                    manga.native_title,
+                    manga.description,
+                    manga.chapters,
+                    manga.volumes,
+                    manga.genres,
+                    manga.average_score,
+                    manga.cover_image,
+                    manga.banner_image,
+                    manga.updated_at.to_rfc3339(),
+                    manga.romaji_stylised,
+                    manga.english_stylised,
+                    manga.native_stylised
+                ],
+            )?;
+
+            tx.execute(
+                "INSERT INTO cached_manga_list_entries (
+                    id, user_id, media_id, status, score, progress, progress_volumes, updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                ON CONFLICT(user_id, media_id) DO UPDATE SET
+                    id = excluded.id,
+                    status = excluded.status,
+                    score = excluded.score,
+                    progress = excluded.progress,
+                    progress_volumes = excluded.progress_volumes,
+                    updated_at = excluded.updated_at",
+                params![
+                    entry.id,
+                    user_id,
+                    entry.media_id,
+                    entry.status,
+                    entry.score,
+                    entry.progress,
+                    entry.progress_volumes,
+                    entry.updated_at.to_rfc3339()
+                ],
+            )?;
+        }
+
+        let ids: Vec<i32> = entries.iter().map(|(entry, _)| entry.id).collect();
+        tx.execute(
+            "DELETE FROM cached_manga_list_entries
+                WHERE user_id = ?1 AND id NOT IN (SELECT value FROM json_each(?2))",
+            params![user_id, serde_json::to_string(&ids).unwrap_or_default()],
+        )?;
+
+        self.finish_list_sync(user_id, MediaType::MANGA)?;
+        tx.commit()
+    }
+
+    // Re-apply edits that haven't reached AniList yet and stamp the sync time
+    fn finish_list_sync(&self, user_id: i32, media_type: MediaType) -> SqlResult<()> {
+        for pending in self.get_pending_list_updates()? {
+            // AniList keeps refusing failed edits, so show what it has instead
+            if pending.update.media_type == media_type && !pending.is_failed() {
+                self.apply_list_update(user_id, &pending.update)?;
+            }
+        }
+
+        self.conn.execute(
+            "INSERT OR REPLACE INTO list_sync_state (user_id, media_type, last_synced_at)
+                VALUES (?, ?, ?)",
+            params![
+                user_id,
+                format!("{:?}", media_type),
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    // When the list of `user_id` was last pulled from AniList
+    pub fn get_last_synced(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+    ) -> SqlResult<Option<DateTime<Utc>>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT last_synced_at FROM list_sync_state
+                WHERE user_id = ? AND media_type = ?",
+        )?;
+
+        let synced_iter = stmt.query_map(params![user_id, format!("{:?}", media_type)], |row| {
+            Self::timestamp_from_row(row, 0)
+        })?;
+
+        let last_synced = synced_iter.filter_map(Result::ok).next();
+        Ok(last_synced)
+    }
+
     // Save user authentication details
     pub fn save_auth(
         &self,
@@ -317,9 +1004,7 @@ impl Database {
     }
 
     // Get stored authentication details
-    pub fn get_auth(
-        &self,
-    ) -> SqlResult<Option<(i32, String, Option<String>, Option<DateTime<Utc>>)>> {
+    pub fn get_auth(&self) -> SqlResult<Option<StoredAuth>> {
         let mut stmt = self.conn.prepare(
             "SELECT user_id, access_token, refresh_token, expires_at
                 FROM user_auth
@@ -347,10 +1032,44 @@ impl Database {
         Ok(auth)
     }
 
+    pub fn save_viewer_preferences(
+        &self,
+        user_id: i32,
+        preferences: &ViewerPreferences,
+    ) -> SqlResult<()> {
+        let json = serde_json::to_string(preferences)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        self.conn.execute(
+            "INSERT OR REPLACE INTO viewer_preferences (user_id, preferences, updated_at)
+                VALUES (?, ?, ?)",
+            params![user_id, json, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    // The preferences last fetched for `user_id`, if any
+    pub fn get_viewer_preferences(&self, user_id: i32) -> SqlResult<Option<ViewerPreferences>> {
+        let json: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT preferences FROM viewer_preferences WHERE user_id = ?",
+                [user_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
     // Clear all cached data
     pub fn clear_cache(&self) -> SqlResult<()> {
         self.conn.execute("DELETE FROM cached_list_entries", [])?;
         self.conn.execute("DELETE FROM cached_anime", [])?;
+        self.conn
+            .execute("DELETE FROM cached_manga_list_entries", [])?;
+        self.conn.execute("DELETE FROM cached_manga", [])?;
+        self.conn.execute("DELETE FROM list_sync_state", [])?;
         Ok(())
     }
 
@@ -386,3 +1105,71 @@ impl Clone for Database {
         Self::new().expect("Failed to clone database connection")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_unversioned_database_is_backed_up_before_migrating() {
+        let dir = std::env::temp_dir().join(format!("anilist-v0-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("anilist.db");
+
+        // The tables the app created before the schema was versioned
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE cached_anime(
+                id INTEGER PRIMARY KEY, title TEXT NOT NULL, english_title TEXT,
+                native_title TEXT, description TEXT, episodes INTEGER, duration INTEGER,
+                genres TEXT, average_score REAL, cover_image TEXT, banner_image TEXT,
+                updated_at TEXT NOT NULL
+             );
+             CREATE TABLE cached_list_entries (
+                id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL, media_id INTEGER NOT NULL,
+                status TEXT NOT NULL, score REAL, progress INTEGER, updated_at TEXT NOT NULL,
+                FOREIGN KEY(media_id) REFERENCES cached_anime(id), UNIQUE(user_id, media_id)
+             );
+             CREATE TABLE user_auth (
+                user_id INTEGER PRIMARY KEY, access_token TEXT NOT NULL, refresh_token TEXT,
+                expires_at TEXT, updated_at TEXT NOT NULL
+             );
+             INSERT INTO cached_anime (id, title, genres, updated_at)
+                VALUES (1, 'Cowboy Bebop', '', '2024-01-01T00:00:00+00:00');
+             INSERT INTO user_auth (user_id, access_token, updated_at)
+                VALUES (7, 'secret-token', '');",
+        )
+        .unwrap();
+        assert_eq!(migrations::schema_version(&conn).unwrap(), 0);
+        drop(conn);
+
+        let db = Database::open(&db_path).unwrap();
+        assert_eq!(
+            migrations::schema_version(&db.conn).unwrap(),
+            migrations::SCHEMA_VERSION
+        );
+        assert!(db.get_cached_anime(1).unwrap().is_some());
+
+        let snapshots = backup::list(&backup::backup_dir(&db_path)).unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].reason, BackupReason::PreMigration);
+        let copy = Connection::open(&snapshots[0].path).unwrap();
+        assert_eq!(migrations::schema_version(&copy).unwrap(), 0);
+        let titles: i64 = copy
+            .query_row("SELECT COUNT(*) FROM cached_anime", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(titles, 1);
+        drop(copy);
+
+        // A fresh database has nothing to back up
+        drop(db);
+        fs::remove_dir_all(&dir).unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        Database::open(&db_path).unwrap();
+        assert!(!backup::backup_dir(&db_path).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,462 @@
+use chrono::{DateTime, Utc};
+use directories::UserDirs;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::api::models::FuzzyDate;
+use crate::data::database::{CachedAnime, CachedListEntry, Database};
+use crate::utils::error::AppError;
+
+/// Bumped whenever the JSON dump changes shape
+pub const DUMP_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    MalXml,
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] =
+        [ExportFormat::MalXml, ExportFormat::Csv, ExportFormat::Json];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::MalXml => "xml",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    /// The format named on the command line: `xml`, `csv` or `json`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "xml" | "mal" => Some(ExportFormat::MalXml),
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::MalXml => write!(f, "MyAnimeList XML"),
+            ExportFormat::Csv => write!(f, "CSV"),
+            ExportFormat::Json => write!(f, "JSON"),
+        }
+    }
+}
+
+/// Everything stored about the list, as written by the JSON export. Advanced
+/// scores are not synced, so they are the one thing it leaves out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListDump {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub user_id: i32,
+    pub entries: Vec<DumpEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DumpEntry {
+    pub entry: CachedListEntry,
+    pub anime: CachedAnime,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportReport {
+    pub path: PathBuf,
+    pub exported: usize,
+    // Entries MyAnimeList can't take because their anime has no MAL id
+    pub skipped: usize,
+}
+
+/// Write the synced anime list of `user_id` to `path`
+pub fn export_anime_list(
+    db: &Database,
+    user_id: i32,
+    format: ExportFormat,
+    path: &Path,
+) -> Result<ExportReport, AppError> {
+    let entries = db.get_user_anime_list(user_id, None)?;
+    if entries.is_empty() {
+        return Err(AppError::DatabaseError(
+            "Nothing to export, sync your list first".into(),
+        ));
+    }
+
+    let (contents, skipped) = render(&entries, user_id, format)?;
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+
+    info!(
+        "Exported {} entries to {} ({} skipped)",
+        entries.len() - skipped,
+        path.display(),
+        skipped
+    );
+    Ok(ExportReport {
+        path: path.to_path_buf(),
+        exported: entries.len() - skipped,
+        skipped,
+    })
+}
+
+/// `anilist-anime-<date>.<ext>` in the downloads folder, or the home folder
+/// when there is none
+pub fn default_path(format: ExportFormat) -> Result<PathBuf, AppError> {
+    let dirs = UserDirs::new()
+        .ok_or_else(|| AppError::ConfigError("Could not determine home directory".into()))?;
+    let dir = dirs.download_dir().unwrap_or(dirs.home_dir());
+
+    Ok(dir.join(format!(
+        "anilist-anime-{}.{}",
+        Utc::now().format("%Y%m%d"),
+        format.extension()
+    )))
+}
+
+/// The file contents and how many entries were left out
+pub fn render(
+    entries: &[(CachedListEntry, CachedAnime)],
+    user_id: i32,
+    format: ExportFormat,
+) -> Result<(String, usize), AppError> {
+    match format {
+        ExportFormat::MalXml => Ok(mal_xml(entries)),
+        ExportFormat::Csv => Ok((csv(entries), 0)),
+        ExportFormat::Json => {
+            let dump = ListDump {
+                version: DUMP_VERSION,
+                exported_at: Utc::now(),
+                user_id,
+                entries: entries
+                    .iter()
+                    .map(|(entry, anime)| DumpEntry {
+                        entry: entry.clone(),
+                        anime: anime.clone(),
+                    })
+                    .collect(),
+            };
+            Ok((serde_json::to_string_pretty(&dump)?, 0))
+        }
+    }
+}
+
+// The layout of MyAnimeList's own export, which its importer accepts
+fn mal_xml(entries: &[(CachedListEntry, CachedAnime)]) -> (String, usize) {
+    let mut anime = String::new();
+    let mut skipped = 0;
+
+    for (entry, media) in entries {
+        let Some(id_mal) = media.id_mal else {
+            skipped += 1;
+            continue;
+        };
+        let details = &entry.details;
+        let custom_lists = details.custom_lists.clone().unwrap_or_default();
+        // MyAnimeList has no rewatching status, only a flag on completed entries
+        let (status, rewatching) = match entry.status.as_str() {
+            "CURRENT" => ("Watching", 0),
+            "REPEATING" => ("Completed", 1),
+            "COMPLETED" => ("Completed", 0),
+            "PAUSED" => ("On-Hold", 0),
+            "DROPPED" => ("Dropped", 0),
+            _ => ("Plan to Watch", 0),
+        };
+
+        anime.push_str(&format!(
+            "\t<anime>\n\
+             \t\t<series_animedb_id>{}</series_animedb_id>\n\
+             \t\t<series_title>{}</series_title>\n\
+             \t\t<series_episodes>{}</series_episodes>\n\
+             \t\t<my_watched_episodes>{}</my_watched_episodes>\n\
+             \t\t<my_start_date>{}</my_start_date>\n\
+             \t\t<my_finish_date>{}</my_finish_date>\n\
+             \t\t<my_score>{}</my_score>\n\
+             \t\t<my_status>{}</my_status>\n\
+             \t\t<my_times_watched>{}</my_times_watched>\n\
+             \t\t<my_rewatching>{}</my_rewatching>\n\
+             \t\t<my_comments>{}</my_comments>\n\
+             \t\t<my_tags>{}</my_tags>\n\
+             \t\t<update_on_import>1</update_on_import>\n\
+             \t</anime>\n",
+            id_mal,
+            xml_escape(&media.title),
+            media.episodes.unwrap_or(0),
+            entry.progress.unwrap_or(0),
+            mal_date(details.started_at),
+            mal_date(details.completed_at),
+            // 0-10 on MyAnimeList
+            (entry.score.unwrap_or(0.0) / 10.0).round() as i32,
+            status,
+            details.repeat.unwrap_or(0),
+            rewatching,
+            xml_escape(details.notes.as_deref().unwrap_or_default()),
+            // MyAnimeList has no custom lists, tags are the closest
+            xml_escape(&custom_lists.join(", ")),
+        ));
+    }
+
+    let xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n\
+         <myanimelist>\n\
+         \t<myinfo>\n\
+         \t\t<user_export_type>1</user_export_type>\n\
+         \t\t<user_total_anime>{}</user_total_anime>\n\
+         \t</myinfo>\n\
+         {}</myanimelist>\n",
+        entries.len() - skipped,
+        anime
+    );
+    (xml, skipped)
+}
+
+fn csv(entries: &[(CachedListEntry, CachedAnime)]) -> String {
+    let mut csv = String::from(
+        "media_id,id_mal,title,english_title,native_title,status,score,progress,episodes,genres,updated_at,\
+         started_at,completed_at,repeat,private,custom_lists,notes\n",
+    );
+
+    for (entry, anime) in entries {
+        let details = &entry.details;
+        let date = |date: Option<FuzzyDate>| {
+            date.filter(|date| !date.is_empty())
+                .map(|date| date.to_string())
+                .unwrap_or_default()
+        };
+        let fields = [
+            entry.media_id.to_string(),
+            anime.id_mal.map(|id| id.to_string()).unwrap_or_default(),
+            anime.title.clone(),
+            anime.english_title.clone().unwrap_or_default(),
+            anime.native_title.clone().unwrap_or_default(),
+            entry.status.clone(),
+            entry.score.map(|s| s.to_string()).unwrap_or_default(),
+            entry.progress.map(|p| p.to_string()).unwrap_or_default(),
+            anime.episodes.map(|e| e.to_string()).unwrap_or_default(),
+            anime.genres.clone(),
+            entry.updated_at.to_rfc3339(),
+            date(details.started_at),
+            date(details.completed_at),
+            details.repeat.map(|r| r.to_string()).unwrap_or_default(),
+            details.private.map(|p| p.to_string()).unwrap_or_default(),
+            details.custom_lists.clone().unwrap_or_default().join(","),
+            details.notes.clone().unwrap_or_default(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+// YYYY-MM-DD with zeroes for unknown parts, as MyAnimeList writes them
+fn mal_date(date: Option<FuzzyDate>) -> String {
+    let date = date.unwrap_or_default();
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.year.unwrap_or(0),
+        date.month.unwrap_or(0),
+        date.day.unwrap_or(0)
+    )
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::ListEntryDetails;
+
+    fn row(
+        media_id: i32,
+        id_mal: Option<i32>,
+        title: &str,
+        status: &str,
+    ) -> (CachedListEntry, CachedAnime) {
+        (
+            CachedListEntry {
+                id: media_id * 10,
+                user_id: 1,
+                media_id,
+                status: status.to_string(),
+                score: Some(85.0),
+                progress: Some(12),
+                updated_at: Utc::now(),
+                details: Default::default(),
+            },
+            CachedAnime {
+                id: media_id,
+                title: title.to_string(),
+                english_title: None,
+                native_title: None,
+                description: None,
+                episodes: Some(12),
+                duration: None,
+                genres: "Action,Drama".to_string(),
+                average_score: None,
+                cover_image: None,
+                banner_image: None,
+                updated_at: Utc::now(),
+                id_mal,
+                romaji_stylised: None,
+                english_stylised: None,
+                native_stylised: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_exports_map_ids_and_escape_titles() {
+        let mut entries = vec![
+            row(1, Some(101), "Tom & Jerry <TV>", "REPEATING"),
+            row(2, None, "Not on MAL", "CURRENT"),
+        ];
+        entries[0].0.details = ListEntryDetails {
+            notes: Some("Rewatch & chill".to_string()),
+            started_at: Some(FuzzyDate {
+                year: Some(2024),
+                month: Some(3),
+                day: None,
+            }),
+            completed_at: Some(FuzzyDate {
+                year: Some(2024),
+                month: Some(4),
+                day: Some(5),
+            }),
+            repeat: Some(2),
+            private: Some(true),
+            custom_lists: Some(vec!["Favourites".to_string()]),
+            ..Default::default()
+        };
+
+        let (xml, skipped) = render(&entries, 1, ExportFormat::MalXml).unwrap();
+        assert_eq!(skipped, 1);
+        assert!(xml.contains("<series_animedb_id>101</series_animedb_id>"));
+        assert!(xml.contains("<series_title>Tom &amp; Jerry &lt;TV&gt;</series_title>"));
+        assert!(xml.contains("<my_score>9</my_score>"));
+        assert!(xml.contains("<my_status>Completed</my_status>"));
+        assert!(xml.contains("<my_rewatching>1</my_rewatching>"));
+        assert!(xml.contains("<my_start_date>2024-03-00</my_start_date>"));
+        assert!(xml.contains("<my_finish_date>2024-04-05</my_finish_date>"));
+        assert!(xml.contains("<my_times_watched>2</my_times_watched>"));
+        assert!(xml.contains("<my_comments>Rewatch &amp; chill</my_comments>"));
+        assert!(xml.contains("<my_tags>Favourites</my_tags>"));
+        assert!(!xml.contains("Not on MAL"));
+
+        let (csv, _) = render(&entries, 1, ExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(
+            lines[1].starts_with("1,101,Tom & Jerry <TV>,,,REPEATING,85,12,12,\"Action,Drama\",")
+        );
+        assert!(lines[1].ends_with(",2024-03,2024-04-05,2,true,Favourites,Rewatch & chill"));
+        assert!(lines[2].starts_with("2,,Not on MAL,"));
+
+        let (json, _) = render(&entries, 1, ExportFormat::Json).unwrap();
+        let dump: ListDump = serde_json::from_str(&json).unwrap();
+        assert_eq!(dump.version, DUMP_VERSION);
+        assert_eq!(dump.entries.len(), 2);
+        assert_eq!(dump.entries[0].anime.id_mal, Some(101));
+        assert_eq!(dump.entries[1].entry.status, "CURRENT");
+        assert_eq!(dump.entries[0].entry.details, entries[0].0.details);
+    }
+
+    #[tokio::test]
+    async fn test_synced_list_exports_mal_ids() {
+        use crate::api::client::AniListClient;
+        use crate::api::models::MediaType;
+        use crate::api::transport::MockTransport;
+        use crate::data::sync::ListSync;
+        use serde_json::json;
+        use std::sync::{Arc, Mutex};
+
+        let entry = |id: i64, media_id: i64, id_mal: Option<i64>| {
+            json!({
+                "id": id,
+                "mediaId": media_id,
+                "status": "CURRENT",
+                "score": 8.0,
+                "progress": 3,
+                "progressVolumes": null,
+                "notes": "Watched with friends",
+                "startedAt": { "year": 2023, "month": 10, "day": 1 },
+                "completedAt": { "year": null, "month": null, "day": null },
+                "updatedAt": 1700000000,
+                "media": {
+                    "id": media_id,
+                    "idMal": id_mal,
+                    "title": { "romaji": format!("Anime {}", media_id), "english": null, "native": null },
+                    "coverImage": { "large": null, "medium": null },
+                    "bannerImage": null,
+                    "episodes": 12,
+                    "chapters": null,
+                    "volumes": null,
+                    "duration": 24,
+                    "genres": ["Action"],
+                    "averageScore": 80,
+                    "nextAiringEpisode": null,
+                    "format": "TV",
+                    "status": "FINISHED"
+                }
+            })
+        };
+        let transport = Arc::new(MockTransport::new().with_data(
+            "UserAnimeList",
+            json!({
+                "MediaListCollection": {
+                    "lists": [{
+                        "name": "Watching",
+                        "status": "CURRENT",
+                        "entries": [entry(1, 10, Some(110)), entry(2, 20, None)]
+                    }]
+                }
+            }),
+        ));
+        let client = AniListClient::new().with_transport(transport);
+
+        let db = Arc::new(Mutex::new(Database::in_memory().unwrap()));
+        ListSync::new(db.clone())
+            .sync(&client, 7, MediaType::ANIME)
+            .await
+            .unwrap();
+
+        let path =
+            std::env::temp_dir().join(format!("anilist-export-test-{}.xml", std::process::id()));
+        let report =
+            export_anime_list(&db.lock().unwrap(), 7, ExportFormat::MalXml, &path).unwrap();
+        let xml = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(report.exported, 1);
+        assert_eq!(report.skipped, 1);
+        assert!(xml.contains("<series_animedb_id>110</series_animedb_id>"));
+        // The editor fields came through the sync
+        assert!(xml.contains("<my_start_date>2023-10-01</my_start_date>"));
+        assert!(xml.contains("<my_finish_date>0000-00-00</my_finish_date>"));
+        assert!(xml.contains("<my_comments>Watched with friends</my_comments>"));
+    }
+}
@@ -0,0 +1,195 @@
+use directories::ProjectDirs;
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::utils::error::AppError;
+
+// Room for a few thousand covers and banners
+pub const DEFAULT_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+struct CachedFile {
+    size: u64,
+    last_used: SystemTime,
+}
+
+/// Downloaded images on disk, evicting the least recently used once the
+/// total size goes over `max_bytes`. A file's modification time records
+/// when it was last used, so the order survives restarts.
+pub struct ImageCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    files: HashMap<String, CachedFile>,
+    total_bytes: u64,
+}
+
+impl ImageCache {
+    /// Open the cache in `dir`, indexing whatever an earlier session left
+    pub fn open(dir: PathBuf, max_bytes: u64) -> Result<Self, AppError> {
+        fs::create_dir_all(&dir)?;
+
+        let mut files = HashMap::new();
+        let mut total_bytes = 0;
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+
+            total_bytes += metadata.len();
+            files.insert(
+                name,
+                CachedFile {
+                    size: metadata.len(),
+                    last_used: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                },
+            );
+        }
+        debug!(
+            "Opened image cache with {} files ({} bytes)",
+            files.len(),
+            total_bytes
+        );
+
+        let mut cache = Self {
+            dir,
+            max_bytes,
+            files,
+            total_bytes,
+        };
+        // The limit may have been lowered since the last session
+        cache.evict(None);
+        Ok(cache)
+    }
+
+    /// The `images` folder in the project data directory
+    pub fn default_dir() -> Result<PathBuf, AppError> {
+        let proj_dirs = ProjectDirs::from("me", "camniel", "AniListClient")
+            .ok_or_else(|| AppError::ConfigError("Could not determine project directory".into()))?;
+        Ok(proj_dirs.data_dir().join("images"))
+    }
+
+    /// The cached file for `url`, marking it as just used
+    pub fn get(&mut self, url: &str) -> Option<PathBuf> {
+        let name = file_name(url);
+        let file = self.files.get_mut(&name)?;
+        let path = self.dir.join(&name);
+
+        file.last_used = SystemTime::now();
+        if let Err(e) = touch(&path, file.last_used) {
+            // Deleted behind our back
+            if !path.exists() {
+                self.total_bytes -= file.size;
+                self.files.remove(&name);
+                return None;
+            }
+            debug!("Could not update {}: {}", path.display(), e);
+        }
+
+        Some(path)
+    }
+
+    /// Store the image downloaded from `url` and return where it was written
+    pub fn insert(&mut self, url: &str, bytes: &[u8]) -> Result<PathBuf, AppError> {
+        let name = file_name(url);
+        let path = self.dir.join(&name);
+        fs::write(&path, bytes)?;
+
+        let file = CachedFile {
+            size: bytes.len() as u64,
+            last_used: SystemTime::now(),
+        };
+        self.total_bytes += file.size;
+        if let Some(previous) = self.files.insert(name.clone(), file) {
+            self.total_bytes -= previous.size;
+        }
+
+        self.evict(Some(&name));
+        Ok(path)
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    // Remove the least recently used files until the cache fits, keeping `keep`
+    fn evict(&mut self, keep: Option<&str>) {
+        if self.total_bytes <= self.max_bytes {
+            return;
+        }
+
+        let mut by_age: Vec<(SystemTime, String)> = self
+            .files
+            .iter()
+            .filter(|(name, _)| Some(name.as_str()) != keep)
+            .map(|(name, file)| (file.last_used, name.clone()))
+            .collect();
+        by_age.sort();
+
+        for (_, name) in by_age {
+            if self.total_bytes <= self.max_bytes {
+                break;
+            }
+            if let Err(e) = fs::remove_file(self.dir.join(&name)) {
+                warn!("Failed to evict cached image {}: {}", name, e);
+            }
+            if let Some(file) = self.files.remove(&name) {
+                self.total_bytes -= file.size;
+            }
+        }
+    }
+}
+
+// A stable name for the url: an FNV-1a hash plus the original extension
+fn file_name(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    let extension = Path::new(url.split(['?', '#']).next().unwrap_or(url))
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .filter(|ext| ext.len() <= 4 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or("img");
+    format!("{:016x}.{}", hash, extension)
+}
+
+fn touch(path: &Path, time: SystemTime) -> std::io::Result<()> {
+    File::options().write(true).open(path)?.set_modified(time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_least_recently_used_images_are_evicted() {
+        let dir = std::env::temp_dir().join(format!("anilist-images-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut cache = ImageCache::open(dir.clone(), 250).unwrap();
+
+        cache.insert("https://img/a.jpg", &[0; 100]).unwrap();
+        cache.insert("https://img/b.png", &[0; 100]).unwrap();
+        // Make sure `a` is used after `b` even on coarse filesystem clocks
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(cache.get("https://img/a.jpg").is_some());
+        cache.insert("https://img/c.jpg", &[0; 100]).unwrap();
+
+        assert!(cache.get("https://img/b.png").is_none());
+        assert_eq!(cache.total_bytes(), 200);
+
+        // Reopening picks up what is on disk
+        let mut reopened = ImageCache::open(dir.clone(), 250).unwrap();
+        assert_eq!(reopened.total_bytes(), 200);
+        let path = reopened.get("https://img/a.jpg").unwrap();
+        assert!(path.to_string_lossy().ends_with(".jpg"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
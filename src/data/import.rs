@@ -0,0 +1,751 @@
+use log::{info, warn};
+use std::collections::HashMap;
+
+use crate::api::client::AniListClient;
+use crate::api::models::{
+    ListEntryDetails, MediaListEntry, MediaSort, MediaTitle, MediaType, SearchFilters,
+};
+use crate::data::database::ListUpdate;
+use crate::data::outbox::{Outbox, SaveOutcome};
+use crate::utils::error::AppError;
+
+// Lowest title similarity accepted as a match, out of 1. Strict enough that
+// another season of the same show doesn't match.
+const MIN_TITLE_SIMILARITY: f64 = 0.9;
+
+/// One entry read from another tracker's export
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportRow {
+    // Position of the entry in the file, counting from 1
+    pub line: usize,
+    pub title: String,
+    pub id_mal: Option<i32>,
+    // Only in files exported from AniList, e.g. our own CSV export
+    pub media_id: Option<i32>,
+    pub status: String,
+    // 0-100
+    pub score: Option<f64>,
+    pub progress: Option<i32>,
+}
+
+/// How a row was matched to an AniList media
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchSource {
+    AniListId,
+    MalId,
+    Title,
+}
+
+impl std::fmt::Display for MatchSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchSource::AniListId => write!(f, "AniList id"),
+            MatchSource::MalId => write!(f, "MAL id"),
+            MatchSource::Title => write!(f, "Title"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    // Not on the list yet
+    New,
+    // Already on the list with the same status, score and progress
+    Unchanged,
+    // Already on the list with different values, kept unless overwritten
+    Conflict,
+    Unresolved,
+}
+
+/// The viewer's current entry for a matched row
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExistingEntry {
+    pub entry_id: i32,
+    pub status: String,
+    pub score: Option<f64>,
+    pub progress: Option<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportItem {
+    pub row: ImportRow,
+    pub media_id: Option<i32>,
+    // The AniList title of the match, when known
+    pub title: Option<MediaTitle>,
+    pub matched_by: Option<MatchSource>,
+    pub existing: Option<ExistingEntry>,
+    pub resolution: Resolution,
+}
+
+impl ImportItem {
+    fn new(
+        row: ImportRow,
+        found: Option<(i32, Option<MediaTitle>, MatchSource)>,
+        list: &HashMap<i32, &MediaListEntry>,
+    ) -> Self {
+        let Some((media_id, title, matched_by)) = found else {
+            return Self {
+                row,
+                media_id: None,
+                title: None,
+                matched_by: None,
+                existing: None,
+                resolution: Resolution::Unresolved,
+            };
+        };
+
+        let entry = list.get(&media_id);
+        let existing = entry.map(|entry| ExistingEntry {
+            entry_id: entry.id,
+            status: entry.status.clone(),
+            score: entry.score,
+            progress: entry.progress,
+        });
+        let resolution = match &existing {
+            None => Resolution::New,
+            Some(existing)
+                if existing.status == row.status
+                    && existing.score.unwrap_or(0.0) == row.score.unwrap_or(0.0)
+                    && existing.progress.unwrap_or(0) == row.progress.unwrap_or(0) =>
+            {
+                Resolution::Unchanged
+            }
+            Some(_) => Resolution::Conflict,
+        };
+        // Rows matched by AniList id carry no title of their own
+        let title = title.or_else(|| {
+            entry
+                .and_then(|entry| entry.media.as_ref())
+                .map(|media| media.title.clone())
+        });
+
+        Self {
+            row,
+            media_id: Some(media_id),
+            title,
+            matched_by: Some(matched_by),
+            existing,
+            resolution,
+        }
+    }
+
+    /// The edit applying this row makes, `None` for unresolved and unchanged rows
+    pub fn update(&self) -> Option<ListUpdate> {
+        if matches!(
+            self.resolution,
+            Resolution::Unresolved | Resolution::Unchanged
+        ) {
+            return None;
+        }
+
+        Some(ListUpdate {
+            entry_id: self
+                .existing
+                .as_ref()
+                .map(|existing| existing.entry_id)
+                .filter(|id| *id > 0),
+            media_id: self.media_id?,
+            media_type: MediaType::ANIME,
+            status: Some(self.row.status.clone()),
+            score: self.row.score,
+            progress: self.row.progress,
+            progress_volumes: None,
+            details: ListEntryDetails::default(),
+        })
+    }
+}
+
+/// Outcome of applying an import
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    // Rows a dry run would have saved
+    pub dry_run: bool,
+    pub saved: usize,
+    pub queued: usize,
+    // Title in the file and the error
+    pub failed: Vec<(String, String)>,
+}
+
+/// Read a MyAnimeList (or Kitsu) XML export, or a CSV file with a header row
+pub fn parse(contents: &str) -> Result<Vec<ImportRow>, AppError> {
+    let contents = contents.trim_start_matches('\u{feff}');
+    let rows = if contents.trim_start().starts_with('<') {
+        parse_mal_xml(contents)?
+    } else {
+        parse_csv(contents)?
+    };
+
+    if rows.is_empty() {
+        return Err(AppError::ImportError("No entries found in the file".into()));
+    }
+    Ok(rows)
+}
+
+pub fn parse_mal_xml(xml: &str) -> Result<Vec<ImportRow>, AppError> {
+    if !xml.contains("<anime>") && xml.contains("<manga>") {
+        return Err(AppError::ImportError(
+            "Only anime lists can be imported".into(),
+        ));
+    }
+
+    let mut rows = Vec::new();
+    for (i, block) in xml.split("<anime>").skip(1).enumerate() {
+        let block = block.split("</anime>").next().unwrap_or(block);
+        let line = i + 1;
+
+        let status = tag(block, "my_status").unwrap_or_default();
+        let mut status = list_status(&status).ok_or_else(|| {
+            AppError::ImportError(format!("Entry {}: unknown status \"{}\"", line, status))
+        })?;
+        // MyAnimeList keeps rewatches as completed entries with a flag
+        if status == "COMPLETED" && tag(block, "my_rewatching").as_deref() == Some("1") {
+            status = "REPEATING";
+        }
+
+        rows.push(ImportRow {
+            line,
+            title: tag(block, "series_title").unwrap_or_default(),
+            id_mal: tag(block, "series_animedb_id")
+                .and_then(|id| id.parse().ok())
+                .filter(|id| *id > 0),
+            media_id: None,
+            status: status.to_string(),
+            // 0-10 on MyAnimeList
+            score: tag(block, "my_score")
+                .and_then(|score| score.parse::<f64>().ok())
+                .filter(|score| *score > 0.0)
+                .map(|score| score * 10.0),
+            progress: tag(block, "my_watched_episodes").and_then(|p| p.parse().ok()),
+        });
+    }
+
+    Ok(rows)
+}
+
+/// A CSV file with a header row naming at least a title column. Scores of at
+/// most 10 are taken to be on a 10-point scale.
+pub fn parse_csv(csv: &str) -> Result<Vec<ImportRow>, AppError> {
+    let mut records = csv_records(csv).into_iter();
+    let header: Vec<String> = records
+        .next()
+        .unwrap_or_default()
+        .iter()
+        .map(|name| name.trim().to_lowercase().replace([' ', '-'], "_"))
+        .collect();
+    let column = |names: &[&str]| {
+        header
+            .iter()
+            .position(|name| names.contains(&name.as_str()))
+    };
+
+    let title = column(&["title", "name", "series_title", "canonical_title", "romaji"])
+        .ok_or_else(|| AppError::ImportError("The file has no title column".into()))?;
+    let id_mal = column(&["id_mal", "mal_id", "series_animedb_id", "myanimelist_id"]);
+    let media_id = column(&["media_id", "anilist_id"]);
+    let status = column(&["status", "my_status", "watch_status"]);
+    let score = column(&["score", "rating", "my_score"]);
+    let progress = column(&[
+        "progress",
+        "episodes_watched",
+        "watched_episodes",
+        "my_watched_episodes",
+    ]);
+
+    let mut rows = Vec::new();
+    for (i, record) in records.enumerate() {
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        // The header is line 1
+        let line = i + 2;
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+
+        let status = match field(status) {
+            Some(status) => list_status(status).ok_or_else(|| {
+                AppError::ImportError(format!("Line {}: unknown status \"{}\"", line, status))
+            })?,
+            None => "PLANNING",
+        };
+
+        rows.push(ImportRow {
+            line,
+            title: field(Some(title)).unwrap_or_default().to_string(),
+            id_mal: field(id_mal).and_then(|id| id.parse().ok()),
+            media_id: field(media_id).and_then(|id| id.parse().ok()),
+            status: status.to_string(),
+            score: field(score)
+                .and_then(|score| score.parse::<f64>().ok())
+                .filter(|score| *score > 0.0),
+            progress: field(progress).and_then(|p| p.parse().ok()),
+        });
+    }
+
+    if rows
+        .iter()
+        .filter_map(|row| row.score)
+        .all(|score| score <= 10.0)
+    {
+        for row in &mut rows {
+            row.score = row.score.map(|score| score * 10.0);
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Find the AniList media of every row: by AniList id when the file has one,
+/// then by MyAnimeList id in batches, then by searching the title. Matches
+/// are compared with `list`, the viewer's current anime list.
+pub async fn resolve(
+    client: &AniListClient,
+    rows: Vec<ImportRow>,
+    list: &[MediaListEntry],
+) -> Result<Vec<ImportItem>, AppError> {
+    let mal_ids: Vec<i32> = rows
+        .iter()
+        .filter(|row| row.media_id.is_none())
+        .filter_map(|row| row.id_mal)
+        .collect();
+    let by_mal_id: HashMap<i32, (i32, MediaTitle)> = if mal_ids.is_empty() {
+        HashMap::new()
+    } else {
+        client
+            .get_media_by_mal_ids(&mal_ids, MediaType::ANIME)
+            .await?
+            .into_iter()
+            .map(|(id_mal, media_id, title)| (id_mal, (media_id, title)))
+            .collect()
+    };
+    let list: HashMap<i32, &MediaListEntry> =
+        list.iter().map(|entry| (entry.media_id, entry)).collect();
+
+    let mut items = Vec::with_capacity(rows.len());
+    for row in rows {
+        let found = if let Some(media_id) = row.media_id {
+            Some((media_id, None, MatchSource::AniListId))
+        } else if let Some((media_id, title)) = row.id_mal.and_then(|id| by_mal_id.get(&id)) {
+            Some((*media_id, Some(title.clone()), MatchSource::MalId))
+        } else if row.title.trim().is_empty() {
+            None
+        } else {
+            search_title(client, &row.title)
+                .await?
+                .map(|(media_id, title)| (media_id, Some(title), MatchSource::Title))
+        };
+
+        items.push(ImportItem::new(row, found, &list));
+    }
+
+    info!(
+        "Resolved {} of {} imported rows",
+        items
+            .iter()
+            .filter(|item| item.resolution != Resolution::Unresolved)
+            .count(),
+        items.len()
+    );
+    Ok(items)
+}
+
+/// Save `items` one `SaveMediaListEntry` at a time through the client's rate
+/// limiter, or queue them in offline mode. Unresolved and unchanged rows are
+/// skipped; a dry run only counts what would be saved.
+pub async fn apply(
+    client: &AniListClient,
+    outbox: &Outbox,
+    items: &[ImportItem],
+    dry_run: bool,
+    offline_mode: bool,
+) -> ImportReport {
+    let mut report = ImportReport {
+        dry_run,
+        ..Default::default()
+    };
+
+    for item in items {
+        let Some(update) = item.update() else {
+            continue;
+        };
+        if dry_run {
+            report.saved += 1;
+            continue;
+        }
+
+        match outbox.save(client, update, offline_mode).await {
+            Ok(SaveOutcome::Saved) => report.saved += 1,
+            Ok(SaveOutcome::Queued) => report.queued += 1,
+            Err(e) => {
+                warn!("Failed to import {}: {}", item.row.title, e);
+                report.failed.push((item.row.title.clone(), e.to_string()));
+            }
+        }
+    }
+
+    info!(
+        "Import finished: {} saved, {} queued, {} failed{}",
+        report.saved,
+        report.queued,
+        report.failed.len(),
+        if dry_run { " (dry run)" } else { "" }
+    );
+    report
+}
+
+// The best search result for `title`, if it is close enough
+async fn search_title(
+    client: &AniListClient,
+    title: &str,
+) -> Result<Option<(i32, MediaTitle)>, AppError> {
+    // Adult titles on the imported list have to resolve too
+    let filters = SearchFilters {
+        query: title.to_string(),
+        sort: Some(MediaSort::SEARCH_MATCH),
+        include_adult: true,
+        ..Default::default()
+    };
+    let results = match client
+        .search_media_filtered(&filters, MediaType::ANIME, Some(1), Some(5))
+        .await
+    {
+        Ok(results) => results,
+        Err(e @ AppError::NetworkError(_)) => return Err(e),
+        Err(e) => {
+            warn!("Search for \"{}\" failed: {}", title, e);
+            return Ok(None);
+        }
+    };
+
+    let candidates = results
+        .page
+        .and_then(|page| page.media)
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .map(|media| {
+            (
+                media.id as i32,
+                media.title.map(MediaTitle::from).unwrap_or_default(),
+            )
+        });
+    Ok(best_match(title, candidates))
+}
+
+// The first candidate with the most similar title, ties going to the search order
+fn best_match(
+    title: &str,
+    candidates: impl IntoIterator<Item = (i32, MediaTitle)>,
+) -> Option<(i32, MediaTitle)> {
+    let mut best: Option<(f64, (i32, MediaTitle))> = None;
+
+    for candidate in candidates {
+        let names = &candidate.1;
+        let score = [&names.romaji, &names.english, &names.native]
+            .into_iter()
+            .flatten()
+            .map(|name| similarity(title, name))
+            .fold(0.0, f64::max);
+
+        if score >= MIN_TITLE_SIMILARITY && best.as_ref().is_none_or(|(s, _)| score > *s) {
+            best = Some((score, candidate));
+        }
+    }
+
+    best.map(|(_, candidate)| candidate)
+}
+
+// Dice coefficient of the character pairs, ignoring case, spaces and punctuation
+fn similarity(a: &str, b: &str) -> f64 {
+    let normalize = |s: &str| -> Vec<char> {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let (a, b) = (normalize(a), normalize(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+
+    let pairs = |s: &[char]| -> Vec<(char, char)> { s.windows(2).map(|w| (w[0], w[1])).collect() };
+    let a_pairs = pairs(&a);
+    let mut b_pairs = pairs(&b);
+    let total = a_pairs.len() + b_pairs.len();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let mut shared = 0;
+    for pair in a_pairs {
+        if let Some(i) = b_pairs.iter().position(|p| *p == pair) {
+            b_pairs.swap_remove(i);
+            shared += 1;
+        }
+    }
+    2.0 * shared as f64 / total as f64
+}
+
+/// Map a status from MyAnimeList, Kitsu or AniList to AniList's name
+fn list_status(status: &str) -> Option<&'static str> {
+    let status: String = status
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    match status.as_str() {
+        "current" | "watching" | "1" => Some("CURRENT"),
+        "completed" | "2" => Some("COMPLETED"),
+        "paused" | "onhold" | "3" => Some("PAUSED"),
+        "dropped" | "4" => Some("DROPPED"),
+        "planning" | "planned" | "plantowatch" | "6" => Some("PLANNING"),
+        "repeating" | "rewatching" => Some("REPEATING"),
+        _ => None,
+    }
+}
+
+// The text of the first `<name>` element in `block`, without CDATA or entities
+fn tag(block: &str, name: &str) -> Option<String> {
+    let start = block.find(&format!("<{}>", name))? + name.len() + 2;
+    let end = start + block[start..].find(&format!("</{}>", name))?;
+    let text = block[start..end].trim();
+
+    let text = match text
+        .strip_prefix("<![CDATA[")
+        .and_then(|t| t.strip_suffix("]]>"))
+    {
+        Some(data) => data.to_string(),
+        None => text
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"),
+    };
+    Some(text.trim().to_string())
+}
+
+// Records of a CSV file, with quoted fields spanning commas, quotes and lines
+fn csv_records(csv: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            '\r' if !quoted => {}
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::rate_limit::RetryPolicy;
+    use crate::api::transport::MockTransport;
+    use crate::data::database::Database;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parses_mal_xml_and_csv() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" ?>
+            <myanimelist>
+                <anime>
+                    <series_animedb_id>5114</series_animedb_id>
+                    <series_title><![CDATA[Fullmetal Alchemist: Brotherhood]]></series_title>
+                    <my_watched_episodes>64</my_watched_episodes>
+                    <my_score>10</my_score>
+                    <my_status>Completed</my_status>
+                    <my_rewatching>1</my_rewatching>
+                </anime>
+                <anime>
+                    <series_animedb_id>1</series_animedb_id>
+                    <series_title>Tom &amp; Jerry</series_title>
+                    <my_watched_episodes>0</my_watched_episodes>
+                    <my_score>0</my_score>
+                    <my_status>Plan to Watch</my_status>
+                </anime>
+            </myanimelist>"#;
+
+        let rows = parse(xml).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].title, "Fullmetal Alchemist: Brotherhood");
+        assert_eq!(rows[0].id_mal, Some(5114));
+        assert_eq!(rows[0].status, "REPEATING");
+        assert_eq!(rows[0].score, Some(100.0));
+        assert_eq!(rows[0].progress, Some(64));
+        assert_eq!(rows[1].title, "Tom & Jerry");
+        assert_eq!(rows[1].status, "PLANNING");
+        assert_eq!(rows[1].score, None);
+
+        let csv = "\u{feff}Title,MAL ID,Status,Rating,Episodes Watched\r\n\
+                   \"Kaguya-sama, Love is War\",37999,watching,8.5,3\r\n\
+                   \r\n\
+                   \"The \"\"Hero\"\"\",,on_hold,,\r\n";
+        let rows = parse(csv).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].title, "Kaguya-sama, Love is War");
+        assert_eq!(rows[0].id_mal, Some(37999));
+        assert_eq!(rows[0].status, "CURRENT");
+        // A 10-point file
+        assert_eq!(rows[0].score, Some(85.0));
+        assert_eq!(rows[0].progress, Some(3));
+        assert_eq!(rows[1].line, 4);
+        assert_eq!(rows[1].title, "The \"Hero\"");
+        assert_eq!(rows[1].status, "PAUSED");
+
+        assert!(matches!(
+            parse("Title,Status\nSomething,binged\n"),
+            Err(AppError::ImportError(_))
+        ));
+        assert!(matches!(
+            parse("Name of show\nSomething\n").map(|rows| rows.len()),
+            Err(AppError::ImportError(_))
+        ));
+    }
+
+    fn row(line: usize, title: &str, id_mal: Option<i32>) -> ImportRow {
+        ImportRow {
+            line,
+            title: title.to_string(),
+            id_mal,
+            media_id: None,
+            status: "COMPLETED".to_string(),
+            score: Some(80.0),
+            progress: Some(12),
+        }
+    }
+
+    fn search_result(id: i32, romaji: &str) -> serde_json::Value {
+        json!({ "Page": { "pageInfo": null, "media": [{
+            "id": id,
+            "title": { "romaji": romaji, "english": null, "native": null },
+            "coverImage": null,
+            "format": "TV",
+            "episodes": 12,
+            "seasonYear": 2020,
+            "averageScore": 80,
+            "popularity": 1000
+        }] } })
+    }
+
+    #[tokio::test]
+    async fn test_rows_resolve_by_mal_id_then_title() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_data(
+                    "MediaByMalId",
+                    json!({ "Page": { "media": [
+                        { "id": 101, "idMal": 1, "title": { "romaji": "Cowboy Bebop" } },
+                        { "id": 102, "idMal": 2, "title": { "romaji": "Trigun" } }
+                    ] } }),
+                )
+                .with_data("AnimeSearch", search_result(103, "Sousou no Frieren"))
+                .with_data("AnimeSearch", search_result(103, "Sousou no Frieren"))
+                .with_data("AnimeSearch", search_result(104, "Shingeki no Kyojin"))
+                .with_data(
+                    "UpdateMediaList",
+                    json!({ "SaveMediaListEntry": { "id": 900 } }),
+                ),
+        );
+        let client = AniListClient::with_token("token".to_string())
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy {
+                max_retries: 0,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(1),
+            });
+
+        let on_list = |media_id: i32, status: &str| MediaListEntry {
+            id: media_id * 10,
+            media_id,
+            status: status.to_string(),
+            score: Some(80.0),
+            progress: Some(12),
+            progress_volumes: None,
+            updated_at: 0,
+            media: None,
+        };
+        let list = vec![on_list(101, "COMPLETED"), on_list(102, "DROPPED")];
+        let rows = vec![
+            row(1, "Cowboy Bebop", Some(1)),
+            row(2, "Trigun", Some(2)),
+            row(3, "Frieren: Beyond Journey's End", None),
+            row(4, "Sousou no Frieren", None),
+            row(5, "Shingeki no Kyojin Season 2", None),
+        ];
+
+        let items = resolve(&client, rows, &list).await.unwrap();
+        let resolutions: Vec<Resolution> = items.iter().map(|item| item.resolution).collect();
+        assert_eq!(
+            resolutions,
+            vec![
+                Resolution::Unchanged,
+                Resolution::Conflict,
+                // The search only found the romaji title
+                Resolution::Unresolved,
+                Resolution::New,
+                // Another season is not a match
+                Resolution::Unresolved,
+            ]
+        );
+        assert_eq!(items[1].matched_by, Some(MatchSource::MalId));
+        assert_eq!(items[1].existing.as_ref().unwrap().status, "DROPPED");
+        assert_eq!(items[3].media_id, Some(103));
+        assert_eq!(items[3].matched_by, Some(MatchSource::Title));
+        // One batched id lookup, then a search per row without an id
+        assert_eq!(transport.requests().len(), 4);
+        // Adult titles are searched as well
+        assert!(transport.requests()[1].body["variables"]["isAdult"].is_null());
+
+        let db = Database::in_memory().unwrap();
+        db.save_auth(1, "token", None, None).unwrap();
+        let outbox = Outbox::new(Arc::new(Mutex::new(db)));
+
+        let dry_run = apply(&client, &outbox, &items, true, false).await;
+        assert_eq!(dry_run.saved, 2);
+        assert_eq!(transport.requests().len(), 4);
+
+        let report = apply(&client, &outbox, &items, false, false).await;
+        assert_eq!(report.saved, 2);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 6);
+        // The conflicting entry is updated in place
+        assert_eq!(requests[4].body["variables"]["id"], 1020);
+        assert_eq!(requests[5].body["variables"]["mediaId"], 103);
+
+        // In offline mode the rows wait in the outbox
+        let offline = apply(&client, &outbox, &items, false, true).await;
+        assert_eq!(offline.queued, 2);
+        assert_eq!(transport.requests().len(), 6);
+        assert_eq!(outbox.pending().unwrap().len(), 2);
+    }
+}
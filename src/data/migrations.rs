@@ -0,0 +1,354 @@
+use log::info;
+use rusqlite::{Connection, Result as SqlResult};
+
+use crate::utils::error::AppError;
+
+/// A single, ordered upgrade step of the database schema
+struct Migration {
+    version: i32,
+    description: &'static str,
+    up: fn(&Connection) -> SqlResult<()>,
+}
+
+// Append new steps at the end; never edit a step that has been released
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "initial schema",
+        up: initial_schema,
+    },
+    Migration {
+        version: 2,
+        description: "store manga list scores as REAL",
+        up: manga_scores_as_real,
+    },
+    Migration {
+        version: 3,
+        description: "track when each list was last synced",
+        up: list_sync_state,
+    },
+    Migration {
+        version: 4,
+        description: "queue notes, dates and custom lists with list edits",
+        up: pending_list_update_details,
+    },
+    Migration {
+        version: 5,
+        description: "store viewer preferences and queue scores on the 100-point scale",
+        up: viewer_preferences,
+    },
+    Migration {
+        version: 6,
+        description: "remember the MyAnimeList id of cached anime",
+        up: anime_mal_ids,
+    },
+    Migration {
+        version: 7,
+        description: "store stylised titles of cached media",
+        up: stylised_titles,
+    },
+    Migration {
+        version: 8,
+        description: "store notes, dates and custom lists of anime list entries",
+        up: list_entry_details,
+    },
+];
+
+/// The schema version this build of the app expects
+pub const SCHEMA_VERSION: i32 = MIGRATIONS[MIGRATIONS.len() - 1].version;
+
+pub fn schema_version(conn: &Connection) -> SqlResult<i32> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
+pub fn table_exists(conn: &Connection, name: &str) -> SqlResult<bool> {
+    conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)",
+        [name],
+        |row| row.get(0),
+    )
+}
+
+/// Whether `migrate` would upgrade tables that already hold data. Databases
+/// created before versioning report version 0 but still have their tables.
+pub fn needs_upgrade(conn: &Connection) -> SqlResult<bool> {
+    let version = schema_version(conn)?;
+    Ok(version < SCHEMA_VERSION
+        && (version > 0 || table_exists(conn, "cached_anime")? || table_exists(conn, "user_auth")?))
+}
+
+/// Bring the database up to `SCHEMA_VERSION`, one transaction per step.
+///
+/// Callers with a database on disk snapshot it first, see `backup::create`.
+pub fn migrate(conn: &mut Connection) -> Result<(), AppError> {
+    let current = schema_version(conn)?;
+
+    if current > SCHEMA_VERSION {
+        return Err(AppError::DatabaseError(format!(
+            "Database schema version {} is newer than this version of the app supports ({}). \
+             Please update the app.",
+            current, SCHEMA_VERSION
+        )));
+    }
+
+    let pending: Vec<&Migration> = MIGRATIONS.iter().filter(|m| m.version > current).collect();
+
+    for migration in pending {
+        info!(
+            "Migrating database to version {}: {}",
+            migration.version, migration.description
+        );
+
+        let tx = conn.transaction()?;
+        (migration.up)(&tx).map_err(|e| {
+            AppError::DatabaseError(format!(
+                "Migration to version {} ({}) failed: {}",
+                migration.version, migration.description, e
+            ))
+        })?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+fn initial_schema(conn: &Connection) -> SqlResult<()> {
+    // Create anime cache table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cached_anime(
+            id INTEGER PRIMARY KEY,
+            title TEXT NOT NULL,
+            english_title TEXT,
+            native_title TEXT,
+            description TEXT,
+            episodes INTEGER,
+            duration INTEGER,
+            genres TEXT,
+            average_score REAL,
+            cover_image TEXT,
+            banner_image TEXT,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cached_list_entries (
+            id INTEGER PRIMARY KEY,
+            user_id INTEGER NOT NULL,
+            media_id INTEGER NOT NULL,
+            status TEXT NOT NULL,
+            score REAL,
+            progress INTEGER,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY(media_id) REFERENCES cached_anime(id),
+            UNIQUE(user_id, media_id)
+        )",
+        [],
+    )?;
+
+    // Create manga cache tables
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cached_manga(
+            id INTEGER PRIMARY KEY,
+            title TEXT NOT NULL,
+            english_title TEXT,
+            native_title TEXT,
+            description TEXT,
+            chapters INTEGER,
+            volumes INTEGER,
+            genres TEXT,
+            average_score REAL,
+            cover_image TEXT,
+            banner_image TEXT,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cached_manga_list_entries (
+            id INTEGER PRIMARY KEY,
+            user_id INTEGER NOT NULL,
+            media_id INTEGER NOT NULL,
+            status TEXT NOT NULL,
+            score INTEGER,
+            progress INTEGER,
+            progress_volumes INTEGER,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY(media_id) REFERENCES cached_manga(id),
+            UNIQUE(user_id, media_id)
+        )",
+        [],
+    )?;
+
+    // Outbox of list edits that still have to be sent to AniList
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS pending_list_updates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            entry_id INTEGER,
+            media_id INTEGER NOT NULL,
+            media_type TEXT NOT NULL,
+            status TEXT,
+            score REAL,
+            progress INTEGER,
+            progress_volumes INTEGER,
+            created_at TEXT NOT NULL,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            last_error TEXT
+        )",
+        [],
+    )?;
+
+    // Create user auth table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS user_auth (
+            user_id INTEGER PRIMARY KEY,
+            access_token TEXT NOT NULL,
+            refresh_token TEXT,
+            expires_at TEXT,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+// Scores can be decimals (POINT_10_DECIMAL), but the manga list stored them as INTEGER
+fn manga_scores_as_real(conn: &Connection) -> SqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE cached_manga_list_entries_new (
+            id INTEGER PRIMARY KEY,
+            user_id INTEGER NOT NULL,
+            media_id INTEGER NOT NULL,
+            status TEXT NOT NULL,
+            score REAL,
+            progress INTEGER,
+            progress_volumes INTEGER,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY(media_id) REFERENCES cached_manga(id),
+            UNIQUE(user_id, media_id)
+        );
+        INSERT INTO cached_manga_list_entries_new
+            SELECT id, user_id, media_id, status, CAST(score AS REAL),
+                progress, progress_volumes, updated_at
+            FROM cached_manga_list_entries;
+        DROP TABLE cached_manga_list_entries;
+        ALTER TABLE cached_manga_list_entries_new RENAME TO cached_manga_list_entries;",
+    )
+}
+
+fn list_sync_state(conn: &Connection) -> SqlResult<()> {
+    conn.execute(
+        "CREATE TABLE list_sync_state (
+            user_id INTEGER NOT NULL,
+            media_type TEXT NOT NULL,
+            last_synced_at TEXT NOT NULL,
+            PRIMARY KEY(user_id, media_type)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn pending_list_update_details(conn: &Connection) -> SqlResult<()> {
+    // ListEntryDetails as JSON, NULL for edits queued before this version
+    conn.execute(
+        "ALTER TABLE pending_list_updates ADD COLUMN details TEXT",
+        [],
+    )?;
+    Ok(())
+}
+
+fn viewer_preferences(conn: &Connection) -> SqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE viewer_preferences (
+            user_id INTEGER PRIMARY KEY,
+            preferences TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        -- Queued scores came from a 0-10 slider
+        UPDATE pending_list_updates SET score = score * 10 WHERE score IS NOT NULL;",
+    )
+}
+
+fn anime_mal_ids(conn: &Connection) -> SqlResult<()> {
+    conn.execute("ALTER TABLE cached_anime ADD COLUMN id_mal INTEGER", [])?;
+    Ok(())
+}
+
+fn stylised_titles(conn: &Connection) -> SqlResult<()> {
+    conn.execute_batch(
+        "ALTER TABLE cached_anime ADD COLUMN romaji_stylised TEXT;
+         ALTER TABLE cached_anime ADD COLUMN english_stylised TEXT;
+         ALTER TABLE cached_anime ADD COLUMN native_stylised TEXT;
+         ALTER TABLE cached_manga ADD COLUMN romaji_stylised TEXT;
+         ALTER TABLE cached_manga ADD COLUMN english_stylised TEXT;
+         ALTER TABLE cached_manga ADD COLUMN native_stylised TEXT;",
+    )
+}
+
+fn list_entry_details(conn: &Connection) -> SqlResult<()> {
+    // ListEntryDetails as JSON, NULL until the list is synced again
+    conn.execute(
+        "ALTER TABLE cached_list_entries ADD COLUMN details TEXT",
+        [],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fresh_database_is_fully_migrated() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+
+        // Running again is a no-op
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_refuses_newer_schema() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+            .unwrap();
+
+        assert!(matches!(
+            migrate(&mut conn),
+            Err(AppError::DatabaseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_upgrade_keeps_data() {
+        // A version 1 database with an integer manga score
+        let mut conn = Connection::open_in_memory().unwrap();
+        initial_schema(&conn).unwrap();
+        conn.pragma_update(None, "user_version", 1).unwrap();
+        conn.execute_batch(
+            "INSERT INTO cached_manga (id, title, updated_at) VALUES (30013, 'One Piece', '');
+             INSERT INTO cached_manga_list_entries
+                (id, user_id, media_id, status, score, updated_at)
+                VALUES (1, 1, 30013, 'CURRENT', 9, '');",
+        )
+        .unwrap();
+
+        migrate(&mut conn).unwrap();
+
+        let score: f64 = conn
+            .query_row(
+                "SELECT score FROM cached_manga_list_entries WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(score, 9.0);
+    }
+}
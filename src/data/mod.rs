@@ -1,6 +1,14 @@
+pub mod backup;
 pub mod cache;
+pub mod cache_layer;
 pub mod database;
+pub mod export;
+pub mod image_cache;
+pub mod import;
+pub mod migrations;
 pub mod models;
+pub mod outbox;
+pub mod sync;
 
 // Re-export commonly used types
 pub use database::Database;
@@ -24,12 +24,40 @@ pub struct CachedListEntry {
     pub id: i32,
     pub user_id: i32,
     pub media_id: i32,
-    pub status: String, // CURRENT, PLANNING, COMPLETED, etc.
-    pub score: Option<i32>,
+    pub status: String,     // CURRENT, PLANNING, COMPLETED, etc.
+    pub score: Option<f64>, // 0-100, see ScoreFormat
     pub progress: Option<i32>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedManga {
+    pub id: i32,
+    pub title: String,
+    pub english_title: Option<String>,
+    pub native_title: Option<String>,
+    pub description: Option<String>,
+    pub chapters: Option<i32>,
+    pub volumes: Option<i32>,
+    pub genres: String, // comma-separated
+    pub average_score: Option<f64>,
+    pub cover_image: Option<String>,
+    pub banner_image: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedMangaListEntry {
+    pub id: i32,
+    pub user_id: i32,
+    pub media_id: i32,
+    pub status: String,        // CURRENT, PLANNING, COMPLETED, etc.
+    pub score: Option<f64>,    // 0-100, see ScoreFormat
+    pub progress: Option<i32>, // chapters read
+    pub progress_volumes: Option<i32>,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserAuth {
     pub user_id: i32,
@@ -0,0 +1,769 @@
+use log::{info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::api::client::{update_media_list, AniListClient};
+use crate::api::models::{ListEntryDetails, MediaType};
+use crate::api::my_list::{BulkEdit, ListItem};
+use crate::data::cache_layer::CacheLayer;
+use crate::data::database::{Database, ListUpdate, PendingListUpdate, MAX_LIST_UPDATE_ATTEMPTS};
+use crate::utils::error::AppError;
+
+/// Whether an edit reached AniList or is waiting in the outbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveOutcome {
+    Saved,
+    Queued,
+}
+
+/// Why a queued list edit could not be sent
+#[derive(Debug, Clone)]
+pub struct ReplayFailure {
+    pub media_id: i32,
+    pub media_type: MediaType,
+    pub error: String,
+    // Rejected for the last time, it won't be sent again on its own
+    pub gave_up: bool,
+}
+
+/// Outcome of sending the outbox to AniList
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    pub synced: usize,
+    pub failed: Vec<ReplayFailure>,
+    // Edits left in the outbox, including the failed ones
+    pub remaining: usize,
+}
+
+/// Persistent queue of list edits that are applied locally straight away and
+/// sent to AniList, in order, once we're back online.
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    db: Arc<Mutex<Database>>,
+    // Told about every edit so it stops serving the old entry
+    cache: Option<CacheLayer>,
+    // Set while a replay runs, shared by every clone of the outbox
+    replaying: Arc<AtomicBool>,
+}
+
+// Clears the in-flight flag when the replay ends, however it ends
+struct ReplayGuard(Arc<AtomicBool>);
+
+impl Drop for ReplayGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl Outbox {
+    pub fn new(db: Arc<Mutex<Database>>) -> Self {
+        Self {
+            db,
+            cache: None,
+            replaying: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn with_cache(mut self, cache: CacheLayer) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Store the edit and apply it to the cached list
+    pub fn queue(&self, update: ListUpdate) -> Result<i64, AppError> {
+        let db = self.lock()?;
+        let id = db.enqueue_list_update(&update)?;
+
+        // Without a stored user there is no local list to update
+        let user_id = db.get_user_id().ok();
+        if let Some(user_id) = user_id {
+            db.apply_list_update(user_id, &update)?;
+        }
+        self.invalidate(user_id, update.media_id);
+
+        info!(
+            "Queued offline edit for {:?} {}",
+            update.media_type, update.media_id
+        );
+        Ok(id)
+    }
+
+    /// Send the edit to AniList, or queue it when offline or the network is down
+    pub async fn save(
+        &self,
+        client: &AniListClient,
+        update: ListUpdate,
+        offline_mode: bool,
+    ) -> Result<SaveOutcome, AppError> {
+        if offline_mode {
+            self.queue(update)?;
+            return Ok(SaveOutcome::Queued);
+        }
+
+        match client.save_list_entry(&update).await {
+            Ok(data) => {
+                // Keep the stored list in step with AniList until the next sync
+                let db = self.lock()?;
+                let user_id = db.get_user_id().ok();
+                if let Some(user_id) = user_id {
+                    let saved = ListUpdate {
+                        entry_id: update
+                            .entry_id
+                            .or(data.save_media_list_entry.map(|saved| saved.id as i32)),
+                        ..update.clone()
+                    };
+                    db.apply_list_update(user_id, &saved)?;
+                }
+                // Older queued edits would overwrite this one on the next replay
+                db.remove_pending_list_updates_for(update.media_id, update.media_type)?;
+                self.invalidate(user_id, update.media_id);
+                Ok(SaveOutcome::Saved)
+            }
+            Err(AppError::NetworkError(e)) => {
+                warn!("Network unavailable ({}), queueing edit", e);
+                self.queue(update)?;
+                Ok(SaveOutcome::Queued)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Delete an entry on AniList and drop it from the cached list and the
+    /// outbox. Entries that only exist locally (`entry_id` is `None`) are just
+    /// dropped.
+    pub async fn delete(
+        &self,
+        client: &AniListClient,
+        entry_id: Option<i32>,
+        media_id: i32,
+        media_type: MediaType,
+    ) -> Result<(), AppError> {
+        if let Some(entry_id) = entry_id {
+            client.delete_media_list_entry(entry_id).await?;
+        }
+
+        // Queued edits would recreate the entry on the next replay
+        let db = self.lock()?;
+        db.remove_pending_list_updates_for(media_id, media_type)?;
+        let user_id = db.get_user_id().ok();
+        if let Some(user_id) = user_id {
+            db.remove_list_entry(user_id, media_id, media_type)?;
+        }
+        self.invalidate(user_id, media_id);
+
+        info!("Deleted {:?} {} from the list", media_type, media_id);
+        Ok(())
+    }
+
+    /// Apply one edit to several entries through batched mutations, so a large
+    /// selection costs a request or two instead of one per entry. Offline, or
+    /// when the network is down, the edits are queued one by one; deleting
+    /// needs a connection.
+    pub async fn save_bulk(
+        &self,
+        client: &AniListClient,
+        items: &[ListItem],
+        media_type: MediaType,
+        edit: &BulkEdit,
+        offline_mode: bool,
+    ) -> Result<SaveOutcome, AppError> {
+        // Entries created offline have no AniList id until the outbox is replayed
+        let (synced, local): (Vec<&ListItem>, Vec<&ListItem>) =
+            items.iter().partition(|item| item.entry_id > 0);
+        let ids: Vec<i32> = synced.iter().map(|item| item.entry_id).collect();
+
+        if *edit == BulkEdit::Delete {
+            if !ids.is_empty() {
+                if offline_mode {
+                    return Err(AppError::NetworkError(
+                        "Deleting entries needs a connection".into(),
+                    ));
+                }
+                client.delete_media_list_entries(&ids).await?;
+            }
+
+            let db = self.lock()?;
+            let user_id = db.get_user_id().ok();
+            for item in items {
+                db.remove_pending_list_updates_for(item.media_id, media_type)?;
+                if let Some(user_id) = user_id {
+                    db.remove_list_entry(user_id, item.media_id, media_type)?;
+                }
+                self.invalidate(user_id, item.media_id);
+            }
+
+            info!("Deleted {} {:?} entries", items.len(), media_type);
+            return Ok(SaveOutcome::Saved);
+        }
+
+        if offline_mode {
+            for item in items {
+                self.queue(bulk_update(item, media_type, edit))?;
+            }
+            return Ok(SaveOutcome::Queued);
+        }
+
+        let result = match edit {
+            _ if ids.is_empty() => Ok(()),
+            BulkEdit::Status(status) => {
+                client
+                    .update_media_list_entries(&ids, Some(status), None)
+                    .await
+            }
+            BulkEdit::Score(score) => {
+                client
+                    .update_media_list_entries(&ids, None, Some(*score))
+                    .await
+            }
+            BulkEdit::MoveToList { .. } => {
+                let entries: Vec<(i32, Vec<String>)> = synced
+                    .iter()
+                    .map(|item| {
+                        let lists = edit.custom_lists_for(item).unwrap_or_default();
+                        (item.entry_id, lists)
+                    })
+                    .collect();
+                client.save_custom_lists(&entries).await
+            }
+            BulkEdit::Delete => Ok(()),
+        };
+
+        match result {
+            Ok(()) => {
+                {
+                    let db = self.lock()?;
+                    let user_id = db.get_user_id().ok();
+                    for item in &synced {
+                        if let Some(user_id) = user_id {
+                            db.apply_list_update(user_id, &bulk_update(item, media_type, edit))?;
+                        }
+                        db.remove_pending_list_updates_for(item.media_id, media_type)?;
+                        self.invalidate(user_id, item.media_id);
+                    }
+                }
+
+                for item in &local {
+                    self.queue(bulk_update(item, media_type, edit))?;
+                }
+                Ok(if local.is_empty() {
+                    SaveOutcome::Saved
+                } else {
+                    SaveOutcome::Queued
+                })
+            }
+            Err(AppError::NetworkError(e)) => {
+                warn!(
+                    "Network unavailable ({}), queueing {} edits",
+                    e,
+                    items.len()
+                );
+                for item in items {
+                    self.queue(bulk_update(item, media_type, edit))?;
+                }
+                Ok(SaveOutcome::Queued)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn pending(&self) -> Result<Vec<PendingListUpdate>, AppError> {
+        Ok(self.lock()?.get_pending_list_updates()?)
+    }
+
+    /// Edits AniList rejected `MAX_LIST_UPDATE_ATTEMPTS` times, which are no
+    /// longer sent
+    pub fn failed(&self) -> Result<Vec<PendingListUpdate>, AppError> {
+        Ok(self
+            .pending()?
+            .into_iter()
+            .filter(PendingListUpdate::is_failed)
+            .collect())
+    }
+
+    /// Drop a queued edit without sending it
+    pub fn discard(&self, id: i64) -> Result<(), AppError> {
+        Ok(self.lock()?.remove_pending_list_update(id)?)
+    }
+
+    /// Send a failed edit again on the next replay
+    pub fn retry(&self, id: i64) -> Result<(), AppError> {
+        Ok(self.lock()?.reset_list_update_attempts(id)?)
+    }
+
+    /// Send every queued edit through `SaveMediaListEntry`, oldest first.
+    ///
+    /// Edits that AniList rejects stay queued with their error recorded until
+    /// they have failed `MAX_LIST_UPDATE_ATTEMPTS` times, then they are skipped;
+    /// a network failure stops the replay so later edits keep their order.
+    /// A call made while another replay is running returns an empty report.
+    pub async fn replay(&self, client: &AniListClient) -> Result<ReplayReport, AppError> {
+        let Some(_guard) = self.start_replay() else {
+            info!("Outbox replay already running, skipping");
+            return Ok(ReplayReport::default());
+        };
+
+        let pending = self.pending()?;
+        let mut report = ReplayReport {
+            remaining: pending.len(),
+            ..Default::default()
+        };
+
+        for queued in pending.into_iter().filter(|queued| !queued.is_failed()) {
+            let update = &queued.update;
+            match client.save_list_entry(update).await {
+                Ok(data) => {
+                    let db = self.lock()?;
+                    db.remove_pending_list_update(queued.id)?;
+
+                    // Newly created entries now have a real id
+                    let user_id = db.get_user_id().ok();
+                    if let (None, Some(saved), Some(user_id)) =
+                        (update.entry_id, data.save_media_list_entry, user_id)
+                    {
+                        db.set_list_entry_id(
+                            user_id,
+                            update.media_id,
+                            update.media_type,
+                            saved.id as i32,
+                        )?;
+                    }
+                    self.invalidate(user_id, update.media_id);
+
+                    report.synced += 1;
+                    report.remaining -= 1;
+                }
+                Err(e) => {
+                    let error = e.to_string();
+                    warn!(
+                        "Failed to sync {:?} {}: {}",
+                        update.media_type, update.media_id, error
+                    );
+                    let rejected = !matches!(e, AppError::NetworkError(_));
+                    self.lock()?
+                        .record_list_update_failure(queued.id, &error, rejected)?;
+
+                    report.failed.push(ReplayFailure {
+                        media_id: update.media_id,
+                        media_type: update.media_type,
+                        error,
+                        gave_up: rejected && queued.attempts + 1 >= MAX_LIST_UPDATE_ATTEMPTS,
+                    });
+
+                    if !rejected {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn start_replay(&self) -> Option<ReplayGuard> {
+        self.replaying
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| ReplayGuard(self.replaying.clone()))
+    }
+
+    fn invalidate(&self, user_id: Option<i32>, media_id: i32) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(user_id, media_id);
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Database>, AppError> {
+        self.db
+            .lock()
+            .map_err(|_| AppError::DatabaseError("Failed to lock database".to_string()))
+    }
+}
+
+// A bulk edit of one entry, as queued or applied to the stored list
+fn bulk_update(item: &ListItem, media_type: MediaType, edit: &BulkEdit) -> ListUpdate {
+    ListUpdate {
+        entry_id: (item.entry_id > 0).then_some(item.entry_id),
+        media_id: item.media_id,
+        media_type,
+        status: match edit {
+            BulkEdit::Status(status) => Some(status.clone()),
+            _ => None,
+        },
+        score: match edit {
+            BulkEdit::Score(score) => Some(*score),
+            _ => None,
+        },
+        progress: None,
+        progress_volumes: None,
+        details: ListEntryDetails {
+            custom_lists: edit.custom_lists_for(item),
+            ..Default::default()
+        },
+    }
+}
+
+/// Map a list status name (`CURRENT`, `PLANNING`, ...) to the mutation enum
+pub fn parse_status(status: &str) -> Option<update_media_list::MediaListStatus> {
+    match status {
+        "CURRENT" => Some(update_media_list::MediaListStatus::CURRENT),
+        "PLANNING" => Some(update_media_list::MediaListStatus::PLANNING),
+        "COMPLETED" => Some(update_media_list::MediaListStatus::COMPLETED),
+        "DROPPED" => Some(update_media_list::MediaListStatus::DROPPED),
+        "PAUSED" => Some(update_media_list::MediaListStatus::PAUSED),
+        "REPEATING" => Some(update_media_list::MediaListStatus::REPEATING),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::{FuzzyDate, ListEntryDetails};
+    use crate::api::rate_limit::RetryPolicy;
+    use crate::api::transport::MockTransport;
+    use crate::data::database::CachedAnime;
+    use reqwest::StatusCode;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn outbox() -> Outbox {
+        let db = Database::in_memory().expect("in-memory database");
+        db.save_auth(1, "token", None, None).unwrap();
+        Outbox::new(Arc::new(Mutex::new(db)))
+    }
+
+    fn client_with(transport: Arc<MockTransport>) -> AniListClient {
+        AniListClient::with_token("token".to_string())
+            .with_transport(transport)
+            .with_retry_policy(RetryPolicy {
+                max_retries: 0,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(1),
+            })
+    }
+
+    fn progress_update(media_id: i32, progress: i32) -> ListUpdate {
+        ListUpdate {
+            entry_id: None,
+            media_id,
+            media_type: MediaType::ANIME,
+            status: Some("CURRENT".to_string()),
+            score: None,
+            progress: Some(progress),
+            progress_volumes: None,
+            details: ListEntryDetails::default(),
+        }
+    }
+
+    #[test]
+    fn test_queue_applies_edit_locally() {
+        let outbox = outbox();
+        outbox
+            .lock()
+            .unwrap()
+            .cache_anime(&CachedAnime {
+                id: 21,
+                title: "One Piece".to_string(),
+                english_title: None,
+                native_title: None,
+                description: None,
+                episodes: None,
+                duration: None,
+                genres: String::new(),
+                average_score: None,
+                cover_image: None,
+                banner_image: None,
+                updated_at: chrono::Utc::now(),
+                id_mal: None,
+                romaji_stylised: None,
+                english_stylised: None,
+                native_stylised: None,
+            })
+            .unwrap();
+
+        outbox.queue(progress_update(21, 3)).unwrap();
+        outbox.queue(progress_update(21, 4)).unwrap();
+
+        let pending = outbox.pending().unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].update.progress, Some(3));
+        assert_eq!(pending[1].update.progress, Some(4));
+
+        let list = outbox.lock().unwrap().get_user_anime_list(1, None).unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].0.id, -21);
+        assert_eq!(list[0].0.progress, Some(4));
+    }
+
+    #[tokio::test]
+    async fn test_replay_sends_in_order_and_reports_failures() {
+        let outbox = outbox();
+        outbox.queue(progress_update(21, 3)).unwrap();
+        outbox.queue(progress_update(22, 1)).unwrap();
+
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_data(
+                    "UpdateMediaList",
+                    json!({ "SaveMediaListEntry": { "id": 100, "status": "CURRENT",
+                        "score": 0, "progress": 3, "progressVolumes": null, "updatedAt": 0 } }),
+                )
+                .with_response(
+                    "UpdateMediaList",
+                    StatusCode::OK,
+                    json!({ "data": null, "errors": [{ "message": "Invalid media." }] }),
+                ),
+        );
+        let client = client_with(transport.clone());
+
+        let report = outbox.replay(&client).await.unwrap();
+        assert_eq!(report.synced, 1);
+        assert_eq!(report.remaining, 1);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].media_id, 22);
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].body["variables"]["mediaId"], 21);
+        assert_eq!(requests[1].body["variables"]["mediaId"], 22);
+
+        // The rejected edit stays queued with its error
+        let pending = outbox.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].attempts, 1);
+        assert!(pending[0].last_error.is_some());
+        assert!(!report.failed[0].gave_up);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_edits_stop_being_sent() {
+        let outbox = outbox();
+        let id = outbox.queue(progress_update(22, 1)).unwrap();
+
+        let rejected = json!({ "data": null, "errors": [{ "message": "Invalid media." }] });
+        let mut transport = MockTransport::new();
+        for _ in 0..MAX_LIST_UPDATE_ATTEMPTS + 1 {
+            transport =
+                transport.with_response("UpdateMediaList", StatusCode::OK, rejected.clone());
+        }
+        let transport = Arc::new(transport);
+        let client = client_with(transport.clone());
+
+        for attempt in 1..=MAX_LIST_UPDATE_ATTEMPTS {
+            let report = outbox.replay(&client).await.unwrap();
+            assert_eq!(
+                report.failed[0].gave_up,
+                attempt == MAX_LIST_UPDATE_ATTEMPTS
+            );
+        }
+        assert_eq!(outbox.failed().unwrap().len(), 1);
+
+        // Given up on, so nothing is sent
+        let report = outbox.replay(&client).await.unwrap();
+        assert!(report.failed.is_empty());
+        assert_eq!(
+            transport.requests().len(),
+            MAX_LIST_UPDATE_ATTEMPTS as usize
+        );
+
+        outbox.retry(id).unwrap();
+        assert!(outbox.failed().unwrap().is_empty());
+        outbox.discard(id).unwrap();
+        assert!(outbox.pending().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_queued_details_are_sent_on_replay() {
+        let outbox = outbox();
+        let mut update = progress_update(21, 3);
+        update.details = ListEntryDetails {
+            notes: Some("Rewatch with friends".to_string()),
+            started_at: Some(FuzzyDate {
+                year: Some(2024),
+                month: Some(3),
+                day: None,
+            }),
+            completed_at: Some(FuzzyDate::default()),
+            custom_lists: Some(vec!["Favourites".to_string()]),
+            ..Default::default()
+        };
+        outbox.queue(update.clone()).unwrap();
+        assert_eq!(outbox.pending().unwrap()[0].update, update);
+
+        let transport = Arc::new(MockTransport::new().with_data(
+            "UpdateMediaList",
+            json!({ "SaveMediaListEntry": { "id": 100 } }),
+        ));
+        let client = client_with(transport.clone());
+        assert_eq!(outbox.replay(&client).await.unwrap().synced, 1);
+
+        let variables = &transport.requests()[0].body["variables"];
+        assert_eq!(variables["notes"], "Rewatch with friends");
+        assert_eq!(
+            variables["startedAt"],
+            json!({ "year": 2024, "month": 3, "day": null })
+        );
+        // An empty date clears the one on AniList
+        assert_eq!(
+            variables["completedAt"],
+            json!({ "year": null, "month": null, "day": null })
+        );
+        assert_eq!(variables["customLists"], json!(["Favourites"]));
+        assert!(variables["repeat"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_delete_drops_cached_entry_and_queued_edits() {
+        let outbox = outbox();
+        outbox.queue(progress_update(21, 3)).unwrap();
+        outbox.queue(progress_update(22, 1)).unwrap();
+
+        let transport = Arc::new(MockTransport::new().with_data(
+            "DeleteMediaListEntry",
+            json!({ "DeleteMediaListEntry": { "deleted": true } }),
+        ));
+        let client = client_with(transport.clone());
+
+        outbox
+            .delete(&client, Some(500), 21, MediaType::ANIME)
+            .await
+            .unwrap();
+
+        assert_eq!(transport.requests()[0].body["variables"]["id"], 500);
+        let pending = outbox.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].update.media_id, 22);
+    }
+
+    #[tokio::test]
+    async fn test_online_save_drops_older_queued_edits() {
+        let outbox = outbox();
+        outbox.queue(progress_update(21, 3)).unwrap();
+        outbox.queue(progress_update(22, 1)).unwrap();
+
+        let transport = Arc::new(MockTransport::new().with_data(
+            "UpdateMediaList",
+            json!({ "SaveMediaListEntry": { "id": 100 } }),
+        ));
+        let client = client_with(transport.clone());
+
+        let outcome = outbox
+            .save(&client, progress_update(21, 5), false)
+            .await
+            .unwrap();
+        assert_eq!(outcome, SaveOutcome::Saved);
+
+        let pending = outbox.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].update.media_id, 22);
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_replay_returns_early() {
+        let outbox = outbox();
+        outbox.queue(progress_update(21, 3)).unwrap();
+
+        let transport = Arc::new(MockTransport::new().with_data(
+            "UpdateMediaList",
+            json!({ "SaveMediaListEntry": { "id": 100 } }),
+        ));
+        let client = client_with(transport.clone());
+
+        // A clone shares the flag, like the screens' copies of the outbox
+        let running = outbox.clone().start_replay().unwrap();
+        let report = outbox.replay(&client).await.unwrap();
+        assert_eq!(report.synced, 0);
+        assert!(transport.requests().is_empty());
+        assert_eq!(outbox.pending().unwrap().len(), 1);
+
+        drop(running);
+        assert_eq!(outbox.replay(&client).await.unwrap().synced, 1);
+    }
+
+    fn list_item(entry_id: i32, media_id: i32) -> ListItem {
+        ListItem {
+            entry_id,
+            media_id,
+            title: Default::default(),
+            cover_url: None,
+            status: "CURRENT".to_string(),
+            score: 0.0,
+            progress: 0,
+            progress_volumes: 0,
+            total: None,
+            format: None,
+            genres: Vec::new(),
+            year: None,
+            custom_lists: vec!["Favourites".to_string()],
+            hidden_from_status_lists: false,
+            started_at: FuzzyDate::default(),
+            updated_at: 0,
+            next_airing_at: None,
+            next_episode: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_edits_are_batched() {
+        let outbox = outbox();
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_data(
+                    "UpdateMediaListEntries",
+                    json!({ "UpdateMediaListEntries": [] }),
+                )
+                .with_data(
+                    "SaveCustomLists",
+                    json!({ "e0": { "id": 500 }, "e1": { "id": 501 } }),
+                )
+                .with_data(
+                    "DeleteMediaListEntries",
+                    json!({ "e0": { "deleted": true }, "e1": { "deleted": true } }),
+                ),
+        );
+        let client = client_with(transport.clone());
+        // The last one was created offline and has no AniList id yet
+        let items = vec![list_item(500, 21), list_item(501, 22), list_item(-23, 23)];
+
+        let outcome = outbox
+            .save_bulk(
+                &client,
+                &items,
+                MediaType::ANIME,
+                &BulkEdit::Status("PAUSED".to_string()),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(outcome, SaveOutcome::Queued);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].body["variables"]["ids"], json!([500, 501]));
+        assert_eq!(requests[0].body["variables"]["status"], "PAUSED");
+        assert_eq!(outbox.pending().unwrap()[0].update.media_id, 23);
+
+        let moved = BulkEdit::MoveToList {
+            from: Some("Favourites".to_string()),
+            to: "Rewatch".to_string(),
+        };
+        outbox
+            .save_bulk(&client, &items[..2], MediaType::ANIME, &moved, false)
+            .await
+            .unwrap();
+        let variables = &transport.requests()[1].body["variables"];
+        assert_eq!(variables["lists0"], json!(["Rewatch"]));
+        assert_eq!(variables["lists1"], json!(["Rewatch"]));
+
+        outbox
+            .save_bulk(&client, &items, MediaType::ANIME, &BulkEdit::Delete, false)
+            .await
+            .unwrap();
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].body["query"]
+            .as_str()
+            .unwrap()
+            .contains("e1: DeleteMediaListEntry(id: 501)"));
+        // The queued edit would bring the deleted entry back
+        assert!(outbox.pending().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,520 @@
+use chrono::{DateTime, TimeZone, Utc};
+use log::{info, warn};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use crate::api::client::{user_anime_list, AniListClient};
+use crate::api::models::{
+    FuzzyDate, ListEntryDetails, Media, MediaCoverImage, MediaListEntry, MediaTitle, MediaType,
+};
+use crate::api::my_list::enabled_custom_lists;
+use crate::data::database::{
+    CachedAnime, CachedListEntry, CachedManga, CachedMangaListEntry, Database,
+};
+use crate::utils::error::AppError;
+
+type RemoteEntry = user_anime_list::UserAnimeListMediaListCollectionListsEntries;
+type RemoteMedia = user_anime_list::UserAnimeListMediaListCollectionListsEntriesMedia;
+
+/// Mirrors a user's AniList lists into the local database so screens can
+/// render from SQLite and refresh in the background.
+#[derive(Debug, Clone)]
+pub struct ListSync {
+    db: Arc<Mutex<Database>>,
+}
+
+impl ListSync {
+    pub fn new(db: Arc<Mutex<Database>>) -> Self {
+        Self { db }
+    }
+
+    /// Pull the whole `MediaListCollection` of `user_id` and store it locally.
+    /// Returns the number of entries synced.
+    pub async fn sync(
+        &self,
+        client: &AniListClient,
+        user_id: i32,
+        media_type: MediaType,
+    ) -> Result<usize, AppError> {
+        let data = client
+            .get_user_media_list(user_id, media_type, None)
+            .await?;
+        self.store(user_id, media_type, &data)
+    }
+
+    /// Store a `MediaListCollection` that was already fetched, replacing the
+    /// local copy. Returns the number of entries stored.
+    ///
+    /// A response without a collection, or without its lists, leaves the local
+    /// copy alone; an empty list of lists still clears it.
+    pub fn store(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+        data: &user_anime_list::ResponseData,
+    ) -> Result<usize, AppError> {
+        let Some(lists) = data
+            .media_list_collection
+            .as_ref()
+            .and_then(|collection| collection.lists.as_ref())
+        else {
+            warn!(
+                "No {:?} list in the response for user {}, keeping the stored one",
+                media_type, user_id
+            );
+            return Err(AppError::ApiError(format!(
+                "AniList returned no {:?} list",
+                media_type
+            )));
+        };
+
+        // Custom lists repeat entries that are already in a status list
+        let mut seen = HashSet::new();
+        let entries: Vec<RemoteEntry> = lists
+            .iter()
+            .flatten()
+            .flat_map(|list| list.entries.iter().flatten())
+            .flatten()
+            .filter(|entry| seen.insert(entry.id))
+            .cloned()
+            .collect();
+        let count = entries.len();
+
+        let db = self.lock()?;
+        match media_type {
+            MediaType::ANIME => {
+                let rows: Vec<_> = entries
+                    .into_iter()
+                    .filter_map(|entry| anime_row(user_id, entry))
+                    .collect();
+                db.sync_anime_list(user_id, &rows)?;
+            }
+            MediaType::MANGA => {
+                let rows: Vec<_> = entries
+                    .into_iter()
+                    .filter_map(|entry| manga_row(user_id, entry))
+                    .collect();
+                db.sync_manga_list(user_id, &rows)?;
+            }
+        }
+
+        info!(
+            "Synced {} {:?} list entries for user {}",
+            count, media_type, user_id
+        );
+        Ok(count)
+    }
+
+    /// The user whose login is stored in the database, if any
+    pub fn stored_user_id(&self) -> Option<i32> {
+        self.lock().ok()?.get_user_id().ok()
+    }
+
+    pub fn last_synced(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+    ) -> Result<Option<DateTime<Utc>>, AppError> {
+        Ok(self.lock()?.get_last_synced(user_id, media_type)?)
+    }
+
+    /// The locally stored list, optionally filtered by status (`CURRENT`, ...)
+    pub fn cached_list(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+        status: Option<&str>,
+    ) -> Result<Vec<MediaListEntry>, AppError> {
+        let db = self.lock()?;
+
+        let entries = match media_type {
+            MediaType::ANIME => db
+                .get_user_anime_list(user_id, status)?
+                .into_iter()
+                .map(|(entry, anime)| MediaListEntry {
+                    id: entry.id,
+                    media_id: entry.media_id,
+                    status: entry.status,
+                    score: entry.score,
+                    progress: entry.progress,
+                    progress_volumes: None,
+                    updated_at: entry.updated_at.timestamp(),
+                    media: Some(anime_media(anime)),
+                })
+                .collect(),
+            MediaType::MANGA => db
+                .get_user_manga_list(user_id, status)?
+                .into_iter()
+                .map(|(entry, manga)| MediaListEntry {
+                    id: entry.id,
+                    media_id: entry.media_id,
+                    status: entry.status,
+                    score: entry.score,
+                    progress: entry.progress,
+                    progress_volumes: entry.progress_volumes,
+                    updated_at: entry.updated_at.timestamp(),
+                    media: Some(manga_media(manga)),
+                })
+                .collect(),
+        };
+
+        Ok(entries)
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Database>, AppError> {
+        self.db
+            .lock()
+            .map_err(|_| AppError::DatabaseError("Failed to lock database".to_string()))
+    }
+}
+
+/// The stored copy of an anime as the API model
+pub fn anime_media(anime: CachedAnime) -> Media {
+    Media {
+        id: anime.id,
+        title: MediaTitle {
+            romaji: Some(anime.title),
+            english: anime.english_title,
+            native: anime.native_title,
+            romaji_stylised: anime.romaji_stylised,
+            english_stylised: anime.english_stylised,
+            native_stylised: anime.native_stylised,
+        },
+        description: anime.description,
+        episodes: anime.episodes,
+        chapters: None,
+        volumes: None,
+        duration: anime.duration,
+        genres: split_genres(&anime.genres),
+        average_score: anime.average_score,
+        cover_image: cover_image(anime.cover_image),
+        banner_image: anime.banner_image,
+        status: None,
+        format: None,
+    }
+}
+
+/// The stored copy of a manga as the API model
+pub fn manga_media(manga: CachedManga) -> Media {
+    Media {
+        id: manga.id,
+        title: MediaTitle {
+            romaji: Some(manga.title),
+            english: manga.english_title,
+            native: manga.native_title,
+            romaji_stylised: manga.romaji_stylised,
+            english_stylised: manga.english_stylised,
+            native_stylised: manga.native_stylised,
+        },
+        description: manga.description,
+        episodes: None,
+        chapters: manga.chapters,
+        volumes: manga.volumes,
+        duration: None,
+        genres: split_genres(&manga.genres),
+        average_score: manga.average_score,
+        cover_image: cover_image(manga.cover_image),
+        banner_image: manga.banner_image,
+        status: None,
+        format: None,
+    }
+}
+
+fn anime_row(user_id: i32, entry: RemoteEntry) -> Option<(CachedListEntry, CachedAnime)> {
+    let updated_at = timestamp(entry.updated_at);
+    let details = entry_details(&entry);
+    let media = entry.media?;
+    let (title, english_title, native_title) = titles(&media);
+    let stylised = media
+        .title
+        .clone()
+        .map(MediaTitle::from)
+        .unwrap_or_default();
+
+    Some((
+        CachedListEntry {
+            id: entry.id as i32,
+            user_id,
+            media_id: entry.media_id as i32,
+            status: status_name(entry.status),
+            score: entry.score,
+            progress: entry.progress.map(|p| p as i32),
+            updated_at,
+            details,
+        },
+        CachedAnime {
+            id: media.id as i32,
+            title,
+            english_title,
+            native_title,
+            description: None,
+            episodes: media.episodes.map(|e| e as i32),
+            duration: media.duration.map(|d| d as i32),
+            genres: join_genres(media.genres),
+            average_score: media.average_score.map(|s| s as f64),
+            cover_image: media.cover_image.and_then(|img| img.large.or(img.medium)),
+            banner_image: media.banner_image,
+            updated_at: Utc::now(),
+            id_mal: media.id_mal.map(|id| id as i32),
+            romaji_stylised: stylised.romaji_stylised,
+            english_stylised: stylised.english_stylised,
+            native_stylised: stylised.native_stylised,
+        },
+    ))
+}
+
+fn manga_row(user_id: i32, entry: RemoteEntry) -> Option<(CachedMangaListEntry, CachedManga)> {
+    let updated_at = timestamp(entry.updated_at);
+    let media = entry.media?;
+    let (title, english_title, native_title) = titles(&media);
+    let stylised = media
+        .title
+        .clone()
+        .map(MediaTitle::from)
+        .unwrap_or_default();
+
+    Some((
+        CachedMangaListEntry {
+            id: entry.id as i32,
+            user_id,
+            media_id: entry.media_id as i32,
+            status: status_name(entry.status),
+            score: entry.score,
+            progress: entry.progress.map(|p| p as i32),
+            progress_volumes: entry.progress_volumes.map(|p| p as i32),
+            updated_at,
+        },
+        CachedManga {
+            id: media.id as i32,
+            title,
+            english_title,
+            native_title,
+            description: None,
+            chapters: media.chapters.map(|c| c as i32),
+            volumes: media.volumes.map(|v| v as i32),
+            genres: join_genres(media.genres),
+            average_score: media.average_score.map(|s| s as f64),
+            cover_image: media.cover_image.and_then(|img| img.large.or(img.medium)),
+            banner_image: media.banner_image,
+            updated_at: Utc::now(),
+            romaji_stylised: stylised.romaji_stylised,
+            english_stylised: stylised.english_stylised,
+            native_stylised: stylised.native_stylised,
+        },
+    ))
+}
+
+// Advanced scores are left out, their order comes from the viewer's settings
+fn entry_details(entry: &RemoteEntry) -> ListEntryDetails {
+    let date = |year: Option<i64>, month: Option<i64>, day: Option<i64>| FuzzyDate {
+        year: year.map(|y| y as i32),
+        month: month.map(|m| m as i32),
+        day: day.map(|d| d as i32),
+    };
+
+    ListEntryDetails {
+        notes: entry.notes.clone(),
+        started_at: entry
+            .started_at
+            .as_ref()
+            .map(|d| date(d.year, d.month, d.day)),
+        completed_at: entry
+            .completed_at
+            .as_ref()
+            .map(|d| date(d.year, d.month, d.day)),
+        repeat: entry.repeat.map(|r| r as i32),
+        private: entry.private,
+        hidden_from_status_lists: entry.hidden_from_status_lists,
+        custom_lists: Some(enabled_custom_lists(entry.custom_lists.as_ref())),
+        advanced_scores: None,
+    }
+}
+
+fn titles(media: &RemoteMedia) -> (String, Option<String>, Option<String>) {
+    let title = media.title.as_ref();
+    (
+        title
+            .and_then(|t| t.romaji.clone())
+            .unwrap_or_else(|| "Unknown".to_string()),
+        title.and_then(|t| t.english.clone()),
+        title.and_then(|t| t.native.clone()),
+    )
+}
+
+fn status_name(status: Option<user_anime_list::MediaListStatus>) -> String {
+    status.map_or("UNKNOWN".to_string(), |s| format!("{:?}", s))
+}
+
+fn timestamp(seconds: Option<i64>) -> DateTime<Utc> {
+    seconds
+        .and_then(|s| Utc.timestamp_opt(s, 0).single())
+        .unwrap_or_else(Utc::now)
+}
+
+fn join_genres(genres: Option<Vec<Option<String>>>) -> String {
+    genres
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn split_genres(genres: &str) -> Option<Vec<String>> {
+    if genres.is_empty() {
+        None
+    } else {
+        Some(genres.split(',').map(|g| g.to_string()).collect())
+    }
+}
+
+fn cover_image(url: Option<String>) -> Option<MediaCoverImage> {
+    url.map(|url| MediaCoverImage {
+        large: Some(url.clone()),
+        medium: Some(url),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::TitleLanguage;
+    use crate::api::transport::MockTransport;
+    use serde_json::json;
+
+    fn list_entry(id: i64, media_id: i64, status: &str, progress: i64) -> serde_json::Value {
+        json!({
+            "id": id,
+            "mediaId": media_id,
+            "status": status,
+            "score": 8.5,
+            "progress": progress,
+            "progressVolumes": null,
+            "updatedAt": 1700000000,
+            "media": {
+                "id": media_id,
+                "title": {
+                    "romaji": format!("Anime {}", media_id),
+                    "english": null,
+                    "native": null,
+                    "romajiStylised": format!("ANIME {}", media_id)
+                },
+                "coverImage": { "large": null, "medium": null },
+                "bannerImage": null,
+                "episodes": 12,
+                "chapters": null,
+                "volumes": null,
+                "duration": 24,
+                "genres": ["Action", "Drama"],
+                "averageScore": 80,
+                "nextAiringEpisode": null,
+                "format": "TV",
+                "status": "FINISHED"
+            }
+        })
+    }
+
+    fn collection(entries: Vec<serde_json::Value>) -> serde_json::Value {
+        json!({
+            "MediaListCollection": {
+                "lists": [{ "name": "Watching", "status": "CURRENT", "entries": entries }]
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_sync_upserts_and_removes_deleted_entries() {
+        let db = Arc::new(Mutex::new(Database::in_memory().unwrap()));
+        let sync = ListSync::new(db);
+
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_data(
+                    "UserAnimeList",
+                    collection(vec![
+                        list_entry(1, 10, "CURRENT", 3),
+                        list_entry(2, 20, "CURRENT", 5),
+                    ]),
+                )
+                .with_data(
+                    "UserAnimeList",
+                    collection(vec![list_entry(1, 10, "COMPLETED", 12)]),
+                ),
+        );
+        let client = AniListClient::new().with_transport(transport);
+
+        assert!(sync.last_synced(7, MediaType::ANIME).unwrap().is_none());
+        assert_eq!(sync.sync(&client, 7, MediaType::ANIME).await.unwrap(), 2);
+        assert_eq!(
+            sync.cached_list(7, MediaType::ANIME, Some("CURRENT"))
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Entry 2 was deleted on AniList and entry 1 was finished
+        sync.sync(&client, 7, MediaType::ANIME).await.unwrap();
+        let list = sync.cached_list(7, MediaType::ANIME, None).unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].status, "COMPLETED");
+        assert_eq!(list[0].progress, Some(12));
+        assert_eq!(list[0].score, Some(8.5));
+        assert_eq!(
+            list[0].media.as_ref().unwrap().genres,
+            Some(vec!["Action".to_string(), "Drama".to_string()])
+        );
+        assert_eq!(
+            list[0]
+                .media
+                .as_ref()
+                .unwrap()
+                .title
+                .preferred(TitleLanguage::ROMAJI_STYLISED),
+            "ANIME 10"
+        );
+        assert!(sync.last_synced(7, MediaType::ANIME).unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_missing_collection_keeps_the_stored_list() {
+        let db = Arc::new(Mutex::new(Database::in_memory().unwrap()));
+        let sync = ListSync::new(db);
+
+        let transport = Arc::new(
+            MockTransport::new()
+                .with_data(
+                    "UserAnimeList",
+                    collection(vec![list_entry(1, 10, "CURRENT", 3)]),
+                )
+                .with_data("UserAnimeList", json!({ "MediaListCollection": null }))
+                .with_data(
+                    "UserAnimeList",
+                    json!({ "MediaListCollection": { "lists": null } }),
+                )
+                .with_data(
+                    "UserAnimeList",
+                    json!({ "MediaListCollection": { "lists": [] } }),
+                ),
+        );
+        let client = AniListClient::new().with_transport(transport);
+
+        sync.sync(&client, 7, MediaType::ANIME).await.unwrap();
+        let synced_at = sync.last_synced(7, MediaType::ANIME).unwrap();
+
+        for _ in 0..2 {
+            assert!(sync.sync(&client, 7, MediaType::ANIME).await.is_err());
+            assert_eq!(
+                sync.cached_list(7, MediaType::ANIME, None).unwrap().len(),
+                1
+            );
+            assert_eq!(sync.last_synced(7, MediaType::ANIME).unwrap(), synced_at);
+        }
+
+        // An empty set of lists is a list that was emptied on AniList
+        assert_eq!(sync.sync(&client, 7, MediaType::ANIME).await.unwrap(), 0);
+        assert!(sync
+            .cached_list(7, MediaType::ANIME, None)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod api;
+pub mod app;
 pub mod data;
 pub mod ui;
 pub mod utils;
@@ -1,4 +1,5 @@
 use anilist_client::app::App;
+use anilist_client::data::export::{default_path, export_anime_list, ExportFormat};
 use anilist_client::ui::AniListApp;
 use anilist_client::utils::logging;
 use anyhow::{Context, Result};
@@ -33,7 +34,7 @@ async fn main() -> Result<()> {
                     Ok(token) => {
                         info!("Authentication successful!");
                         info!("Access token: {}", token.access_token);
-                        if let Some(refresh_token) = &token.refresh_token {
+                        if token.refresh_token.is_some() {
                             info!("Refresh token received");
                         }
                         info!("Token expires in: {:?} seconds", token.expires_in);
@@ -103,12 +104,43 @@ async fn main() -> Result<()> {
                     }
                 }
             }
+            "--export" => {
+                let format = args
+                    .get(2)
+                    .and_then(|name| ExportFormat::from_name(name))
+                    .context("Usage: anilist_client --export <xml|csv|json> [path]")?;
+                let path = match args.get(3) {
+                    Some(path) => path.into(),
+                    None => default_path(format)?,
+                };
+
+                let db = app.get_database();
+                let user_id = db
+                    .get_user_id()
+                    .context("Not logged in, log in and sync your list first")?;
+                let report = export_anime_list(db, user_id, format, &path)?;
+
+                println!(
+                    "Exported {} entries to {}",
+                    report.exported,
+                    report.path.display()
+                );
+                if report.skipped > 0 {
+                    println!(
+                        "{} entries have no MyAnimeList id and were left out",
+                        report.skipped
+                    );
+                }
+            }
             "--help" => {
                 println!("AniList Desktop Client");
                 println!("Usage:");
                 println!("  anilist_client            Start the GUI application");
                 println!("  anilist_client --test-auth Test the authentication flow");
                 println!("  anilist_client --test-api  Test the API connection");
+                println!(
+                    "  anilist_client --export <xml|csv|json> [path]  Export your synced anime list"
+                );
                 println!("  anilist_client --help      Show this help message");
             }
             _ => {
@@ -121,8 +153,6 @@ async fn main() -> Result<()> {
         // Launch the GUI application
         info!("Starting GUI application...");
 
-        let ui_app = app.create_ui_app();
-
         if let Err(e) = AniListApp::launch() {
             error!("Application error: {}", e);
             return Err(anyhow::anyhow!("Failed to launch UI: {}", e));
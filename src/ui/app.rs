@@ -1,27 +1,53 @@
 use iced::time::every;
-use iced::widget::{button, column, container, row, scrollable, text, text_input};
-use iced::{settings, Application, Command, Element, Length, Settings, Subscription, Theme};
+use iced::widget::{button, column, container, row, text, text_input};
+use iced::{Application, Command, Element, Length, Settings, Subscription, Theme};
+use log::warn;
 use std::sync::{Arc, Mutex};
 
 use crate::api::auth::AuthManager;
 use crate::api::client::AniListClient;
+use crate::api::models::{MediaType, NotificationLink, ViewerPreferences};
+use crate::api::transport::HttpTransport;
+use crate::data::cache_layer::{CacheLayer, CacheTtls};
 use crate::data::database::Database;
+use crate::data::outbox::{Outbox, ReplayReport};
 use crate::ui::components::auth::{AuthComponent, Message as AuthMessage};
+use crate::ui::images::ImageLoader;
+use crate::ui::screens::activity::{ActivityScreen, Message as ActivityMessage};
+use crate::ui::screens::calendar::{CalendarScreen, Message as CalendarMessage};
+use crate::ui::screens::character::{CharacterScreen, Message as CharacterMessage};
 use crate::ui::screens::details::{DetailsScreen, Message as DetailsMessage};
 use crate::ui::screens::home::{HomeScreen, Message as HomeMessage};
+use crate::ui::screens::import::{ImportScreen, Message as ImportMessage};
+use crate::ui::screens::my_list::{Message as MyListMessage, MyListScreen};
+use crate::ui::screens::notifications::{Message as NotificationsMessage, NotificationsScreen};
 use crate::ui::screens::profile::{Message as ProfileMessage, ProfileScreen};
 use crate::ui::screens::search::{Message as SearchMessage, SearchScreen};
+use crate::ui::screens::seasonal::{Message as SeasonalMessage, SeasonalScreen};
 use crate::ui::screens::settings::{Message as SettingsMessage, SettingsScreen};
-use crate::utils::config::AuthConfig;
+use crate::ui::screens::staff::{Message as StaffMessage, StaffScreen};
+use crate::ui::screens::studio::{Message as StudioMessage, StudioScreen};
+use crate::utils::config::{load_config, AuthConfig, Config};
 
 // Application screens
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Screen {
     Home,
+    MyList,
     Search,
-    Details(i32),    // Anime ID
-    Profile(String), // Username
+    Seasonal,
+    Calendar,
+    Details(i32),      // Anime ID
+    MangaDetails(i32), // Manga ID
+    Character(i32),    // Character ID
+    Staff(i32),        // Staff ID
+    Studio(i32),       // Studio ID
+    Profile(String),   // Username
+    Notifications,
+    Feed,
+    Activity(i32), // Activity ID
     Settings,
+    Import,
 }
 
 // Application state
@@ -34,18 +60,33 @@ pub enum Message {
     // Auth-related
     Auth(AuthMessage),
     AuthStatusChanged(bool),
+    PreferencesLoaded(Result<(i32, ViewerPreferences), String>),
+    UnreadCountLoaded(Result<i32, String>),
 
     // Screen-specific messages
     Home(HomeMessage),
+    MyList(MyListMessage),
     Search(SearchMessage),
+    Seasonal(SeasonalMessage),
+    Calendar(CalendarMessage),
     Details(DetailsMessage),
+    Character(CharacterMessage),
+    Staff(StaffMessage),
+    Studio(StudioMessage),
     Profile(ProfileMessage),
+    Notifications(NotificationsMessage),
+    Activity(ActivityMessage),
     Settings(SettingsMessage),
+    Import(ImportMessage),
 
     // Search-related
     SearchQueryChanged(String),
     SearchSubmitted,
 
+    // Offline edits
+    ReplayOutbox,
+    OutboxReplayed(Result<ReplayReport, String>),
+
     // Error handling
     Error(String),
 
@@ -57,7 +98,12 @@ pub struct AniListApp {
     // Core components
     api_client: AniListClient,
     db: Arc<Mutex<Database>>,
+    cache: CacheLayer,
+    outbox: Outbox,
+    images: ImageLoader,
     auth_component: AuthComponent,
+    preferences: ViewerPreferences,
+    unread_notifications: i32,
 
     // App state
     current_screen: Screen,
@@ -66,13 +112,21 @@ pub struct AniListApp {
 
     // Screen modules
     home_screen: HomeScreen,
+    my_list_screen: MyListScreen,
     search_screen: SearchScreen,
+    seasonal_screen: SeasonalScreen,
+    calendar_screen: CalendarScreen,
     details_screen: DetailsScreen,
+    character_screen: CharacterScreen,
+    staff_screen: StaffScreen,
+    studio_screen: StudioScreen,
     profile_screen: ProfileScreen,
+    notifications_screen: NotificationsScreen,
+    activity_screen: ActivityScreen,
     settings_screen: SettingsScreen,
+    import_screen: ImportScreen,
 
     // UI state
-    is_loading: bool,
     error: Option<String>,
 }
 
@@ -81,34 +135,77 @@ impl AniListApp {
         // Wrap database in Arc<Mutex>
         let db_arc = Arc::new(Mutex::new(db.clone()));
 
-        // Create auth manager
-        let auth_manager = AuthManager::new(auth_config, db.clone());
+        // Create auth manager on the same transport as the API client
+        let auth_manager =
+            AuthManager::new(auth_config, db.clone()).with_transport(client.transport());
 
         // Create auth component
         let auth_component = AuthComponent::new(auth_manager);
 
         // Create screen modules
-        let home_screen = HomeScreen::new(client.clone());
+        let cache = CacheLayer::new(client.clone(), db_arc.clone(), CacheTtls::default());
+        let home_screen = HomeScreen::new(client.clone(), cache.clone());
         let search_screen = SearchScreen::new(client.clone());
-        let details_screen = DetailsScreen::new(client.clone());
-        let profile_screen = ProfileScreen::new(client.clone());
-        let settings_screen = SettingsScreen::new(db_arc.clone());
-
-        Self {
+        let outbox = Outbox::new(db_arc.clone()).with_cache(cache.clone());
+        let images = ImageLoader::new();
+        let mut my_list_screen = MyListScreen::new(
+            client.clone(),
+            cache.clone(),
+            outbox.clone(),
+            images.clone(),
+        );
+        let mut details_screen = DetailsScreen::new(
+            client.clone(),
+            cache.clone(),
+            outbox.clone(),
+            images.clone(),
+        );
+        let character_screen = CharacterScreen::new(client.clone());
+        let staff_screen = StaffScreen::new(client.clone());
+        let studio_screen = StudioScreen::new(client.clone());
+        let profile_screen = ProfileScreen::new(client.clone(), images.clone());
+        let notifications_screen = NotificationsScreen::new(client.clone());
+        let activity_screen = ActivityScreen::new(client.clone());
+        let mut seasonal_screen = SeasonalScreen::new(client.clone(), outbox.clone());
+        let calendar_screen = CalendarScreen::new(client.clone());
+        let settings_screen = SettingsScreen::new(db_arc.clone(), outbox.clone());
+        let mut import_screen = ImportScreen::new(client.clone(), cache.clone(), outbox.clone());
+        details_screen.set_offline_mode(settings_screen.offline_mode());
+        my_list_screen.set_offline_mode(settings_screen.offline_mode());
+        seasonal_screen.set_offline_mode(settings_screen.offline_mode());
+        import_screen.set_offline_mode(settings_screen.offline_mode());
+        images.set_offline_mode(settings_screen.offline_mode());
+
+        let mut app = Self {
             api_client: client,
             db: db_arc,
+            cache,
+            outbox,
+            images,
             auth_component,
+            preferences: ViewerPreferences::default(),
+            unread_notifications: 0,
             current_screen: Screen::Home,
             screen_history: Vec::new(),
             search_query: String::new(),
             home_screen,
+            my_list_screen,
             search_screen,
+            seasonal_screen,
+            calendar_screen,
             details_screen,
+            character_screen,
+            staff_screen,
+            studio_screen,
             profile_screen,
+            notifications_screen,
+            activity_screen,
             settings_screen,
-            is_loading: false,
+            import_screen,
             error: None,
-        }
+        };
+        app.load_stored_preferences();
+        app
     }
 
     pub fn launch() -> iced::Result {
@@ -123,12 +220,147 @@ impl AniListApp {
         }
     }
 
-    fn go_back(&mut self) {
-        if let Some(previous_screen) = self.screen_history.pop() {
-            self.current_screen = previous_screen;
+    fn go_back(&mut self) -> Command<Message> {
+        let Some(previous_screen) = self.screen_history.pop() else {
+            return Command::none();
+        };
+        self.current_screen = previous_screen;
+
+        // Each screen holds one item, which may have changed since we left
+        match self.current_screen {
+            Screen::Details(id) if self.details_screen.media_id() != Some(id) => {
+                self.details_screen.load(id).map(Message::Details)
+            }
+            Screen::MangaDetails(id) if self.details_screen.media_id() != Some(id) => {
+                self.details_screen.load_manga(id).map(Message::Details)
+            }
+            Screen::Character(id) if self.character_screen.character_id() != Some(id) => {
+                self.character_screen.load(id).map(Message::Character)
+            }
+            Screen::Staff(id) if self.staff_screen.staff_id() != Some(id) => {
+                self.staff_screen.load(id).map(Message::Staff)
+            }
+            Screen::Studio(id) if self.studio_screen.studio_id() != Some(id) => {
+                self.studio_screen.load(id).map(Message::Studio)
+            }
+            Screen::Activity(id) if self.activity_screen.focused_id() != Some(id) => {
+                self.activity_screen.open(id).map(Message::Activity)
+            }
+            Screen::Feed => self.activity_screen.init().map(Message::Activity),
+            Screen::MyList => self.my_list_screen.init().map(Message::MyList),
+            _ => Command::none(),
+        }
+    }
+
+    fn open_media(&mut self, id: i32, media_type: MediaType) -> Command<Message> {
+        match media_type {
+            MediaType::ANIME => {
+                self.navigate_to(Screen::Details(id));
+                self.details_screen.load(id).map(Message::Details)
+            }
+            MediaType::MANGA => {
+                self.navigate_to(Screen::MangaDetails(id));
+                self.details_screen.load_manga(id).map(Message::Details)
+            }
         }
     }
 
+    fn open_character(&mut self, id: i32) -> Command<Message> {
+        self.navigate_to(Screen::Character(id));
+        self.character_screen.load(id).map(Message::Character)
+    }
+
+    fn open_staff(&mut self, id: i32) -> Command<Message> {
+        self.navigate_to(Screen::Staff(id));
+        self.staff_screen.load(id).map(Message::Staff)
+    }
+
+    fn open_profile(&mut self, name: String) -> Command<Message> {
+        self.navigate_to(Screen::Profile(name.clone()));
+        self.profile_screen.load(name).map(Message::Profile)
+    }
+
+    fn open_activity(&mut self, id: i32) -> Command<Message> {
+        self.navigate_to(Screen::Activity(id));
+        self.activity_screen.open(id).map(Message::Activity)
+    }
+
+    // Use the preferences saved by an earlier session until fresh ones arrive
+    fn load_stored_preferences(&mut self) {
+        let stored = self.db.lock().ok().and_then(|db| {
+            let user_id = db.get_user_id().ok()?;
+            Some((user_id, db.get_viewer_preferences(user_id).ok().flatten()))
+        });
+
+        if let Some((user_id, preferences)) = stored {
+            self.profile_screen.set_viewer_id(Some(user_id));
+            if let Some(preferences) = preferences {
+                self.preferences = preferences;
+            }
+        }
+        self.apply_preferences();
+    }
+
+    fn apply_preferences(&mut self) {
+        // A language picked in Settings wins over the account setting
+        let title_language = self
+            .settings_screen
+            .title_language()
+            .unwrap_or(self.preferences.title_language);
+        let show_adult = self.preferences.display_adult_content;
+
+        self.details_screen
+            .set_score_format(self.preferences.score_format);
+        self.details_screen.set_title_language(title_language);
+        self.home_screen.set_title_language(title_language);
+        self.my_list_screen.set_title_language(title_language);
+        self.import_screen.set_title_language(title_language);
+        self.my_list_screen
+            .set_score_format(self.preferences.score_format);
+        self.search_screen.set_title_language(title_language);
+        self.search_screen.set_show_adult(show_adult);
+        self.details_screen.set_show_adult(show_adult);
+        self.seasonal_screen.set_title_language(title_language);
+        self.calendar_screen.set_title_language(title_language);
+        self.profile_screen.set_title_language(title_language);
+        self.profile_screen
+            .set_score_format(self.preferences.score_format);
+        self.character_screen.set_title_language(title_language);
+        self.staff_screen.set_title_language(title_language);
+        self.studio_screen.set_title_language(title_language);
+        self.notifications_screen.set_title_language(title_language);
+        self.activity_screen.set_title_language(title_language);
+    }
+
+    fn load_preferences(&self) -> Command<Message> {
+        let client = self.api_client.clone();
+        Command::perform(
+            async move {
+                client
+                    .get_viewer_preferences()
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            Message::PreferencesLoaded,
+        )
+    }
+
+    fn load_unread_count(&self) -> Command<Message> {
+        let client = self.api_client.clone();
+        Command::perform(
+            async move {
+                if !client.is_authenticated().await {
+                    return Ok(0);
+                }
+                client
+                    .get_unread_notification_count()
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            Message::UnreadCountLoaded,
+        )
+    }
+
     fn check_auth_status(&self) -> Command<Message> {
         let client = self.api_client.clone();
         Command::perform(
@@ -145,53 +377,53 @@ impl Application for AniListApp {
     type Flags = ();
 
     fn new(_flags: ()) -> (Self, Command<Message>) {
-        // This should not be called directly, but we need to provide a default
-        // implementation to satisfy the trait.
-        let default_db =
-            Database::new().unwrap_or_else(|_| panic!("Failed to initialize database"));
-        let db_arc = Arc::new(Mutex::new(default_db.clone()));
-
-        let client = AniListClient::new();
-
-        let home_screen = HomeScreen::new(client.clone());
-        let search_screen = SearchScreen::new(client.clone());
-        let details_screen = DetailsScreen::new(client.clone());
-        let profile_screen = ProfileScreen::new(client.clone());
-        let settings_screen = SettingsScreen::new(db_arc.clone());
-
-        let default_app = Self {
-            api_client: client,
-            db: db_arc,
-            auth_component: AuthComponent::new(AuthManager::new(
-                AuthConfig {
-                    client_id: "default".to_string(),
-                    client_secret: "default".to_string(),
-                    redirect_uri: "http://localhost:8080/callback".to_string(),
-                },
-                default_db,
-            )),
-            current_screen: Screen::Home,
-            screen_history: Vec::new(),
-            search_query: String::new(),
-            home_screen,
-            search_screen,
-            details_screen,
-            profile_screen,
-            settings_screen,
-            is_loading: false,
-            error: None,
-        };
-
-        (default_app, Command::none())
+        let config = load_config().unwrap_or_else(|e| {
+            warn!("Failed to load config, using defaults: {}", e);
+            Config::default()
+        });
+        let db = Database::new().unwrap_or_else(|e| panic!("Failed to initialize database: {}", e));
+
+        // One transport for the API client and the auth flow, so both talk to
+        // the configured endpoint
+        let transport = Arc::new(HttpTransport::new(config.api_endpoint.clone()));
+        let auth_manager =
+            AuthManager::new(config.auth_config.clone(), db.clone()).with_transport(transport);
+        let client = AniListClient::with_auth_manager(auth_manager);
+
+        let mut app = Self::new(client, db, config.auth_config);
+
+        // Send any edits left over from an earlier offline session, and back up
+        // the database if the last backup is old enough
+        let backup = app
+            .settings_screen
+            .scheduled_backup()
+            .map(Message::Settings);
+        (
+            app,
+            Command::batch(vec![
+                Command::perform(async {}, |_| Message::ReplayOutbox),
+                backup,
+            ]),
+        )
     }
 
     fn title(&self) -> String {
         match &self.current_screen {
             Screen::Home => String::from("AniList Desktop - Home"),
+            Screen::MyList => String::from("AniList Desktop - My List"),
             Screen::Search => String::from("AniList Desktop - Search"),
+            Screen::Seasonal => String::from("AniList Desktop - Seasonal Chart"),
+            Screen::Calendar => String::from("AniList Desktop - Airing Schedule"),
             Screen::Details(_) => String::from("AniList Desktop - Anime Details"),
+            Screen::MangaDetails(_) => String::from("AniList Desktop - Manga Details"),
+            Screen::Character(_) => String::from("AniList Desktop - Character"),
+            Screen::Staff(_) => String::from("AniList Desktop - Staff"),
+            Screen::Studio(_) => String::from("AniList Desktop - Studio"),
             Screen::Profile(username) => format!("AniList Desktop - {}'s Profile", username),
+            Screen::Notifications => String::from("AniList Desktop - Notifications"),
+            Screen::Feed | Screen::Activity(_) => String::from("AniList Desktop - Activity"),
             Screen::Settings => String::from("AniList Desktop - Settings"),
+            Screen::Import => String::from("AniList Desktop - Import"),
         }
     }
 
@@ -202,19 +434,32 @@ impl Application for AniListApp {
 
                 match &screen {
                     Screen::Home => self.home_screen.init().map(Message::Home),
+                    Screen::MyList => self.my_list_screen.init().map(Message::MyList),
                     Screen::Search => Command::none(),
+                    Screen::Seasonal => self.seasonal_screen.init().map(Message::Seasonal),
+                    Screen::Calendar => self.calendar_screen.init().map(Message::Calendar),
                     Screen::Details(id) => self.details_screen.load(*id).map(Message::Details),
+                    Screen::MangaDetails(id) => {
+                        self.details_screen.load_manga(*id).map(Message::Details)
+                    }
+                    Screen::Character(id) => {
+                        self.character_screen.load(*id).map(Message::Character)
+                    }
+                    Screen::Staff(id) => self.staff_screen.load(*id).map(Message::Staff),
+                    Screen::Studio(id) => self.studio_screen.load(*id).map(Message::Studio),
                     Screen::Profile(username) => self
                         .profile_screen
                         .load(username.clone())
                         .map(Message::Profile),
-                    Screen::Settings => Command::none(),
+                    Screen::Notifications => {
+                        self.notifications_screen.init().map(Message::Notifications)
+                    }
+                    Screen::Feed => self.activity_screen.init().map(Message::Activity),
+                    Screen::Activity(id) => self.activity_screen.open(*id).map(Message::Activity),
+                    Screen::Settings | Screen::Import => Command::none(),
                 }
             }
-            Message::GoBack => {
-                self.go_back();
-                Command::none()
-            }
+            Message::GoBack => self.go_back(),
             Message::Auth(auth_msg) => {
                 let auth_cmd = self.auth_component.update(auth_msg);
 
@@ -230,6 +475,10 @@ impl Application for AniListApp {
                         self.navigate_to(Screen::Details(id));
                         self.details_screen.load(id).map(Message::Details)
                     }
+                    HomeMessage::MangaSelected(id) => {
+                        self.navigate_to(Screen::MangaDetails(id));
+                        self.details_screen.load_manga(id).map(Message::Details)
+                    }
                     _ => cmd.map(Message::Home),
                 }
             }
@@ -242,28 +491,231 @@ impl Application for AniListApp {
                         self.navigate_to(Screen::Details(id));
                         self.details_screen.load(id).map(Message::Details)
                     }
+                    SearchMessage::MangaSelected(id) => {
+                        self.navigate_to(Screen::MangaDetails(id));
+                        self.details_screen.load_manga(id).map(Message::Details)
+                    }
                     _ => cmd.map(Message::Search),
                 }
             }
-            Message::Details(details_msg) => self
-                .details_screen
-                .update(details_msg)
-                .map(Message::Details),
-            Message::Profile(profile_msg) => self
-                .profile_screen
-                .update(profile_msg)
-                .map(Message::Profile),
-            Message::Settings(settings_msg) => self
-                .settings_screen
-                .update(settings_msg)
-                .map(Message::Settings),
+            Message::Seasonal(seasonal_msg) => {
+                let cmd = self.seasonal_screen.update(seasonal_msg.clone());
+
+                match seasonal_msg {
+                    SeasonalMessage::AnimeSelected(id) => {
+                        self.navigate_to(Screen::Details(id));
+                        self.details_screen.load(id).map(Message::Details)
+                    }
+                    _ => cmd.map(Message::Seasonal),
+                }
+            }
+            Message::Calendar(calendar_msg) => {
+                let cmd = self.calendar_screen.update(calendar_msg.clone());
+
+                match calendar_msg {
+                    CalendarMessage::AnimeSelected(id) => {
+                        self.navigate_to(Screen::Details(id));
+                        self.details_screen.load(id).map(Message::Details)
+                    }
+                    _ => cmd.map(Message::Calendar),
+                }
+            }
+            Message::Details(details_msg) => {
+                let cmd = self.details_screen.update(details_msg.clone());
+
+                match details_msg {
+                    DetailsMessage::CharacterSelected(id) => self.open_character(id),
+                    DetailsMessage::StaffSelected(id) => self.open_staff(id),
+                    DetailsMessage::MediaSelected(id, media_type) => {
+                        self.open_media(id, media_type)
+                    }
+                    DetailsMessage::StudioSelected(id) => {
+                        self.navigate_to(Screen::Studio(id));
+                        self.studio_screen.load(id).map(Message::Studio)
+                    }
+                    _ => cmd.map(Message::Details),
+                }
+            }
+            Message::Character(character_msg) => {
+                let cmd = self.character_screen.update(character_msg.clone());
+
+                match character_msg {
+                    CharacterMessage::StaffSelected(id) => self.open_staff(id),
+                    CharacterMessage::MediaSelected(id, media_type) => {
+                        self.open_media(id, media_type)
+                    }
+                    _ => cmd.map(Message::Character),
+                }
+            }
+            Message::Staff(staff_msg) => {
+                let cmd = self.staff_screen.update(staff_msg.clone());
+
+                match staff_msg {
+                    StaffMessage::CharacterSelected(id) => self.open_character(id),
+                    StaffMessage::MediaSelected(id, media_type) => self.open_media(id, media_type),
+                    _ => cmd.map(Message::Staff),
+                }
+            }
+            Message::Studio(studio_msg) => {
+                let cmd = self.studio_screen.update(studio_msg.clone());
+
+                match studio_msg {
+                    StudioMessage::MediaSelected(id, media_type) => self.open_media(id, media_type),
+                    _ => cmd.map(Message::Studio),
+                }
+            }
+            Message::Profile(profile_msg) => {
+                let cmd = self.profile_screen.update(profile_msg.clone());
+
+                match profile_msg {
+                    ProfileMessage::FavoriteAnimeSelected(id) => {
+                        self.navigate_to(Screen::Details(id));
+                        self.details_screen.load(id).map(Message::Details)
+                    }
+                    ProfileMessage::FavoriteMangaSelected(id) => {
+                        self.navigate_to(Screen::MangaDetails(id));
+                        self.details_screen.load_manga(id).map(Message::Details)
+                    }
+                    ProfileMessage::FavoriteCharacterSelected(id) => self.open_character(id),
+                    ProfileMessage::UserSelected(name) => self.open_profile(name),
+                    ProfileMessage::MediaSelected(id, media_type) => {
+                        self.open_media(id, media_type)
+                    }
+                    ProfileMessage::ActivitySelected(id) => self.open_activity(id),
+                    _ => cmd.map(Message::Profile),
+                }
+            }
+            Message::Notifications(notifications_msg) => {
+                let cmd = self.notifications_screen.update(notifications_msg.clone());
+
+                match notifications_msg {
+                    NotificationsMessage::Open(NotificationLink::Media(id, media_type)) => {
+                        self.open_media(id, media_type)
+                    }
+                    NotificationsMessage::Open(NotificationLink::User(name)) => {
+                        self.open_profile(name)
+                    }
+                    NotificationsMessage::Open(NotificationLink::Activity(id)) => {
+                        self.open_activity(id)
+                    }
+                    // The first page was fetched with the unread count reset
+                    NotificationsMessage::ResultsReceived(_, 1, Ok(_)) => {
+                        self.unread_notifications = 0;
+                        cmd.map(Message::Notifications)
+                    }
+                    _ => cmd.map(Message::Notifications),
+                }
+            }
+            Message::MyList(my_list_msg) => {
+                let cmd = self.my_list_screen.update(my_list_msg.clone());
+
+                match my_list_msg {
+                    MyListMessage::MediaSelected(id, media_type) => self.open_media(id, media_type),
+                    _ => cmd.map(Message::MyList),
+                }
+            }
+            Message::Activity(activity_msg) => {
+                let cmd = self.activity_screen.update(activity_msg.clone());
+
+                match activity_msg {
+                    ActivityMessage::UserSelected(name) => self.open_profile(name),
+                    ActivityMessage::MediaSelected(id, media_type) => {
+                        self.open_media(id, media_type)
+                    }
+                    _ => cmd.map(Message::Activity),
+                }
+            }
+            Message::Settings(settings_msg) => {
+                let cmd = self
+                    .settings_screen
+                    .update(settings_msg.clone())
+                    .map(Message::Settings);
+
+                match settings_msg {
+                    SettingsMessage::ConfigSaved(Ok(())) => {
+                        let offline_mode = self.settings_screen.offline_mode();
+                        self.details_screen.set_offline_mode(offline_mode);
+                        self.my_list_screen.set_offline_mode(offline_mode);
+                        self.seasonal_screen.set_offline_mode(offline_mode);
+                        self.import_screen.set_offline_mode(offline_mode);
+                        self.images.set_offline_mode(offline_mode);
+                        self.apply_preferences();
+
+                        // Back online, flush the outbox
+                        if offline_mode {
+                            cmd
+                        } else {
+                            Command::batch(vec![
+                                cmd,
+                                Command::perform(async {}, |_| Message::ReplayOutbox),
+                            ])
+                        }
+                    }
+                    SettingsMessage::RetryEdit(_) => Command::batch(vec![
+                        cmd,
+                        Command::perform(async {}, |_| Message::ReplayOutbox),
+                    ]),
+                    // The in-memory copies would outlive the database ones
+                    SettingsMessage::ClearCacheCompleted(Ok(())) => {
+                        self.cache.clear();
+                        cmd
+                    }
+                    // Nothing cached from before the restore still holds
+                    SettingsMessage::Restored(Ok(_)) => {
+                        self.cache.clear();
+                        Command::batch(vec![cmd, self.check_auth_status(), self.load_preferences()])
+                    }
+                    SettingsMessage::OpenImport => {
+                        self.navigate_to(Screen::Import);
+                        cmd
+                    }
+                    _ => cmd,
+                }
+            }
+            Message::Import(import_msg) => {
+                self.import_screen.update(import_msg).map(Message::Import)
+            }
             Message::AuthStatusChanged(is_authenticated) => {
-                // Refresh the home screen if authentication status changed
-                if is_authenticated && self.current_screen == Screen::Home {
-                    self.home_screen.init().map(Message::Home)
+                if !is_authenticated {
+                    self.profile_screen.set_viewer_id(None);
+                    self.cache.clear();
+                    return Command::none();
+                }
+
+                // Refresh the home screen, the viewer's preferences and unread count
+                if self.current_screen == Screen::Home {
+                    Command::batch(vec![
+                        self.home_screen.init().map(Message::Home),
+                        self.load_preferences(),
+                        self.load_unread_count(),
+                    ])
                 } else {
-                    Command::none()
+                    Command::batch(vec![self.load_preferences(), self.load_unread_count()])
+                }
+            }
+            Message::PreferencesLoaded(result) => {
+                match result {
+                    Ok((user_id, preferences)) => {
+                        if let Ok(db) = self.db.lock() {
+                            if let Err(e) = db.save_viewer_preferences(user_id, &preferences) {
+                                warn!("Failed to store viewer preferences: {}", e);
+                            }
+                        }
+                        self.preferences = preferences;
+                        self.profile_screen.set_viewer_id(Some(user_id));
+                        self.apply_preferences();
+                    }
+                    // Keep the stored preferences when offline
+                    Err(e) => warn!("Failed to load viewer preferences: {}", e),
+                }
+                Command::none()
+            }
+            Message::UnreadCountLoaded(result) => {
+                match result {
+                    Ok(count) => self.unread_notifications = count,
+                    Err(e) => warn!("Failed to load unread notification count: {}", e),
                 }
+                Command::none()
             }
             Message::SearchQueryChanged(query) => {
                 self.search_query = query;
@@ -276,18 +728,73 @@ impl Application for AniListApp {
                     .update(SearchMessage::QueryChanged(self.search_query.clone()))
                     .map(Message::Search)
             }
+            Message::ReplayOutbox => {
+                if self.settings_screen.offline_mode() {
+                    return Command::none();
+                }
+
+                let outbox = self.outbox.clone();
+                let client = self.api_client.clone();
+                Command::perform(
+                    async move { outbox.replay(&client).await.map_err(|e| e.to_string()) },
+                    Message::OutboxReplayed,
+                )
+            }
+            Message::OutboxReplayed(result) => {
+                self.settings_screen.refresh_failed_edits();
+
+                match result {
+                    Ok(report) if !report.failed.is_empty() => {
+                        // Report every edit that could not be sent
+                        let failures: Vec<String> = report
+                            .failed
+                            .iter()
+                            .map(|failure| {
+                                format!(
+                                    "{:?} {}: {}",
+                                    failure.media_type, failure.media_id, failure.error
+                                )
+                            })
+                            .collect();
+                        let mut error = format!(
+                            "Failed to sync {} offline change(s): {}",
+                            failures.len(),
+                            failures.join("; ")
+                        );
+                        if report.failed.iter().any(|failure| failure.gave_up) {
+                            error.push_str(
+                                ". Changes AniList keeps rejecting won't be sent again, \
+                                 review them in Settings",
+                            );
+                        }
+                        self.error = Some(error);
+                    }
+                    Ok(_) => {}
+                    Err(e) => self.error = Some(format!("Failed to sync offline changes: {}", e)),
+                }
+                Command::none()
+            }
             Message::Error(e) => {
                 self.error = Some(e);
                 Command::none()
             }
             Message::Tick => {
-                // Check auth status periodically
-                self.check_auth_status()
+                // Check auth status, retry offline edits and refresh countdowns and the
+                // unread count periodically
+                Command::batch(vec![
+                    self.check_auth_status(),
+                    self.load_unread_count(),
+                    self.calendar_screen.tick().map(Message::Calendar),
+                    self.settings_screen
+                        .scheduled_backup()
+                        .map(Message::Settings),
+                    Command::perform(async {}, |_| Message::ReplayOutbox),
+                ])
             }
         }
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         // Top navigation bar
         let nav_bar = row![
             button(text("Home"))
@@ -298,6 +805,14 @@ impl Application for AniListApp {
                 } else {
                     iced::theme::Button::Secondary
                 }),
+            button(text("My List"))
+                .on_press(Message::ChangeScreen(Screen::MyList))
+                .padding(10)
+                .style(if self.current_screen == Screen::MyList {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                }),
             button(text("Search"))
                 .on_press(Message::ChangeScreen(Screen::Search))
                 .padding(10)
@@ -306,6 +821,44 @@ impl Application for AniListApp {
                 } else {
                     iced::theme::Button::Secondary
                 }),
+            button(text("Seasonal"))
+                .on_press(Message::ChangeScreen(Screen::Seasonal))
+                .padding(10)
+                .style(if self.current_screen == Screen::Seasonal {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                }),
+            button(text("Calendar"))
+                .on_press(Message::ChangeScreen(Screen::Calendar))
+                .padding(10)
+                .style(if self.current_screen == Screen::Calendar {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                }),
+            button(text("Activity"))
+                .on_press(Message::ChangeScreen(Screen::Feed))
+                .padding(10)
+                .style(
+                    if matches!(self.current_screen, Screen::Feed | Screen::Activity(_)) {
+                        iced::theme::Button::Primary
+                    } else {
+                        iced::theme::Button::Secondary
+                    }
+                ),
+            button(text(if self.unread_notifications > 0 {
+                format!("Notifications ({})", self.unread_notifications)
+            } else {
+                "Notifications".to_string()
+            }))
+            .on_press(Message::ChangeScreen(Screen::Notifications))
+            .padding(10)
+            .style(if self.current_screen == Screen::Notifications {
+                iced::theme::Button::Primary
+            } else {
+                iced::theme::Button::Secondary
+            }),
             button(text("Settings"))
                 .on_press(Message::ChangeScreen(Screen::Settings))
                 .padding(10)
@@ -340,7 +893,7 @@ impl Application for AniListApp {
             .width(Length::Fill);
 
         // Error message if any
-        let error_view = if let Some(error) = &self.error {
+        let error_view: Element<Message> = if let Some(error) = &self.error {
             container(
                 text(error).style(iced::theme::Text::Color(iced::Color::from_rgb(
                     0.8, 0.2, 0.2,
@@ -359,23 +912,36 @@ impl Application for AniListApp {
         // Main content area based on current screen
         let content: Element<Message> = match &self.current_screen {
             Screen::Home => self.home_screen.view().map(Message::Home),
+            Screen::MyList => self.my_list_screen.view().map(Message::MyList),
             Screen::Search => self.search_screen.view().map(Message::Search),
-            Screen::Details(_) => self.details_screen.view().map(Message::Details),
+            Screen::Seasonal => self.seasonal_screen.view().map(Message::Seasonal),
+            Screen::Calendar => self.calendar_screen.view().map(Message::Calendar),
+            Screen::Details(_) | Screen::MangaDetails(_) => {
+                self.details_screen.view().map(Message::Details)
+            }
+            Screen::Character(_) => self.character_screen.view().map(Message::Character),
+            Screen::Staff(_) => self.staff_screen.view().map(Message::Staff),
+            Screen::Studio(_) => self.studio_screen.view().map(Message::Studio),
             Screen::Profile(_) => self.profile_screen.view().map(Message::Profile),
+            Screen::Notifications => self.notifications_screen.view().map(Message::Notifications),
+            Screen::Feed | Screen::Activity(_) => {
+                self.activity_screen.view().map(Message::Activity)
+            }
             Screen::Settings => self.settings_screen.view().map(Message::Settings),
+            Screen::Import => self.import_screen.view().map(Message::Import),
         };
 
         // Back button if we're not on the home screen
-        let back_button = if self.current_screen != Screen::Home && !self.screen_history.is_empty()
-        {
-            container(button(text("← Back")).on_press(Message::GoBack).padding(10))
-                .padding(10)
-                .width(Length::Fill)
-                .align_x(iced::alignment::Horizontal::Left)
-                .into()
-        } else {
-            container(text("")).width(Length::Fill).into()
-        };
+        let back_button: Element<Message> =
+            if self.current_screen != Screen::Home && !self.screen_history.is_empty() {
+                container(button(text("← Back")).on_press(Message::GoBack).padding(10))
+                    .padding(10)
+                    .width(Length::Fill)
+                    .align_x(iced::alignment::Horizontal::Left)
+                    .into()
+            } else {
+                container(text("")).width(Length::Fill).into()
+            };
 
         // Main layout
         container(column![top_area, error_view, back_button, content].spacing(5))
@@ -0,0 +1,105 @@
+use chrono::Utc;
+use iced::widget::{button, column, container, row, text};
+use iced::{Alignment, Element, Length};
+
+use crate::api::models::{Activity, ActivityKind, MediaType, TitleLanguage};
+use crate::utils::text::time_ago;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    UserSelected(String),
+    MediaSelected(i32, MediaType),
+    ToggleLike(i32),
+    RepliesSelected(i32),
+}
+
+// A status post, list update or message with its likes and replies
+pub struct ActivityCard<'a> {
+    activity: &'a Activity,
+    title_language: TitleLanguage,
+    // Without actions the like count is shown but can't be changed
+    show_actions: bool,
+}
+
+impl<'a> ActivityCard<'a> {
+    pub fn new(activity: &'a Activity) -> Self {
+        Self {
+            activity,
+            title_language: TitleLanguage::default(),
+            show_actions: true,
+        }
+    }
+
+    pub fn title_language(mut self, title_language: TitleLanguage) -> Self {
+        self.title_language = title_language;
+        self
+    }
+
+    pub fn show_actions(mut self, show_actions: bool) -> Self {
+        self.show_actions = show_actions;
+        self
+    }
+
+    pub fn view(&self) -> Element<'a, Message> {
+        let activity = self.activity;
+
+        let mut headline = row![button(text(&activity.user).size(16))
+            .on_press(Message::UserSelected(activity.user.clone()))
+            .style(iced::theme::Button::Text)
+            .padding(0)]
+        .spacing(5)
+        .align_items(Alignment::Center);
+
+        let mut card = column![].spacing(8);
+        match (&activity.kind, &activity.media) {
+            (ActivityKind::List, Some((id, media_type, _))) => {
+                headline = headline.push(
+                    button(text(activity.summary(self.title_language)).size(16))
+                        .on_press(Message::MediaSelected(*id, *media_type))
+                        .style(iced::theme::Button::Text)
+                        .padding(0),
+                );
+                card = card.push(headline);
+            }
+            _ => {
+                card = card.push(headline).push(text(&activity.text).size(14));
+            }
+        }
+
+        let like_label = format!(
+            "{} {}",
+            if activity.is_liked { "♥" } else { "♡" },
+            activity.like_count
+        );
+        let replies_label = match activity.reply_count {
+            1 => "1 reply".to_string(),
+            n => format!("{} replies", n),
+        };
+
+        let mut footer = row![text(time_ago(activity.created_at, Utc::now().timestamp())).size(12)]
+            .spacing(15)
+            .align_items(Alignment::Center);
+        footer = if self.show_actions {
+            footer.push(
+                button(text(like_label).size(12))
+                    .on_press(Message::ToggleLike(activity.id))
+                    .style(iced::theme::Button::Text)
+                    .padding(0),
+            )
+        } else {
+            footer.push(text(like_label).size(12))
+        };
+        footer = footer.push(
+            button(text(replies_label).size(12))
+                .on_press(Message::RepliesSelected(activity.id))
+                .style(iced::theme::Button::Text)
+                .padding(0),
+        );
+
+        container(card.push(footer))
+            .padding(10)
+            .width(Length::Fill)
+            .style(iced::theme::Container::Box)
+            .into()
+    }
+}
@@ -1,10 +1,15 @@
+use iced::widget::image::Handle;
 use iced::widget::{button, column, container, text};
 use iced::{Element, Length};
 
-use crate::api::models::Media;
+use crate::api::models::{Media, TitleLanguage};
+use crate::ui::images::image_or_placeholder;
 
 pub struct AnimeCard {
     media: Media,
+    title_language: TitleLanguage,
+    // The cover, once the image loader has it
+    cover: Option<Handle>,
     on_click: Option<Box<dyn Fn(i32) -> Message + 'static>>,
 }
 
@@ -17,10 +22,30 @@ impl AnimeCard {
     pub fn new(media: Media) -> Self {
         Self {
             media,
+            title_language: TitleLanguage::default(),
+            cover: None,
             on_click: None,
         }
     }
 
+    pub fn title_language(mut self, title_language: TitleLanguage) -> Self {
+        self.title_language = title_language;
+        self
+    }
+
+    /// The url of the cover this card shows, for the image loader
+    pub fn cover_url(&self) -> Option<&str> {
+        self.media
+            .cover_image
+            .as_ref()
+            .and_then(|cover| cover.medium.as_deref())
+    }
+
+    pub fn cover(mut self, cover: Option<Handle>) -> Self {
+        self.cover = cover;
+        self
+    }
+
     pub fn on_click<F>(mut self, f: F) -> Self
     where
         F: Fn(i32) -> Message + 'static,
@@ -29,30 +54,16 @@ impl AnimeCard {
         self
     }
 
-    pub fn view(&self) -> Element<Message> {
-        let title = self
-            .media
-            .title
-            .romaji
-            .as_ref()
-            .or(self.media.title.english.as_ref())
-            .unwrap_or(&"Unknown".to_string())
-            .clone();
-
-        let _image_url = self
-            .media
-            .cover_image
-            .as_ref()
-            .and_then(|cover| cover.medium.clone())
-            .unwrap_or_default();
-
-        // For now, we'll use a placeholder instead of loading the actual image
-        let image_placeholder = container(text("Image"))
-            .width(Length::Fixed(100.0))
-            .height(Length::Fixed(150.0));
+    pub fn view(&self) -> Element<'_, Message> {
+        let title = self.media.title.preferred(self.title_language);
 
         let card_content = column![
-            image_placeholder,
+            image_or_placeholder(
+                self.cover.clone(),
+                "Image",
+                Length::Fixed(100.0),
+                Length::Fixed(150.0)
+            ),
             text(&title).size(14),
             text(format!(
                 "Score: {}",
@@ -123,7 +123,7 @@ impl AuthComponent {
         }
     }
 
-    pub fn view(&self) -> Element<Message> {
+    pub fn view(&self) -> Element<'_, Message> {
         match &self.state {
             State::Idle => container(
                 column![
@@ -186,8 +186,4 @@ impl AuthComponent {
                 .into(),
         }
     }
-
-    pub fn is_authenticated(&self) -> bool {
-        matches!(self.state, State::Authenticated { .. })
-    }
 }
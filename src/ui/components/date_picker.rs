@@ -0,0 +1,104 @@
+use chrono::{Datelike, Local};
+use iced::widget::{button, pick_list, row, text};
+use iced::{Alignment, Element, Length};
+
+use crate::api::models::FuzzyDate;
+
+const UNSET: &str = "—";
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+// Year, month and day pickers for a date that may be partially known
+pub struct DatePicker {
+    label: String,
+    date: FuzzyDate,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    YearSelected(String),
+    MonthSelected(String),
+    DaySelected(String),
+    Today,
+    Clear,
+}
+
+impl DatePicker {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            date: FuzzyDate::default(),
+        }
+    }
+
+    pub fn date(&self) -> FuzzyDate {
+        self.date
+    }
+
+    pub fn set_date(&mut self, date: FuzzyDate) {
+        self.date = date;
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::YearSelected(year) => self.date.year = year.parse().ok(),
+            Message::MonthSelected(month) => {
+                self.date.month = MONTHS
+                    .iter()
+                    .position(|m| *m == month)
+                    .map(|i| i as i32 + 1);
+            }
+            Message::DaySelected(day) => self.date.day = day.parse().ok(),
+            Message::Today => {
+                let today = Local::now();
+                self.date = FuzzyDate {
+                    year: Some(today.year()),
+                    month: Some(today.month() as i32),
+                    day: Some(today.day() as i32),
+                };
+            }
+            Message::Clear => self.date = FuzzyDate::default(),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let current_year = Local::now().year();
+        let years: Vec<String> = std::iter::once(UNSET.to_string())
+            .chain((1940..=current_year + 1).rev().map(|y| y.to_string()))
+            .collect();
+        let months: Vec<String> = std::iter::once(UNSET)
+            .chain(MONTHS)
+            .map(str::to_string)
+            .collect();
+        let days: Vec<String> = std::iter::once(UNSET.to_string())
+            .chain((1..=31).map(|d| d.to_string()))
+            .collect();
+
+        let year = self.date.year.map_or(UNSET.to_string(), |y| y.to_string());
+        let month = self
+            .date
+            .month
+            .and_then(|m| MONTHS.get((m - 1) as usize))
+            .map_or(UNSET.to_string(), |m| m.to_string());
+        let day = self.date.day.map_or(UNSET.to_string(), |d| d.to_string());
+
+        row![
+            text(&self.label).size(14).width(Length::Fixed(90.0)),
+            pick_list(years, Some(year), Message::YearSelected),
+            pick_list(months, Some(month), Message::MonthSelected),
+            pick_list(days, Some(day), Message::DaySelected),
+            button(text("Today").size(14))
+                .on_press(Message::Today)
+                .padding(5)
+                .style(iced::theme::Button::Secondary),
+            button(text("Clear").size(14))
+                .on_press(Message::Clear)
+                .padding(5)
+                .style(iced::theme::Button::Secondary),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center)
+        .into()
+    }
+}
@@ -0,0 +1,122 @@
+use iced::widget::{button, column, row, text};
+use iced::{Alignment, Element, Length};
+
+use crate::api::models::{MediaTitle, MediaType, TitleLanguage};
+
+// A media a character, staff member or studio is credited on
+#[derive(Debug, Clone)]
+pub struct MediaCredit {
+    pub id: i32,
+    pub media_type: MediaType,
+    pub title: MediaTitle,
+    pub format: Option<String>,
+    pub year: Option<i32>,
+    // e.g. "MAIN", "Director" or "Main studio"
+    pub role: Option<String>,
+    // Characters voiced in this media, for voice acting credits
+    pub characters: Vec<(i32, String)>,
+    // The viewer's list status, if the media is on their list
+    pub list_status: Option<String>,
+}
+
+/// Append credits, folding a media that is already listed into one credit
+/// with all of its roles and characters
+pub fn merge_credits(credits: &mut Vec<MediaCredit>, new: Vec<MediaCredit>) {
+    for credit in new {
+        match credits.iter_mut().find(|c| c.id == credit.id) {
+            Some(existing) => {
+                existing.role = match (existing.role.take(), credit.role) {
+                    (Some(a), Some(b)) if a != b => Some(format!("{}, {}", a, b)),
+                    (a, b) => a.or(b),
+                };
+                for character in credit.characters {
+                    if !existing.characters.contains(&character) {
+                        existing.characters.push(character);
+                    }
+                }
+            }
+            None => credits.push(credit),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    MediaSelected(i32, MediaType),
+    CharacterSelected(i32),
+}
+
+// A grid of credits linking to the media and any characters voiced
+pub struct MediaCredits<'a> {
+    credits: &'a [MediaCredit],
+    title_language: TitleLanguage,
+}
+
+impl<'a> MediaCredits<'a> {
+    pub fn new(credits: &'a [MediaCredit]) -> Self {
+        Self {
+            credits,
+            title_language: TitleLanguage::default(),
+        }
+    }
+
+    pub fn title_language(mut self, title_language: TitleLanguage) -> Self {
+        self.title_language = title_language;
+        self
+    }
+
+    pub fn view(&self) -> Element<'a, Message> {
+        let mut grid = column![].spacing(15);
+
+        for chunk in self.credits.chunks(4) {
+            let mut row_content = row![].spacing(15);
+
+            for credit in chunk {
+                let subtitle = match (&credit.format, credit.year) {
+                    (Some(format), Some(year)) => format!("{} • {}", format, year),
+                    (Some(format), None) => format.clone(),
+                    (None, Some(year)) => year.to_string(),
+                    (None, None) => String::new(),
+                };
+
+                let mut card = column![button(
+                    column![
+                        text(credit.title.preferred(self.title_language)).size(14),
+                        text(subtitle).size(12),
+                    ]
+                    .spacing(2)
+                )
+                .on_press(Message::MediaSelected(credit.id, credit.media_type))
+                .style(iced::theme::Button::Text)
+                .width(Length::Fill)]
+                .spacing(4)
+                .width(Length::Fixed(200.0));
+
+                if let Some(role) = &credit.role {
+                    card = card.push(text(role).size(12));
+                }
+
+                for (character_id, name) in &credit.characters {
+                    card = card.push(
+                        button(text(format!("as {}", name)).size(12))
+                            .on_press(Message::CharacterSelected(*character_id))
+                            .style(iced::theme::Button::Text)
+                            .padding(0),
+                    );
+                }
+
+                if let Some(status) = &credit.list_status {
+                    card = card.push(text(format!("On list: {}", status)).size(12).style(
+                        iced::theme::Text::Color(iced::Color::from_rgb(0.2, 0.7, 0.3)),
+                    ));
+                }
+
+                row_content = row_content.push(card);
+            }
+
+            grid = grid.push(row_content.align_items(Alignment::Start));
+        }
+
+        grid.into()
+    }
+}
@@ -1,11 +1,12 @@
 use iced::widget::{column, row, scrollable, text};
 use iced::{Element, Length};
 
-use crate::api::models::MediaListEntry;
-use crate::ui::components::anime_card::{AnimeCard, Message as CardMessage};
+use crate::api::models::{Media, MediaListEntry, MediaType, TitleLanguage};
 
 pub struct MediaList {
     entries: Vec<MediaListEntry>,
+    media_type: MediaType,
+    title_language: TitleLanguage,
     on_select: Option<Box<dyn Fn(i32) -> Message + 'static>>,
 }
 
@@ -19,10 +20,22 @@ impl MediaList {
     pub fn new(entries: Vec<MediaListEntry>) -> Self {
         Self {
             entries,
+            media_type: MediaType::ANIME,
+            title_language: TitleLanguage::default(),
             on_select: None,
         }
     }
 
+    pub fn media_type(mut self, media_type: MediaType) -> Self {
+        self.media_type = media_type;
+        self
+    }
+
+    pub fn title_language(mut self, title_language: TitleLanguage) -> Self {
+        self.title_language = title_language;
+        self
+    }
+
     pub fn on_select<F>(mut self, f: F) -> Self
     where
         F: Fn(i32) -> Message + 'static,
@@ -31,7 +44,7 @@ impl MediaList {
         self
     }
 
-    pub fn view(&self) -> Element<Message> {
+    pub fn view<'a>(&self) -> Element<'a, Message> {
         let mut list_content = column![text("Your List").size(24)].spacing(10).padding(20);
 
         // Group entries by status
@@ -55,28 +68,28 @@ impl MediaList {
 
         // Display Current section if not empty
         if !current.is_empty() {
-            list_content = list_content.push(text("Currently Watching").size(18));
+            list_content = list_content.push(
+                text(match self.media_type {
+                    MediaType::ANIME => "Currently Watching",
+                    MediaType::MANGA => "Currently Reading",
+                })
+                .size(18),
+            );
 
             // Create rows of 4 cards
             for chunk in current.chunks(4) {
                 let mut row_content = row![].spacing(10);
 
-                for (_entry, media) in chunk {
+                for (entry, media) in chunk {
                     // For each card, create a new function that generates the card view
                     let card_id = media.id;
 
                     // Create a button directly with the anime info
                     let btn = iced::widget::button(
                         column![
-                            text(
-                                &media
-                                    .title
-                                    .romaji
-                                    .clone()
-                                    .unwrap_or_else(|| "Unknown".to_string())
-                            )
-                            .size(14),
-                            text(format!("Score: {}", media.average_score.unwrap_or(0.0))).size(12)
+                            text(media.title.preferred(self.title_language)).size(14),
+                            text(format!("Score: {}", media.average_score.unwrap_or(0.0))).size(12),
+                            text(self.progress_label(entry, media)).size(12)
                         ]
                         .spacing(5)
                         .padding(10)
@@ -102,4 +115,23 @@ impl MediaList {
             .width(Length::Fill)
             .into()
     }
+
+    fn progress_label(&self, entry: &MediaListEntry, media: &Media) -> String {
+        let of_total = |total: Option<i32>| total.map_or_else(String::new, |t| format!("/{}", t));
+
+        match self.media_type {
+            MediaType::ANIME => format!(
+                "Ep {}{}",
+                entry.progress.unwrap_or(0),
+                of_total(media.episodes)
+            ),
+            MediaType::MANGA => format!(
+                "Ch {}{} • Vol {}{}",
+                entry.progress.unwrap_or(0),
+                of_total(media.chapters),
+                entry.progress_volumes.unwrap_or(0),
+                of_total(media.volumes)
+            ),
+        }
+    }
 }
@@ -1,10 +1,10 @@
+pub mod activity_card;
+// Not used by any screen yet
+#[allow(dead_code)]
 pub mod anime_card;
 pub mod auth;
+pub mod date_picker;
+pub mod media_credits;
 pub mod media_list;
+#[allow(dead_code)]
 pub mod user_stats;
-
-// Re-export common components
-pub use anime_card::AnimeCard;
-pub use auth::AuthComponent;
-pub use media_list::MediaList;
-pub use user_stats::UserStats;
@@ -17,7 +17,7 @@ impl UserStats {
         Self { user }
     }
 
-    pub fn view(&self) -> Element<Message> {
+    pub fn view(&self) -> Element<'_, Message> {
         let anime_stats = column![
             text("Anime Stats").size(18),
             row![
@@ -0,0 +1,185 @@
+use iced::widget::image::Handle;
+use iced::widget::{container, image, text};
+use iced::{Element, Length};
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+use crate::data::image_cache::{ImageCache, DEFAULT_MAX_BYTES};
+use crate::utils::error::AppError;
+
+type PendingLoad = Arc<OnceCell<Result<Handle, String>>>;
+
+/// Loads cover art, banners, avatars and character images through the disk
+/// cache. Clones share the same cache, so screens can each hold one.
+#[derive(Clone)]
+pub struct ImageLoader {
+    // None when the cache directory could not be opened
+    cache: Option<Arc<Mutex<ImageCache>>>,
+    http: reqwest::Client,
+    loaded: Arc<Mutex<HashMap<String, Handle>>>,
+    // Downloads in progress, so a url wanted by several views is fetched once
+    in_flight: Arc<Mutex<HashMap<String, PendingLoad>>>,
+    offline_mode: Arc<AtomicBool>,
+}
+
+impl ImageLoader {
+    pub fn new() -> Self {
+        let cache = ImageCache::default_dir()
+            .and_then(|dir| ImageCache::open(dir, DEFAULT_MAX_BYTES))
+            .map_err(|e| warn!("Image cache unavailable, images won't be kept: {}", e))
+            .ok();
+
+        Self::with_cache(cache)
+    }
+
+    pub fn with_cache(cache: Option<ImageCache>) -> Self {
+        Self {
+            cache: cache.map(|cache| Arc::new(Mutex::new(cache))),
+            http: reqwest::Client::new(),
+            loaded: Arc::new(Mutex::new(HashMap::new())),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            offline_mode: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    // Offline only images already on disk are shown
+    pub fn set_offline_mode(&self, offline_mode: bool) {
+        self.offline_mode.store(offline_mode, Ordering::Relaxed);
+    }
+
+    /// The image for `url` if it has been loaded
+    pub fn get(&self, url: &str) -> Option<Handle> {
+        self.loaded.lock().ok()?.get(url).cloned()
+    }
+
+    /// Load `url` from the disk cache, downloading it if it isn't there
+    pub async fn load(&self, url: String) -> Result<Handle, AppError> {
+        if let Some(handle) = self.get(&url) {
+            return Ok(handle);
+        }
+
+        let pending = {
+            let mut in_flight = self
+                .in_flight
+                .lock()
+                .map_err(|e| AppError::UnknownError(e.to_string()))?;
+            in_flight.entry(url.clone()).or_default().clone()
+        };
+        let result = pending
+            .get_or_init(|| async { self.fetch(&url).await.map_err(|e| e.to_string()) })
+            .await
+            .clone();
+
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            in_flight.remove(&url);
+        }
+        result.map_err(AppError::NetworkError)
+    }
+
+    /// Commands loading every url that isn't loaded yet. Empty urls are skipped.
+    pub fn load_all<Message: 'static>(
+        &self,
+        urls: impl IntoIterator<Item = String>,
+        on_loaded: fn(Result<String, String>) -> Message,
+    ) -> iced::Command<Message> {
+        let commands: Vec<_> = urls
+            .into_iter()
+            .filter(|url| !url.is_empty() && self.get(url).is_none())
+            .map(|url| {
+                let loader = self.clone();
+                iced::Command::perform(
+                    async move {
+                        loader
+                            .load(url.clone())
+                            .await
+                            .map(|_| url)
+                            .map_err(|e| e.to_string())
+                    },
+                    on_loaded,
+                )
+            })
+            .collect();
+
+        iced::Command::batch(commands)
+    }
+
+    async fn fetch(&self, url: &str) -> Result<Handle, AppError> {
+        let cached = match &self.cache {
+            Some(cache) => cache
+                .lock()
+                .map_err(|e| AppError::UnknownError(e.to_string()))?
+                .get(url),
+            None => None,
+        };
+        let cached = match cached {
+            Some(path) => tokio::fs::read(path).await.ok(),
+            None => None,
+        };
+
+        // Handles keep the bytes in memory: cache files may be evicted while
+        // the image is still on screen
+        let handle = match cached {
+            Some(bytes) => Handle::from_memory(bytes),
+            None if self.offline_mode.load(Ordering::Relaxed) => {
+                return Err(AppError::NetworkError(
+                    "Image not cached and offline mode is on".into(),
+                ));
+            }
+            None => {
+                debug!("Downloading image {}", url);
+                let bytes = self
+                    .http
+                    .get(url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .bytes()
+                    .await?;
+
+                if let Some(cache) = &self.cache {
+                    if let Err(e) = cache
+                        .lock()
+                        .map_err(|e| AppError::UnknownError(e.to_string()))?
+                        .insert(url, &bytes)
+                    {
+                        warn!("Failed to cache image {}: {}", url, e);
+                    }
+                }
+                Handle::from_memory(bytes.to_vec())
+            }
+        };
+
+        if let Ok(mut loaded) = self.loaded.lock() {
+            loaded.insert(url.to_string(), handle.clone());
+        }
+        Ok(handle)
+    }
+}
+
+impl Default for ImageLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The image if it is loaded, otherwise a placeholder box of the same size
+pub fn image_or_placeholder<'a, Message: 'a>(
+    handle: Option<Handle>,
+    placeholder: &str,
+    width: Length,
+    height: Length,
+) -> Element<'a, Message> {
+    match handle {
+        Some(handle) => image(handle).width(width).height(height).into(),
+        None => container(text(placeholder))
+            .width(width)
+            .height(height)
+            .center_x()
+            .center_y()
+            .style(iced::theme::Container::Box)
+            .into(),
+    }
+}
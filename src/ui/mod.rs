@@ -1,6 +1,9 @@
 mod app;
 mod components;
+mod images;
 mod screens;
+// JSON themes, not wired into the app yet
+#[allow(dead_code)]
 mod theme;
 
 // Re-export commonly used types
@@ -0,0 +1,534 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use iced::widget::{button, column, row, scrollable, text, text_input};
+use iced::{Alignment, Command, Element, Length};
+
+use crate::api::client::AniListClient;
+use crate::api::models::{
+    Activity, ActivityKind, ActivityQuery, ActivityReply, LikeTarget, MediaType, TitleLanguage,
+};
+use crate::ui::components::activity_card::{ActivityCard, Message as CardMessage};
+use crate::utils::text::time_ago;
+
+const PER_PAGE: i32 = 20;
+const REPLIES_PER_PAGE: i32 = 50;
+
+// The replies under an activity, loaded when it is expanded
+#[derive(Debug, Clone, Default)]
+struct ReplyThread {
+    replies: Vec<ActivityReply>,
+    draft: String,
+    is_loading: bool,
+    is_sending: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Load,
+    FollowingOnly(bool),
+    KindSelected(Option<ActivityKind>),
+    LoadMore,
+    ResultsReceived(u64, Result<(Vec<Activity>, bool), String>),
+    DraftChanged(String),
+    Post,
+    Posted(Result<i32, String>),
+    Card(CardMessage),
+    RepliesLoaded(i32, Result<Vec<ActivityReply>, String>),
+    ReplyDraftChanged(i32, String),
+    SendReply(i32),
+    ReplySent(i32, Result<ActivityReply, String>),
+    LikeReply(i32, i32),
+    Liked(LikeTarget, Result<(bool, i32), String>),
+    UserSelected(String),
+    MediaSelected(i32, MediaType),
+}
+
+pub struct ActivityScreen {
+    client: AniListClient,
+    title_language: TitleLanguage,
+    following_only: bool,
+    kind: Option<ActivityKind>,
+    // Set when showing a single activity, e.g. from a notification
+    focus: Option<i32>,
+    activities: Vec<Activity>,
+    page: i32,
+    has_next_page: bool,
+    // Bumped on every new query so late responses for an old one are dropped
+    generation: u64,
+    is_loading: bool,
+    error: Option<String>,
+    draft: String,
+    is_posting: bool,
+    // Expanded reply threads by activity id
+    threads: HashMap<i32, ReplyThread>,
+}
+
+impl ActivityScreen {
+    pub fn new(client: AniListClient) -> Self {
+        Self {
+            client,
+            title_language: TitleLanguage::default(),
+            following_only: true,
+            kind: None,
+            focus: None,
+            activities: Vec::new(),
+            page: 1,
+            has_next_page: false,
+            generation: 0,
+            is_loading: false,
+            error: None,
+            draft: String::new(),
+            is_posting: false,
+            threads: HashMap::new(),
+        }
+    }
+
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    pub fn focused_id(&self) -> Option<i32> {
+        self.focus
+    }
+
+    // Load the feed the first time it is opened, or after viewing one activity
+    pub fn init(&mut self) -> Command<Message> {
+        if self.focus.is_some() || (self.activities.is_empty() && !self.is_loading) {
+            self.focus = None;
+            self.update(Message::Load)
+        } else {
+            Command::none()
+        }
+    }
+
+    /// Show a single activity with its replies open
+    pub fn open(&mut self, activity_id: i32) -> Command<Message> {
+        self.focus = Some(activity_id);
+        Command::batch(vec![
+            self.update(Message::Load),
+            self.load_replies(activity_id),
+        ])
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Load => {
+                self.activities.clear();
+                self.threads.retain(|id, _| Some(*id) == self.focus);
+                self.page = 1;
+                self.generation += 1;
+                self.is_loading = true;
+                self.error = None;
+                self.fetch()
+            }
+            Message::FollowingOnly(following_only) => {
+                self.following_only = following_only;
+                self.update(Message::Load)
+            }
+            Message::KindSelected(kind) => {
+                self.kind = kind;
+                self.update(Message::Load)
+            }
+            Message::LoadMore => {
+                if self.is_loading || !self.has_next_page {
+                    return Command::none();
+                }
+
+                self.page += 1;
+                self.is_loading = true;
+                self.fetch()
+            }
+            Message::ResultsReceived(generation, result) => {
+                if generation != self.generation {
+                    return Command::none();
+                }
+                self.is_loading = false;
+
+                match result {
+                    Ok((activities, has_next_page)) => {
+                        self.activities.extend(activities);
+                        self.has_next_page = has_next_page;
+                    }
+                    Err(e) => self.error = Some(format!("Failed to load activity: {}", e)),
+                }
+
+                Command::none()
+            }
+            Message::DraftChanged(draft) => {
+                self.draft = draft;
+                Command::none()
+            }
+            Message::Post => {
+                if self.is_posting || self.draft.trim().is_empty() {
+                    return Command::none();
+                }
+                self.is_posting = true;
+
+                let client = self.client.clone();
+                let text = self.draft.trim().to_string();
+
+                Command::perform(
+                    async move {
+                        client
+                            .post_text_activity(text)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::Posted,
+                )
+            }
+            Message::Posted(result) => {
+                self.is_posting = false;
+
+                match result {
+                    Ok(_) => {
+                        self.draft.clear();
+                        // Show the new post at the top
+                        self.update(Message::Load)
+                    }
+                    Err(e) => {
+                        self.error = Some(format!("Failed to post: {}", e));
+                        Command::none()
+                    }
+                }
+            }
+            Message::Card(CardMessage::ToggleLike(id)) => {
+                self.toggle_like(LikeTarget::Activity(id))
+            }
+            Message::Card(CardMessage::RepliesSelected(id)) => {
+                if self.threads.remove(&id).is_some() {
+                    return Command::none();
+                }
+                self.load_replies(id)
+            }
+            // Propagate card clicks up to the parent
+            Message::Card(CardMessage::UserSelected(name)) => {
+                Command::perform(async move { name }, Message::UserSelected)
+            }
+            Message::Card(CardMessage::MediaSelected(id, media_type)) => {
+                Command::perform(async move { (id, media_type) }, |(id, media_type)| {
+                    Message::MediaSelected(id, media_type)
+                })
+            }
+            Message::RepliesLoaded(activity_id, result) => {
+                // The thread was closed while loading
+                let Some(thread) = self.threads.get_mut(&activity_id) else {
+                    return Command::none();
+                };
+                thread.is_loading = false;
+
+                match result {
+                    Ok(replies) => thread.replies = replies,
+                    Err(e) => self.error = Some(format!("Failed to load replies: {}", e)),
+                }
+
+                Command::none()
+            }
+            Message::ReplyDraftChanged(activity_id, draft) => {
+                if let Some(thread) = self.threads.get_mut(&activity_id) {
+                    thread.draft = draft;
+                }
+                Command::none()
+            }
+            Message::SendReply(activity_id) => {
+                let Some(thread) = self.threads.get_mut(&activity_id) else {
+                    return Command::none();
+                };
+                if thread.is_sending || thread.draft.trim().is_empty() {
+                    return Command::none();
+                }
+                thread.is_sending = true;
+
+                let client = self.client.clone();
+                let text = thread.draft.trim().to_string();
+
+                Command::perform(
+                    async move {
+                        client
+                            .reply_to_activity(activity_id, text)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    move |result| Message::ReplySent(activity_id, result),
+                )
+            }
+            Message::ReplySent(activity_id, result) => {
+                match result {
+                    Ok(reply) => {
+                        if let Some(thread) = self.threads.get_mut(&activity_id) {
+                            thread.is_sending = false;
+                            thread.draft.clear();
+                            thread.replies.push(reply);
+                        }
+                        if let Some(activity) =
+                            self.activities.iter_mut().find(|a| a.id == activity_id)
+                        {
+                            activity.reply_count += 1;
+                        }
+                    }
+                    Err(e) => {
+                        if let Some(thread) = self.threads.get_mut(&activity_id) {
+                            thread.is_sending = false;
+                        }
+                        self.error = Some(format!("Failed to reply: {}", e));
+                    }
+                }
+
+                Command::none()
+            }
+            Message::LikeReply(_, reply_id) => {
+                self.toggle_like(LikeTarget::ActivityReply(reply_id))
+            }
+            Message::Liked(target, result) => {
+                match (target, result) {
+                    (LikeTarget::Activity(id), Ok((is_liked, like_count))) => {
+                        if let Some(activity) = self.activities.iter_mut().find(|a| a.id == id) {
+                            activity.is_liked = is_liked;
+                            activity.like_count = like_count;
+                        }
+                    }
+                    (LikeTarget::ActivityReply(id), Ok((is_liked, like_count))) => {
+                        let reply = self
+                            .threads
+                            .values_mut()
+                            .flat_map(|thread| thread.replies.iter_mut())
+                            .find(|reply| reply.id == id);
+                        if let Some(reply) = reply {
+                            reply.is_liked = is_liked;
+                            reply.like_count = like_count;
+                        }
+                    }
+                    (_, Err(e)) => self.error = Some(format!("Failed to like: {}", e)),
+                }
+
+                Command::none()
+            }
+            Message::UserSelected(_) | Message::MediaSelected(_, _) => {
+                // Handled by the parent component
+                Command::none()
+            }
+        }
+    }
+
+    fn fetch(&self) -> Command<Message> {
+        let client = self.client.clone();
+        let query = ActivityQuery {
+            id: self.focus,
+            // A single activity is shown whoever posted it
+            following_only: self.following_only && self.focus.is_none(),
+            kind: self.kind,
+            ..Default::default()
+        };
+        let page = self.page;
+        let generation = self.generation;
+
+        Command::perform(
+            async move {
+                if query.following_only && !client.is_authenticated().await {
+                    return Err("Log in to see activity from people you follow".to_string());
+                }
+
+                client
+                    .get_activities(&query, Some(page), Some(PER_PAGE))
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            move |result| Message::ResultsReceived(generation, result),
+        )
+    }
+
+    fn load_replies(&mut self, activity_id: i32) -> Command<Message> {
+        self.threads.insert(
+            activity_id,
+            ReplyThread {
+                is_loading: true,
+                ..Default::default()
+            },
+        );
+
+        let client = self.client.clone();
+        Command::perform(
+            async move {
+                client
+                    .get_activity_replies(activity_id, Some(1), Some(REPLIES_PER_PAGE))
+                    .await
+                    .map(|(replies, _)| replies)
+                    .map_err(|e| e.to_string())
+            },
+            move |result| Message::RepliesLoaded(activity_id, result),
+        )
+    }
+
+    fn toggle_like(&self, target: LikeTarget) -> Command<Message> {
+        let client = self.client.clone();
+        Command::perform(
+            async move { client.toggle_like(target).await.map_err(|e| e.to_string()) },
+            move |result| Message::Liked(target, result),
+        )
+    }
+
+    fn thread_view(&self, activity_id: i32, thread: &ReplyThread) -> Element<'_, Message> {
+        let now = Utc::now().timestamp();
+        let mut replies = column![].spacing(10).padding([0, 0, 0, 30]);
+
+        if thread.is_loading {
+            replies = replies.push(text("Loading replies...").size(14));
+        }
+
+        for reply in &thread.replies {
+            replies = replies.push(
+                column![
+                    row![
+                        button(text(&reply.user).size(14))
+                            .on_press(Message::UserSelected(reply.user.clone()))
+                            .style(iced::theme::Button::Text)
+                            .padding(0),
+                        text(time_ago(reply.created_at, now)).size(12),
+                        button(
+                            text(format!(
+                                "{} {}",
+                                if reply.is_liked { "♥" } else { "♡" },
+                                reply.like_count
+                            ))
+                            .size(12)
+                        )
+                        .on_press(Message::LikeReply(activity_id, reply.id))
+                        .style(iced::theme::Button::Text)
+                        .padding(0),
+                    ]
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+                    text(&reply.text).size(14),
+                ]
+                .spacing(4),
+            );
+        }
+
+        replies
+            .push(
+                row![
+                    text_input("Write a reply...", &thread.draft)
+                        .on_input(move |draft| Message::ReplyDraftChanged(activity_id, draft))
+                        .on_submit(Message::SendReply(activity_id))
+                        .padding(5),
+                    button(text(if thread.is_sending {
+                        "Sending..."
+                    } else {
+                        "Reply"
+                    }))
+                    .on_press(Message::SendReply(activity_id))
+                    .padding(5),
+                ]
+                .spacing(10),
+            )
+            .into()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let toggle = |label: &'static str, selected: bool, message: Message| {
+            button(text(label).size(14))
+                .on_press(message)
+                .padding(5)
+                .style(if selected {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                })
+        };
+
+        let mut content = column![].spacing(20).padding(20);
+
+        if self.focus.is_some() {
+            content = content.push(text("Activity").size(28));
+        } else {
+            let feeds = row![
+                toggle(
+                    "Following",
+                    self.following_only,
+                    Message::FollowingOnly(true)
+                ),
+                toggle(
+                    "Global",
+                    !self.following_only,
+                    Message::FollowingOnly(false)
+                ),
+                text("|").size(14),
+                toggle("All", self.kind.is_none(), Message::KindSelected(None)),
+                toggle(
+                    "Status",
+                    self.kind == Some(ActivityKind::Text),
+                    Message::KindSelected(Some(ActivityKind::Text))
+                ),
+                toggle(
+                    "List",
+                    self.kind == Some(ActivityKind::List),
+                    Message::KindSelected(Some(ActivityKind::List))
+                ),
+                button(text("Refresh").size(14))
+                    .on_press(Message::Load)
+                    .padding(5),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center);
+
+            let composer = row![
+                text_input("Write a status...", &self.draft)
+                    .on_input(Message::DraftChanged)
+                    .on_submit(Message::Post)
+                    .padding(10),
+                button(text(if self.is_posting {
+                    "Posting..."
+                } else {
+                    "Post"
+                }))
+                .on_press(Message::Post)
+                .padding(10),
+            ]
+            .spacing(10);
+
+            content = content
+                .push(text("Activity").size(28))
+                .push(feeds)
+                .push(composer);
+        }
+
+        if let Some(error) = &self.error {
+            content = content.push(text(error).size(16).style(iced::theme::Text::Color(
+                iced::Color::from_rgb(0.8, 0.2, 0.2),
+            )));
+        }
+
+        if self.is_loading && self.activities.is_empty() {
+            content = content.push(text("Loading activity...").size(18));
+        } else if self.activities.is_empty() && self.error.is_none() {
+            content = content.push(text("Nothing here yet").size(18));
+        }
+
+        for activity in &self.activities {
+            content = content.push(
+                ActivityCard::new(activity)
+                    .title_language(self.title_language)
+                    .view()
+                    .map(Message::Card),
+            );
+            if let Some(thread) = self.threads.get(&activity.id) {
+                content = content.push(self.thread_view(activity.id, thread));
+            }
+        }
+
+        if self.has_next_page && self.focus.is_none() {
+            content = content.push(
+                button(text(if self.is_loading {
+                    "Loading..."
+                } else {
+                    "Load more"
+                }))
+                .on_press(Message::LoadMore)
+                .padding(10),
+            );
+        }
+
+        scrollable(content).height(Length::Fill).into()
+    }
+}
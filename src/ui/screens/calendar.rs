@@ -0,0 +1,390 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use iced::widget::{button, column, container, row, scrollable, text};
+use iced::{Alignment, Command, Element, Length};
+
+use crate::api::client::{user_anime_list, AniListClient};
+use crate::api::models::{MediaTitle, MediaType, TitleLanguage};
+use crate::utils::text::countdown;
+
+// Global schedules are fetched page by page; a busy week is a few hundred episodes
+const MAX_SCHEDULE_PAGES: i32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarMode {
+    // Next episode of every show on the viewer's CURRENT list
+    MyShows,
+    // Everything airing this week
+    AllAiring,
+}
+
+// An episode with a known airing time
+#[derive(Debug, Clone)]
+pub struct AiringEntry {
+    pub media_id: i32,
+    pub title: MediaTitle,
+    pub episode: i32,
+    pub total_episodes: Option<i32>,
+    pub airing_at: i64, // Unix timestamp
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Load,
+    ModeSelected(CalendarMode),
+    ScheduleLoaded(CalendarMode, Result<Vec<AiringEntry>, String>),
+    AnimeSelected(i32),
+}
+
+pub struct CalendarScreen {
+    client: AniListClient,
+    mode: CalendarMode,
+    title_language: TitleLanguage,
+    my_shows: Vec<AiringEntry>,
+    all_airing: Vec<AiringEntry>,
+    // First day shown, the global schedule is reloaded when it changes
+    week_start: NaiveDate,
+    now: i64,
+    is_loading: bool,
+    error: Option<String>,
+}
+
+impl CalendarScreen {
+    pub fn new(client: AniListClient) -> Self {
+        Self {
+            client,
+            mode: CalendarMode::MyShows,
+            title_language: TitleLanguage::default(),
+            my_shows: Vec::new(),
+            all_airing: Vec::new(),
+            week_start: Local::now().date_naive(),
+            now: Utc::now().timestamp(),
+            is_loading: false,
+            error: None,
+        }
+    }
+
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    pub fn init(&mut self) -> Command<Message> {
+        if self.current_entries().is_empty() && !self.is_loading {
+            self.update(Message::Load)
+        } else {
+            Command::none()
+        }
+    }
+
+    /// Refresh countdowns; reload once an episode has aired or the day changed
+    pub fn tick(&mut self) -> Command<Message> {
+        self.now = Utc::now().timestamp();
+        let today = Local::now().date_naive();
+
+        let stale = match self.mode {
+            // The next episode of an aired show is only known after a refetch
+            CalendarMode::MyShows => self.my_shows.iter().any(|e| e.airing_at <= self.now),
+            CalendarMode::AllAiring => today != self.week_start && !self.all_airing.is_empty(),
+        };
+
+        if stale && !self.is_loading {
+            self.update(Message::Load)
+        } else {
+            Command::none()
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Load => {
+                self.is_loading = true;
+                self.error = None;
+                self.now = Utc::now().timestamp();
+                self.week_start = Local::now().date_naive();
+
+                let client = self.client.clone();
+                let mode = self.mode;
+                let week_start = self.week_start;
+
+                Command::perform(
+                    async move {
+                        match mode {
+                            CalendarMode::MyShows => Self::load_my_shows(client).await,
+                            CalendarMode::AllAiring => {
+                                Self::load_all_airing(client, week_start).await
+                            }
+                        }
+                    },
+                    move |result| Message::ScheduleLoaded(mode, result),
+                )
+            }
+            Message::ModeSelected(mode) => {
+                self.mode = mode;
+
+                if self.current_entries().is_empty() {
+                    self.update(Message::Load)
+                } else {
+                    Command::none()
+                }
+            }
+            Message::ScheduleLoaded(mode, result) => {
+                self.is_loading = false;
+
+                match result {
+                    Ok(mut entries) => {
+                        entries.sort_by_key(|e| e.airing_at);
+                        match mode {
+                            CalendarMode::MyShows => self.my_shows = entries,
+                            CalendarMode::AllAiring => self.all_airing = entries,
+                        }
+                    }
+                    Err(e) => self.error = Some(format!("Failed to load schedule: {}", e)),
+                }
+
+                Command::none()
+            }
+            Message::AnimeSelected(_) => {
+                // This will be handled by the parent component
+                Command::none()
+            }
+        }
+    }
+
+    fn current_entries(&self) -> &[AiringEntry] {
+        match self.mode {
+            CalendarMode::MyShows => &self.my_shows,
+            CalendarMode::AllAiring => &self.all_airing,
+        }
+    }
+
+    async fn load_my_shows(client: AniListClient) -> Result<Vec<AiringEntry>, String> {
+        let viewer = client
+            .get_viewer()
+            .await
+            .map_err(|e| e.to_string())?
+            .viewer
+            .ok_or_else(|| "Could not get user information".to_string())?;
+
+        let data = client
+            .get_user_media_list(
+                viewer.id as i32,
+                MediaType::ANIME,
+                Some(user_anime_list::MediaListStatus::CURRENT),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let entries = data
+            .media_list_collection
+            .and_then(|collection| collection.lists)
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .flat_map(|list| list.entries.unwrap_or_default())
+            .flatten()
+            .filter_map(|entry| {
+                let media = entry.media?;
+                let next = media.next_airing_episode?;
+
+                Some(AiringEntry {
+                    media_id: media.id as i32,
+                    title: media.title.map(MediaTitle::from).unwrap_or_default(),
+                    episode: next.episode as i32,
+                    total_episodes: media.episodes.map(|e| e as i32),
+                    airing_at: next.airing_at,
+                })
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    async fn load_all_airing(
+        client: AniListClient,
+        week_start: NaiveDate,
+    ) -> Result<Vec<AiringEntry>, String> {
+        let start = local_midnight(week_start).timestamp();
+        let end = start + Duration::days(7).num_seconds();
+        let mut entries = Vec::new();
+
+        for page in 1..=MAX_SCHEDULE_PAGES {
+            let data = client
+                .get_airing_schedule(start, end, Some(page), Some(50))
+                .await
+                .map_err(|e| e.to_string())?;
+
+            let Some(page_data) = data.page else {
+                break;
+            };
+
+            entries.extend(
+                page_data
+                    .airing_schedules
+                    .unwrap_or_default()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|schedule| {
+                        let media = schedule.media?;
+                        if media.is_adult.unwrap_or(false) {
+                            return None;
+                        }
+
+                        Some(AiringEntry {
+                            media_id: schedule.media_id as i32,
+                            title: media.title.map(MediaTitle::from).unwrap_or_default(),
+                            episode: schedule.episode as i32,
+                            total_episodes: media.episodes.map(|e| e as i32),
+                            airing_at: schedule.airing_at,
+                        })
+                    }),
+            );
+
+            let has_next_page = page_data
+                .page_info
+                .and_then(|info| info.has_next_page)
+                .unwrap_or(false);
+            if !has_next_page {
+                break;
+            }
+        }
+
+        Ok(entries)
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let mode_button = |label: &'static str, mode: CalendarMode| {
+            button(text(label))
+                .on_press(Message::ModeSelected(mode))
+                .padding(10)
+                .style(if self.mode == mode {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                })
+        };
+
+        let header = row![
+            text("Airing Schedule").size(30),
+            mode_button("My Shows", CalendarMode::MyShows),
+            mode_button("All Airing", CalendarMode::AllAiring),
+            button(text("Refresh")).on_press(Message::Load).padding(10),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let entries = self.current_entries();
+
+        let body: Element<Message> = if self.is_loading && entries.is_empty() {
+            text("Loading schedule...").size(18).into()
+        } else if entries.is_empty() {
+            text(match self.mode {
+                CalendarMode::MyShows => {
+                    "None of the shows you are watching have an upcoming episode"
+                }
+                CalendarMode::AllAiring => "Nothing is airing this week",
+            })
+            .size(18)
+            .into()
+        } else {
+            // One column per day, starting today
+            let mut week = row![].spacing(10);
+            for offset in 0..7 {
+                let day = self.week_start + Duration::days(offset);
+                let mut day_column = column![text(day.format("%A %d %b").to_string()).size(16)]
+                    .spacing(8)
+                    .width(Length::FillPortion(1));
+
+                for entry in entries
+                    .iter()
+                    .filter(|e| local_date(e.airing_at) == Some(day))
+                {
+                    day_column = day_column.push(self.entry_view(entry));
+                }
+
+                week = week.push(day_column);
+            }
+
+            // Shows whose next episode is more than a week away
+            let later: Vec<&AiringEntry> = entries
+                .iter()
+                .filter(|e| {
+                    local_date(e.airing_at)
+                        .is_some_and(|d| d >= self.week_start + Duration::days(7))
+                })
+                .collect();
+
+            let mut content = column![week].spacing(20);
+            if !later.is_empty() {
+                let mut later_column = column![text("Later").size(16)].spacing(8);
+                for entry in later {
+                    later_column = later_column.push(self.entry_view(entry));
+                }
+                content = content.push(later_column);
+            }
+
+            content.into()
+        };
+
+        // Error message if any
+        let error_display: Element<Message> = if let Some(error) = &self.error {
+            text(error)
+                .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                    0.8, 0.2, 0.2,
+                )))
+                .size(16)
+                .into()
+        } else {
+            container(text("")).into()
+        };
+
+        scrollable(
+            column![header, error_display, body]
+                .spacing(20)
+                .padding(20)
+                .width(Length::Fill),
+        )
+        .height(Length::Fill)
+        .into()
+    }
+
+    fn entry_view<'a>(&self, entry: &AiringEntry) -> Element<'a, Message> {
+        let time = Local
+            .timestamp_opt(entry.airing_at, 0)
+            .single()
+            .map_or_else(String::new, |t| t.format("%H:%M").to_string());
+        let episode = match entry.total_episodes {
+            Some(total) => format!("Ep {}/{}", entry.episode, total),
+            None => format!("Ep {}", entry.episode),
+        };
+
+        button(
+            column![
+                text(entry.title.preferred(self.title_language)).size(14),
+                text(format!(
+                    "{} • {} • {}",
+                    time,
+                    episode,
+                    countdown(entry.airing_at - self.now)
+                ))
+                .size(12),
+            ]
+            .spacing(2),
+        )
+        .on_press(Message::AnimeSelected(entry.media_id))
+        .style(iced::theme::Button::Secondary)
+        .width(Length::Fill)
+        .into()
+    }
+}
+
+fn local_date(timestamp: i64) -> Option<NaiveDate> {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|t| t.date_naive())
+}
+
+fn local_midnight(date: NaiveDate) -> DateTime<Local> {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+        .unwrap_or_else(Local::now)
+}
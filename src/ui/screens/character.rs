@@ -0,0 +1,401 @@
+use iced::widget::{button, column, container, row, scrollable, text};
+use iced::{Alignment, Command, Element, Length};
+
+use crate::api::client::{character_details, AniListClient};
+use crate::api::models::{FuzzyDate, MediaTitle, MediaType, TitleLanguage};
+use crate::ui::components::media_credits::{
+    merge_credits, MediaCredit, MediaCredits, Message as CreditsMessage,
+};
+use crate::utils::text::{long_date, plain_description};
+
+const PER_PAGE: i32 = 25;
+
+#[derive(Debug, Clone)]
+pub struct CharacterProfile {
+    pub id: i32,
+    pub name: String,
+    pub native_name: Option<String>,
+    pub alternative_names: Vec<String>,
+    pub image_url: String,
+    pub description: String,
+    pub gender: Option<String>,
+    pub age: Option<String>,
+    pub blood_type: Option<String>,
+    pub birthday: FuzzyDate,
+    pub favourites: i32,
+}
+
+// A voice actor and the language they voice the character in
+#[derive(Debug, Clone)]
+pub struct VoiceActor {
+    pub id: i32,
+    pub name: String,
+    pub language: String,
+}
+
+// One page of appearances
+#[derive(Debug, Clone)]
+pub struct AppearancePage {
+    pub appearances: Vec<MediaCredit>,
+    pub voice_actors: Vec<VoiceActor>,
+    pub has_next_page: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Loaded(
+        i32,
+        i32,
+        Box<Result<(CharacterProfile, AppearancePage), String>>,
+    ),
+    LoadMore,
+    Credits(CreditsMessage),
+    StaffSelected(i32),
+    MediaSelected(i32, MediaType),
+}
+
+pub struct CharacterScreen {
+    client: AniListClient,
+    title_language: TitleLanguage,
+    character_id: Option<i32>,
+    character: Option<CharacterProfile>,
+    appearances: Vec<MediaCredit>,
+    voice_actors: Vec<VoiceActor>,
+    page: i32,
+    has_next_page: bool,
+    is_loading: bool,
+    error: Option<String>,
+}
+
+impl CharacterScreen {
+    pub fn new(client: AniListClient) -> Self {
+        Self {
+            client,
+            title_language: TitleLanguage::default(),
+            character_id: None,
+            character: None,
+            appearances: Vec::new(),
+            voice_actors: Vec::new(),
+            page: 1,
+            has_next_page: false,
+            is_loading: false,
+            error: None,
+        }
+    }
+
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    pub fn character_id(&self) -> Option<i32> {
+        self.character_id
+    }
+
+    pub fn load(&mut self, character_id: i32) -> Command<Message> {
+        self.character_id = Some(character_id);
+        self.character = None;
+        self.appearances.clear();
+        self.voice_actors.clear();
+        self.page = 1;
+        self.has_next_page = false;
+        self.error = None;
+        self.fetch_page()
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Loaded(character_id, page, result) => {
+                // A response for a character we have since navigated away from
+                if self.character_id != Some(character_id) {
+                    return Command::none();
+                }
+                self.is_loading = false;
+
+                match *result {
+                    Ok((character, page_data)) => {
+                        self.page = page;
+                        self.has_next_page = page_data.has_next_page;
+                        merge_credits(&mut self.appearances, page_data.appearances);
+                        for actor in page_data.voice_actors {
+                            if !self.voice_actors.iter().any(|a| a.id == actor.id) {
+                                self.voice_actors.push(actor);
+                            }
+                        }
+                        self.character = Some(character);
+                    }
+                    Err(e) => self.error = Some(format!("Failed to load character: {}", e)),
+                }
+
+                Command::none()
+            }
+            Message::LoadMore => {
+                if self.is_loading || !self.has_next_page {
+                    return Command::none();
+                }
+                self.page += 1;
+                self.fetch_page()
+            }
+            // Propagate card clicks up to the parent
+            Message::Credits(CreditsMessage::MediaSelected(id, media_type)) => {
+                Command::perform(async move { (id, media_type) }, |(id, media_type)| {
+                    Message::MediaSelected(id, media_type)
+                })
+            }
+            Message::Credits(CreditsMessage::CharacterSelected(_)) => Command::none(),
+            Message::StaffSelected(_) | Message::MediaSelected(_, _) => {
+                // Handled by the parent component
+                Command::none()
+            }
+        }
+    }
+
+    fn fetch_page(&mut self) -> Command<Message> {
+        let Some(character_id) = self.character_id else {
+            return Command::none();
+        };
+        self.is_loading = true;
+
+        let client = self.client.clone();
+        let page = self.page;
+
+        Command::perform(
+            async move {
+                let character = client
+                    .get_character(character_id, Some(page), Some(PER_PAGE))
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .character
+                    .ok_or_else(|| "Character not found".to_string())?;
+
+                Ok(convert_character(character))
+            },
+            move |result| Message::Loaded(character_id, page, Box::new(result)),
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let Some(character) = &self.character else {
+            let status = match &self.error {
+                Some(error) => {
+                    text(error)
+                        .size(18)
+                        .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                            0.8, 0.2, 0.2,
+                        )))
+                }
+                None => text("Loading character...").size(18),
+            };
+            return container(status).padding(40).into();
+        };
+
+        let mut facts = column![].spacing(5);
+        if let Some(native) = &character.native_name {
+            facts = facts.push(text(native).size(18));
+        }
+        if !character.alternative_names.is_empty() {
+            facts = facts.push(
+                text(format!(
+                    "Also known as: {}",
+                    character.alternative_names.join(", ")
+                ))
+                .size(14),
+            );
+        }
+        if let Some(birthday) = long_date(&character.birthday) {
+            facts = facts.push(text(format!("Birthday: {}", birthday)).size(14));
+        }
+        if let Some(age) = &character.age {
+            facts = facts.push(text(format!("Age: {}", age)).size(14));
+        }
+        if let Some(gender) = &character.gender {
+            facts = facts.push(text(format!("Gender: {}", gender)).size(14));
+        }
+        if let Some(blood_type) = &character.blood_type {
+            facts = facts.push(text(format!("Blood type: {}", blood_type)).size(14));
+        }
+        facts = facts.push(text(format!("❤ {} favourites", character.favourites)).size(14));
+
+        let mut content = column![
+            text(&character.name).size(30),
+            row![
+                container(text("Image"))
+                    .width(Length::Fixed(150.0))
+                    .height(Length::Fixed(225.0))
+                    .center_x()
+                    .center_y()
+                    .style(iced::theme::Container::Box),
+                facts,
+            ]
+            .spacing(20),
+        ]
+        .spacing(20)
+        .padding(20);
+
+        if !character.description.is_empty() {
+            content = content.push(
+                column![
+                    text("Description").size(20),
+                    text(&character.description).size(14)
+                ]
+                .spacing(10),
+            );
+        }
+
+        // Voice actors grouped by language, in the order AniList lists them
+        if !self.voice_actors.is_empty() {
+            let mut languages: Vec<&str> = Vec::new();
+            for actor in &self.voice_actors {
+                if !languages.contains(&actor.language.as_str()) {
+                    languages.push(&actor.language);
+                }
+            }
+
+            let mut section = column![text("Voice Actors").size(20)].spacing(10);
+            for language in languages {
+                let mut actors = row![text(format!("{}:", language)).size(14)]
+                    .spacing(5)
+                    .align_items(Alignment::Center);
+                for actor in self.voice_actors.iter().filter(|a| a.language == language) {
+                    actors = actors.push(
+                        button(text(&actor.name).size(14))
+                            .on_press(Message::StaffSelected(actor.id))
+                            .style(iced::theme::Button::Secondary)
+                            .padding(5),
+                    );
+                }
+                section = section.push(actors);
+            }
+            content = content.push(section);
+        }
+
+        if !self.appearances.is_empty() {
+            content = content.push(
+                column![
+                    text("Appearances").size(20),
+                    MediaCredits::new(&self.appearances)
+                        .title_language(self.title_language)
+                        .view()
+                        .map(Message::Credits),
+                ]
+                .spacing(10),
+            );
+        }
+
+        if self.has_next_page {
+            content = content.push(
+                button(text(if self.is_loading {
+                    "Loading..."
+                } else {
+                    "Load more"
+                }))
+                .on_press(Message::LoadMore)
+                .padding(10),
+            );
+        }
+
+        if let Some(error) = &self.error {
+            content = content.push(text(error).size(16).style(iced::theme::Text::Color(
+                iced::Color::from_rgb(0.8, 0.2, 0.2),
+            )));
+        }
+
+        scrollable(content).height(Length::Fill).into()
+    }
+}
+
+fn convert_character(
+    character: character_details::CharacterDetailsCharacter,
+) -> (CharacterProfile, AppearancePage) {
+    let name = character.name.as_ref();
+    let profile = CharacterProfile {
+        id: character.id as i32,
+        name: name
+            .and_then(|n| n.full.clone())
+            .unwrap_or_else(|| "Unknown".to_string()),
+        native_name: name.and_then(|n| n.native.clone()),
+        alternative_names: name
+            .and_then(|n| n.alternative.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .filter(|alt| !alt.is_empty())
+            .collect(),
+        image_url: character
+            .image
+            .and_then(|img| img.large.or(img.medium))
+            .unwrap_or_default(),
+        description: character
+            .description
+            .as_deref()
+            .map(plain_description)
+            .unwrap_or_default(),
+        gender: character.gender,
+        age: character.age,
+        blood_type: character.blood_type,
+        birthday: character
+            .date_of_birth
+            .map(|date| FuzzyDate {
+                year: date.year.map(|y| y as i32),
+                month: date.month.map(|m| m as i32),
+                day: date.day.map(|d| d as i32),
+            })
+            .unwrap_or_default(),
+        favourites: character.favourites.unwrap_or(0) as i32,
+    };
+
+    let connection = character.media;
+    let has_next_page = connection
+        .as_ref()
+        .and_then(|c| c.page_info.as_ref())
+        .and_then(|info| info.has_next_page)
+        .unwrap_or(false);
+
+    let mut appearances = Vec::new();
+    let mut voice_actors = Vec::new();
+    for edge in connection
+        .and_then(|c| c.edges)
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+    {
+        for actor in edge.voice_actors.unwrap_or_default().into_iter().flatten() {
+            voice_actors.push(VoiceActor {
+                id: actor.id as i32,
+                name: actor
+                    .name
+                    .and_then(|n| n.full)
+                    .unwrap_or_else(|| "Unknown".to_string()),
+                language: actor.language_v2.unwrap_or_else(|| "Unknown".to_string()),
+            });
+        }
+
+        let Some(media) = edge.node else {
+            continue;
+        };
+        appearances.push(MediaCredit {
+            id: media.id as i32,
+            media_type: match media.type_ {
+                Some(character_details::MediaType::MANGA) => MediaType::MANGA,
+                _ => MediaType::ANIME,
+            },
+            title: media.title.map(MediaTitle::from).unwrap_or_default(),
+            format: media.format.map(|f| format!("{:?}", f)),
+            year: media.start_date.and_then(|d| d.year).map(|y| y as i32),
+            role: edge.character_role.map(|r| format!("{:?}", r)),
+            characters: Vec::new(),
+            list_status: media
+                .media_list_entry
+                .and_then(|entry| entry.status)
+                .map(|status| format!("{:?}", status)),
+        });
+    }
+
+    (
+        profile,
+        AppearancePage {
+            appearances,
+            voice_actors,
+            has_next_page,
+        },
+    )
+}
@@ -1,17 +1,33 @@
-use iced::widget::{button, column, container, row, scrollable, slider, text};
+use iced::widget::{
+    button, checkbox, column, container, row, scrollable, slider, text, text_input,
+};
 use iced::{Alignment, Command, Element, Length};
-
-use crate::api::client::{update_media_list, AniListClient};
+use log::debug;
+
+use crate::api::client::{anime_details, viewer, AniListClient, Json};
+use crate::api::franchise::{walk_franchise, FranchiseEntry, MAX_FRANCHISE_ENTRIES};
+use crate::api::models::{
+    FavouriteTarget, FuzzyDate, ListEntryDetails, Media, MediaTitle, MediaType, ScoreFormat,
+    TitleLanguage,
+};
+use crate::data::cache_layer::CacheLayer;
+use crate::data::database::ListUpdate;
+use crate::data::outbox::{Outbox, SaveOutcome};
+use crate::ui::components::date_picker::{self, DatePicker};
+use crate::ui::components::media_credits::{MediaCredit, MediaCredits, Message as CreditsMessage};
+use crate::ui::images::{image_or_placeholder, ImageLoader};
+use crate::utils::text::long_date;
 
 // Anime details
 #[derive(Debug, Clone)]
 pub struct AnimeDetails {
     pub id: i32,
-    pub title: String,
-    pub english_title: Option<String>,
-    pub native_title: Option<String>,
+    pub media_type: MediaType,
+    pub title: MediaTitle,
     pub description: String,
     pub episodes: Option<i32>,
+    pub chapters: Option<i32>,
+    pub volumes: Option<i32>,
     pub duration: Option<i32>,
     pub genres: Vec<String>,
     pub score: f32,
@@ -21,8 +37,27 @@ pub struct AnimeDetails {
     pub year: Option<i32>,
     pub cover_image: String,
     pub banner_image: Option<String>,
-    pub studios: Vec<String>,
+    pub studios: Vec<StudioPreview>,
+    pub staff_previews: Vec<StaffPreview>,
     pub character_previews: Vec<CharacterPreview>,
+    // Related media grouped by relation type, in the order AniList lists them
+    pub relations: Vec<(String, Vec<MediaCredit>)>,
+    // Community recommendations, highest rated first
+    pub recommendations: Vec<MediaCredit>,
+    pub is_favourite: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct StudioPreview {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct StaffPreview {
+    pub id: i32,
+    pub name: String,
+    pub role: String,
 }
 
 #[derive(Debug, Clone)]
@@ -37,60 +72,193 @@ pub struct CharacterPreview {
 pub struct UserProgress {
     pub list_entry_id: Option<i32>,
     pub status: String,
-    pub score: f32,
+    pub score: f32, // 0-100, shown in the viewer's ScoreFormat
     pub progress: i32,
     pub max_progress: Option<i32>,
+    pub progress_volumes: i32,
+    pub max_volumes: Option<i32>,
+    pub notes: String,
+    pub started_at: FuzzyDate,
+    pub completed_at: FuzzyDate,
+    pub repeat: i32,
+    pub private: bool,
+    pub hidden_from_status_lists: bool,
+    // Every custom list of this media type and whether the entry is in it
+    pub custom_lists: Vec<(String, bool)>,
+    // Score per advanced scoring category, empty when the feature is off
+    pub advanced_scores: Vec<(String, f32)>,
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     LoadAnimeDetails(i32),
-    AnimeDetailsLoaded(Result<AnimeDetails, String>),
+    AnimeDetailsLoaded(Box<Result<AnimeDetails, String>>),
+    AnimeDetailsRefreshed(Box<Result<AnimeDetails, String>>),
     UserProgressLoaded(Result<UserProgress, String>),
     StatusChanged(String),
+    // A score in the viewer's format
     ScoreChanged(f32),
     ProgressChanged(i32),
+    VolumesChanged(i32),
+    NotesChanged(String),
+    StartedAtPicker(date_picker::Message),
+    CompletedAtPicker(date_picker::Message),
+    RepeatChanged(i32),
+    PrivateToggled(bool),
+    HiddenFromStatusListsToggled(bool),
+    CustomListToggled(String, bool),
+    AdvancedScoreChanged(usize, f32),
     SaveProgress,
-    ProgressSaved(Result<(), String>),
+    ProgressSaved(Result<SaveOutcome, String>),
+    ToggleFavourite,
+    FavouriteToggled(Result<bool, String>),
+    RemoveFromList,
+    ConfirmRemove,
+    CancelRemove,
+    RemovedFromList(Result<(), String>),
+    ImageLoaded(Result<String, String>),
+    ToggleWatchOrder,
+    WatchOrderLoaded(i32, Result<Vec<FranchiseEntry>, String>),
+    Credits(CreditsMessage),
+    CharacterSelected(i32),
+    StaffSelected(i32),
+    StudioSelected(i32),
+    MediaSelected(i32, MediaType),
     Error(String),
 }
 
 pub struct DetailsScreen {
     client: AniListClient,
+    cache: CacheLayer,
+    outbox: Outbox,
+    images: ImageLoader,
+    offline_mode: bool,
+    score_format: ScoreFormat,
+    title_language: TitleLanguage,
+    show_adult: bool,
     anime_id: Option<i32>,
+    media_type: MediaType,
     anime: Option<AnimeDetails>,
+    // What the cache knows about the media, shown while its details load
+    preview: Option<Media>,
+    // The franchise timeline, walked the first time it is shown
+    watch_order: Option<Vec<FranchiseEntry>>,
+    show_watch_order: bool,
+    is_loading_watch_order: bool,
     user_progress: Option<UserProgress>,
     is_authenticated: bool,
     is_loading: bool,
     is_saving: bool,
+    // "Remove from list" was pressed and awaits confirmation
+    confirm_remove: bool,
     error: Option<String>,
+    notice: Option<String>,
     temp_status: Option<String>,
     temp_score: Option<f32>,
     temp_progress: Option<i32>,
+    temp_progress_volumes: Option<i32>,
+    temp_notes: String,
+    temp_repeat: i32,
+    temp_private: bool,
+    temp_hidden_from_status_lists: bool,
+    temp_custom_lists: Vec<(String, bool)>,
+    temp_advanced_scores: Vec<(String, f32)>,
+    started_at_picker: DatePicker,
+    completed_at_picker: DatePicker,
 }
 
 impl DetailsScreen {
-    pub fn new(client: AniListClient) -> Self {
+    pub fn new(
+        client: AniListClient,
+        cache: CacheLayer,
+        outbox: Outbox,
+        images: ImageLoader,
+    ) -> Self {
         Self {
             client,
+            cache,
+            outbox,
+            images,
+            offline_mode: false,
+            score_format: ScoreFormat::default(),
+            title_language: TitleLanguage::default(),
+            show_adult: false,
             anime_id: None,
+            media_type: MediaType::ANIME,
             anime: None,
+            preview: None,
+            watch_order: None,
+            show_watch_order: false,
+            is_loading_watch_order: false,
             user_progress: None,
             is_authenticated: false,
             is_loading: false,
             is_saving: false,
+            confirm_remove: false,
             error: None,
+            notice: None,
             temp_status: None,
             temp_score: None,
             temp_progress: None,
+            temp_progress_volumes: None,
+            temp_notes: String::new(),
+            temp_repeat: 0,
+            temp_private: false,
+            temp_hidden_from_status_lists: false,
+            temp_custom_lists: Vec::new(),
+            temp_advanced_scores: Vec::new(),
+            started_at_picker: DatePicker::new("Started"),
+            completed_at_picker: DatePicker::new("Completed"),
         }
     }
 
+    // In offline mode edits go straight to the outbox
+    pub fn set_offline_mode(&mut self, offline_mode: bool) {
+        self.offline_mode = offline_mode;
+    }
+
+    pub fn set_score_format(&mut self, score_format: ScoreFormat) {
+        self.score_format = score_format;
+    }
+
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    // Adult relations and recommendations are hidden unless the viewer opted in
+    pub fn set_show_adult(&mut self, show_adult: bool) {
+        self.show_adult = show_adult;
+    }
+
+    pub fn media_id(&self) -> Option<i32> {
+        self.anime_id
+    }
+
     pub fn load(&mut self, anime_id: i32) -> Command<Message> {
+        self.load_media(anime_id, MediaType::ANIME)
+    }
+
+    pub fn load_manga(&mut self, manga_id: i32) -> Command<Message> {
+        self.load_media(manga_id, MediaType::MANGA)
+    }
+
+    fn load_media(&mut self, anime_id: i32, media_type: MediaType) -> Command<Message> {
         self.anime_id = Some(anime_id);
+        self.media_type = media_type;
         self.is_loading = true;
         self.error = None;
+        self.notice = None;
         self.anime = None;
+        self.preview = self
+            .cache
+            .peek_media(anime_id, media_type)
+            .ok()
+            .flatten()
+            .map(|cached| cached.value);
+        self.watch_order = None;
+        self.show_watch_order = false;
+        self.is_loading_watch_order = false;
+        self.confirm_remove = false;
 
         Command::perform(async move { anime_id }, Message::LoadAnimeDetails)
     }
@@ -98,142 +266,23 @@ impl DetailsScreen {
     pub fn update(&mut self, message: Message) -> Command<Message> {
         match message {
             Message::LoadAnimeDetails(id) => {
-                // Create two separate client instances for each async operation
-                let client1 = self.client.clone();
+                // Details go through the cache so the basics are kept for next time
+                let cache = self.cache.clone();
                 let client2 = self.client.clone();
+                let media_type = self.media_type;
+                let show_adult = self.show_adult;
 
-                Command::batch(vec![
+                let mut commands = vec![
                     // Load anime details
                     Command::perform(
                         async move {
-                            match client1.get_anime_details(id).await {
-                                Ok(data) => {
-                                    if let Some(media) = data.media {
-                                        // Convert to our model
-                                        let title = media
-                                            .title
-                                            .as_ref()
-                                            .and_then(|t| t.romaji.as_ref())
-                                            .unwrap_or(&"Unknown Title".to_string())
-                                            .clone();
-
-                                        let english_title =
-                                            media.title.as_ref().and_then(|t| t.english.clone());
-
-                                        let native_title =
-                                            media.title.as_ref().and_then(|t| t.native.clone());
-
-                                        let description = media
-                                            .description
-                                            .unwrap_or_default()
-                                            .replace("<br>", "\n")
-                                            .replace("<i>", "")
-                                            .replace("</i>", "");
-
-                                        let cover_image = media
-                                            .cover_image
-                                            .as_ref()
-                                            .and_then(|img| img.large.clone())
-                                            .unwrap_or_default();
-
-                                        let genres = media
-                                            .genres
-                                            .unwrap_or_default()
-                                            .into_iter()
-                                            .filter_map(|g| g)
-                                            .collect();
-
-                                        // Extract studio names
-                                        let studios = if let Some(studio_conn) = media.studios {
-                                            studio_conn
-                                                .edges
-                                                .unwrap_or_default()
-                                                .into_iter()
-                                                .filter_map(|edge| {
-                                                    edge?
-                                                        .node
-                                                        .as_ref()
-                                                        .map(|node| node.name.clone())
-                                                })
-                                                .collect()
-                                        } else {
-                                            Vec::new()
-                                        };
-
-                                        // Extract character previews
-                                        let character_previews =
-                                            if let Some(char_conn) = media.characters {
-                                                char_conn
-                                                    .edges
-                                                    .unwrap_or_default()
-                                                    .into_iter()
-                                                    .filter_map(|edge| {
-                                                        let node = edge.as_ref()?.node.as_ref()?;
-                                                        let role = edge.as_ref()?.role.as_ref()?;
-
-                                                        Some(CharacterPreview {
-                                                            id: node.id as i32,
-                                                            name: node
-                                                                .name
-                                                                .as_ref()
-                                                                .and_then(|n| n.full.clone())
-                                                                .unwrap_or_default(),
-                                                            image_url: node
-                                                                .image
-                                                                .as_ref()
-                                                                .and_then(|img| img.medium.clone())
-                                                                .unwrap_or_default(),
-                                                            role: format!("{:?}", role), // Convert enum to string
-                                                        })
-                                                    })
-                                                    .collect()
-                                            } else {
-                                                Vec::new()
-                                            };
-
-                                        // Convert status and format enums to strings
-                                        let status_str = media.status.map_or_else(
-                                            || "Unknown".to_string(),
-                                            |s| format!("{:?}", s),
-                                        );
-
-                                        let format_str = media.format.map_or_else(
-                                            || "Unknown".to_string(),
-                                            |f| format!("{:?}", f),
-                                        );
-
-                                        // Convert season enum to string
-                                        let season_str = media.season.map(|s| format!("{:?}", s));
-
-                                        let details = AnimeDetails {
-                                            id: media.id as i32,
-                                            title,
-                                            english_title,
-                                            native_title,
-                                            description,
-                                            episodes: media.episodes.map(|e| e as i32),
-                                            duration: media.duration.map(|d| d as i32),
-                                            genres,
-                                            score: media.average_score.unwrap_or(0) as f32 / 10.0,
-                                            status: status_str,
-                                            format: format_str,
-                                            season: season_str,
-                                            year: media.season_year.map(|y| y as i32),
-                                            cover_image,
-                                            banner_image: media.banner_image,
-                                            studios,
-                                            character_previews,
-                                        };
-
-                                        Ok(details)
-                                    } else {
-                                        Err("Anime not found".to_string())
-                                    }
-                                }
-                                Err(e) => Err(e.to_string()),
-                            }
+                            let cached = cache
+                                .media_details(id, media_type)
+                                .await
+                                .map_err(|e| e.to_string())?;
+                            details_from_response(cached.value, media_type, show_adult)
                         },
-                        Message::AnimeDetailsLoaded,
+                        |result| Message::AnimeDetailsLoaded(Box::new(result)),
                     ),
                     // Check authentication and load user progress if authenticated
                     Command::perform(
@@ -247,43 +296,68 @@ impl DetailsScreen {
                                     Ok(viewer_data) => {
                                         if let Some(viewer) = viewer_data.viewer {
                                             let user_id = viewer.id as i32;
+                                            let (list_names, scoring) =
+                                                list_options(&viewer, media_type);
 
                                             // Get user's anime list to find this specific entry
                                             match client_clone
-                                                .get_user_anime_list(user_id, None)
+                                                .get_user_media_list(user_id, media_type, None)
                                                 .await
                                             {
                                                 Ok(list_data) => {
                                                     if let Some(collection) =
                                                         list_data.media_list_collection
                                                     {
-                                                        for list_opt in
-                                                            collection.lists.unwrap_or_default()
+                                                        for list in collection
+                                                            .lists
+                                                            .unwrap_or_default()
+                                                            .into_iter()
+                                                            .flatten()
                                                         {
-                                                            if let Some(list) = list_opt {
-                                                                if let Some(entries) = list.entries
+                                                            if let Some(entries) = list.entries {
+                                                                for entry in
+                                                                    entries.into_iter().flatten()
                                                                 {
-                                                                    for entry in entries {
-                                                                        if let Some(entry) = entry {
-                                                                            if entry.media_id as i32
-                                                                                == id
-                                                                            {
-                                                                                // Found the entry
-                                                                                let progress = UserProgress {
+                                                                    if entry.media_id as i32 == id {
+                                                                        // Found the entry
+                                                                        let progress = UserProgress {
                                                                                     list_entry_id: Some(entry.id as i32),
                                                                                     status: entry.status.map_or("PLANNING".to_string(), |s| format!("{:?}", s)),
                                                                                     score: entry.score.unwrap_or(0.0) as f32,
                                                                                     progress: entry.progress.unwrap_or(0) as i32,
                                                                                     max_progress: entry.media.as_ref()
-                                                                                        .and_then(|m| m.episodes)
+                                                                                        .and_then(|m| match media_type {
+                                                                                            MediaType::ANIME => m.episodes,
+                                                                                            MediaType::MANGA => m.chapters,
+                                                                                        })
                                                                                         .map(|e| e as i32),
+                                                                                    progress_volumes: entry.progress_volumes.unwrap_or(0) as i32,
+                                                                                    max_volumes: entry.media.as_ref()
+                                                                                        .and_then(|m| m.volumes)
+                                                                                        .map(|v| v as i32),
+                                                                                    notes: entry.notes.unwrap_or_default(),
+                                                                                    started_at: entry.started_at
+                                                                                        .map(|d| fuzzy_date(d.year, d.month, d.day))
+                                                                                        .unwrap_or_default(),
+                                                                                    completed_at: entry.completed_at
+                                                                                        .map(|d| fuzzy_date(d.year, d.month, d.day))
+                                                                                        .unwrap_or_default(),
+                                                                                    repeat: entry.repeat.unwrap_or(0) as i32,
+                                                                                    private: entry.private.unwrap_or(false),
+                                                                                    hidden_from_status_lists: entry
+                                                                                        .hidden_from_status_lists
+                                                                                        .unwrap_or(false),
+                                                                                    custom_lists: entry_custom_lists(
+                                                                                        &list_names,
+                                                                                        entry.custom_lists.as_ref(),
+                                                                                    ),
+                                                                                    advanced_scores: entry_advanced_scores(
+                                                                                        &scoring,
+                                                                                        entry.advanced_scores.as_ref(),
+                                                                                    ),
                                                                                 };
 
-                                                                                return Ok(
-                                                                                    progress,
-                                                                                );
-                                                                            }
-                                                                        }
+                                                                        return Ok(progress);
                                                                     }
                                                                 }
                                                             }
@@ -296,6 +370,21 @@ impl DetailsScreen {
                                                             score: 0.0,
                                                             progress: 0,
                                                             max_progress: None,
+                                                            progress_volumes: 0,
+                                                            max_volumes: None,
+                                                            notes: String::new(),
+                                                            started_at: FuzzyDate::default(),
+                                                            completed_at: FuzzyDate::default(),
+                                                            repeat: 0,
+                                                            private: false,
+                                                            hidden_from_status_lists: false,
+                                                            custom_lists: entry_custom_lists(
+                                                                &list_names,
+                                                                None,
+                                                            ),
+                                                            advanced_scores: entry_advanced_scores(
+                                                                &scoring, None,
+                                                            ),
                                                         });
                                                     }
                                                 }
@@ -319,21 +408,72 @@ impl DetailsScreen {
                         },
                         Message::UserProgressLoaded,
                     ),
-                ])
+                ];
+
+                // Shown from the cache straight away, then replaced once refetched
+                let stale = self
+                    .cache
+                    .peek_media_details(id, media_type)
+                    .ok()
+                    .flatten()
+                    .is_some_and(|cached| cached.stale);
+                if stale {
+                    let cache = self.cache.clone();
+                    commands.push(Command::perform(
+                        async move {
+                            let data = cache
+                                .refresh_media_details(id, media_type)
+                                .await
+                                .map_err(|e| e.to_string())?;
+                            details_from_response(data, media_type, show_adult)
+                        },
+                        |result| Message::AnimeDetailsRefreshed(Box::new(result)),
+                    ));
+                }
+
+                Command::batch(commands)
             }
             Message::AnimeDetailsLoaded(result) => {
                 self.is_loading = false;
 
-                match result {
+                match *result {
                     Ok(details) => {
+                        let urls: Vec<String> = std::iter::once(details.cover_image.clone())
+                            .chain(details.banner_image.clone())
+                            .chain(
+                                details
+                                    .character_previews
+                                    .iter()
+                                    .map(|c| c.image_url.clone()),
+                            )
+                            .collect();
                         self.anime = Some(details);
                         self.error = None;
+                        self.images.load_all(urls, Message::ImageLoaded)
                     }
                     Err(e) => {
                         self.error = Some(format!("Failed to load anime details: {}", e));
+                        Command::none()
                     }
                 }
-
+            }
+            Message::AnimeDetailsRefreshed(result) => match *result {
+                // Ignore a refresh that lands after another media was opened
+                Ok(details) if self.anime_id == Some(details.id) => {
+                    self.update(Message::AnimeDetailsLoaded(Box::new(Ok(details))))
+                }
+                Ok(_) => Command::none(),
+                Err(e) => {
+                    // The cached copy is still on screen
+                    debug!("Failed to refresh details: {}", e);
+                    Command::none()
+                }
+            },
+            Message::ImageLoaded(result) => {
+                // The view picks loaded images up from the loader
+                if let Err(e) = result {
+                    debug!("Failed to load image: {}", e);
+                }
                 Command::none()
             }
             Message::UserProgressLoaded(result) => {
@@ -346,6 +486,17 @@ impl DetailsScreen {
                         self.temp_status = Some(progress_clone.status.clone());
                         self.temp_score = Some(progress_clone.score);
                         self.temp_progress = Some(progress_clone.progress);
+                        self.temp_progress_volumes = Some(progress_clone.progress_volumes);
+                        self.temp_notes = progress_clone.notes;
+                        self.temp_repeat = progress_clone.repeat;
+                        self.temp_private = progress_clone.private;
+                        self.temp_hidden_from_status_lists =
+                            progress_clone.hidden_from_status_lists;
+                        self.temp_custom_lists = progress_clone.custom_lists;
+                        self.temp_advanced_scores = progress_clone.advanced_scores;
+                        self.started_at_picker.set_date(progress_clone.started_at);
+                        self.completed_at_picker
+                            .set_date(progress_clone.completed_at);
                     }
                     Err(e) => {
                         if e == "Not authenticated" {
@@ -363,13 +514,53 @@ impl DetailsScreen {
                 Command::none()
             }
             Message::ScoreChanged(score) => {
-                self.temp_score = Some(score);
+                self.temp_score = Some(self.score_format.to_raw(score as f64) as f32);
                 Command::none()
             }
             Message::ProgressChanged(progress) => {
                 self.temp_progress = Some(progress);
                 Command::none()
             }
+            Message::VolumesChanged(volumes) => {
+                self.temp_progress_volumes = Some(volumes);
+                Command::none()
+            }
+            Message::NotesChanged(notes) => {
+                self.temp_notes = notes;
+                Command::none()
+            }
+            Message::StartedAtPicker(message) => {
+                self.started_at_picker.update(message);
+                Command::none()
+            }
+            Message::CompletedAtPicker(message) => {
+                self.completed_at_picker.update(message);
+                Command::none()
+            }
+            Message::RepeatChanged(repeat) => {
+                self.temp_repeat = repeat.max(0);
+                Command::none()
+            }
+            Message::PrivateToggled(private) => {
+                self.temp_private = private;
+                Command::none()
+            }
+            Message::HiddenFromStatusListsToggled(hidden) => {
+                self.temp_hidden_from_status_lists = hidden;
+                Command::none()
+            }
+            Message::CustomListToggled(name, enabled) => {
+                if let Some(list) = self.temp_custom_lists.iter_mut().find(|l| l.0 == name) {
+                    list.1 = enabled;
+                }
+                Command::none()
+            }
+            Message::AdvancedScoreChanged(index, score) => {
+                if let Some(category) = self.temp_advanced_scores.get_mut(index) {
+                    category.1 = score;
+                }
+                Command::none()
+            }
             Message::SaveProgress => {
                 if !self.is_authenticated {
                     self.error = Some("You must be logged in to save progress".to_string());
@@ -393,38 +584,34 @@ impl DetailsScreen {
 
                     // Extract and clone the required values for use in the async closure
                     let entry_id = progress.list_entry_id;
-                    let media_id = Some(anime.id);
                     let status_str = status.clone();
                     let score_val = *score;
                     let progress_value = *progress_val;
+                    // Volume progress only exists for manga
+                    let volumes_value = match self.media_type {
+                        MediaType::ANIME => None,
+                        MediaType::MANGA => self.temp_progress_volumes,
+                    };
                     let client = self.client.clone();
+                    let outbox = self.outbox.clone();
+                    let offline_mode = self.offline_mode;
+                    let update = ListUpdate {
+                        entry_id,
+                        media_id: anime.id,
+                        media_type: self.media_type,
+                        status: Some(status_str),
+                        score: Some(score_val as f64),
+                        progress: Some(progress_value),
+                        progress_volumes: volumes_value,
+                        details: self.temp_details(),
+                    };
 
                     Command::perform(
                         async move {
-                            // Convert status string to enum
-                            let status_enum = match status_str.as_str() {
-                                "CURRENT" => Some(update_media_list::MediaListStatus::CURRENT),
-                                "PLANNING" => Some(update_media_list::MediaListStatus::PLANNING),
-                                "COMPLETED" => Some(update_media_list::MediaListStatus::COMPLETED),
-                                "DROPPED" => Some(update_media_list::MediaListStatus::DROPPED),
-                                "PAUSED" => Some(update_media_list::MediaListStatus::PAUSED),
-                                "REPEATING" => Some(update_media_list::MediaListStatus::REPEATING),
-                                _ => None,
-                            };
-
-                            match client
-                                .update_media_list(
-                                    entry_id,
-                                    media_id,
-                                    status_enum,
-                                    Some(score_val as f64),
-                                    Some(progress_value),
-                                )
+                            outbox
+                                .save(&client, update, offline_mode)
                                 .await
-                            {
-                                Ok(_) => Ok(()),
-                                Err(e) => Err(e.to_string()),
-                            }
+                                .map_err(|e| e.to_string())
                         },
                         Message::ProgressSaved,
                     )
@@ -437,7 +624,15 @@ impl DetailsScreen {
                 self.is_saving = false;
 
                 match result {
-                    Ok(_) => {
+                    Ok(outcome) => {
+                        self.notice = match outcome {
+                            SaveOutcome::Saved => None,
+                            SaveOutcome::Queued => Some(
+                                "Saved offline, your changes will be synced once you're back online"
+                                    .to_string(),
+                            ),
+                        };
+
                         // Update user_progress with temporary values
                         if let Some(progress) = &mut self.user_progress {
                             if let (Some(status), Some(score), Some(progress_val)) =
@@ -447,6 +642,17 @@ impl DetailsScreen {
                                 progress.score = *score;
                                 progress.progress = *progress_val;
                             }
+                            if let Some(volumes) = self.temp_progress_volumes {
+                                progress.progress_volumes = volumes;
+                            }
+                            progress.notes = self.temp_notes.clone();
+                            progress.started_at = self.started_at_picker.date();
+                            progress.completed_at = self.completed_at_picker.date();
+                            progress.repeat = self.temp_repeat;
+                            progress.private = self.temp_private;
+                            progress.hidden_from_status_lists = self.temp_hidden_from_status_lists;
+                            progress.custom_lists = self.temp_custom_lists.clone();
+                            progress.advanced_scores = self.temp_advanced_scores.clone();
                         }
                     }
                     Err(e) => {
@@ -456,6 +662,174 @@ impl DetailsScreen {
 
                 Command::none()
             }
+            Message::ToggleFavourite => {
+                let Some(anime) = &self.anime else {
+                    return Command::none();
+                };
+
+                let client = self.client.clone();
+                let target = FavouriteTarget::media(anime.media_type, anime.id);
+                let is_favourite = anime.is_favourite;
+
+                Command::perform(
+                    async move {
+                        client
+                            .toggle_favourite(target)
+                            .await
+                            .map(|_| !is_favourite)
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::FavouriteToggled,
+                )
+            }
+            Message::FavouriteToggled(result) => {
+                match result {
+                    Ok(is_favourite) => {
+                        if let Some(anime) = &mut self.anime {
+                            anime.is_favourite = is_favourite;
+                            // The cached details still have the old state
+                            self.cache.invalidate(None, anime.id);
+                        }
+                    }
+                    Err(e) => {
+                        self.error = Some(format!("Failed to update favourites: {}", e));
+                    }
+                }
+
+                Command::none()
+            }
+            Message::RemoveFromList => {
+                self.confirm_remove = true;
+                Command::none()
+            }
+            Message::CancelRemove => {
+                self.confirm_remove = false;
+                Command::none()
+            }
+            Message::ConfirmRemove => {
+                self.confirm_remove = false;
+
+                let (Some(anime), Some(progress)) = (&self.anime, &self.user_progress) else {
+                    return Command::none();
+                };
+
+                self.is_saving = true;
+                let client = self.client.clone();
+                let outbox = self.outbox.clone();
+                let entry_id = progress.list_entry_id;
+                let media_id = anime.id;
+                let media_type = anime.media_type;
+
+                Command::perform(
+                    async move {
+                        outbox
+                            .delete(&client, entry_id, media_id, media_type)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::RemovedFromList,
+                )
+            }
+            Message::RemovedFromList(result) => {
+                self.is_saving = false;
+
+                match result {
+                    Ok(()) => {
+                        self.notice = Some("Removed from your list".to_string());
+
+                        // Start over with an empty entry
+                        if let Some(progress) = &self.user_progress {
+                            let cleared = UserProgress {
+                                list_entry_id: None,
+                                status: "PLANNING".to_string(),
+                                score: 0.0,
+                                progress: 0,
+                                progress_volumes: 0,
+                                notes: String::new(),
+                                started_at: FuzzyDate::default(),
+                                completed_at: FuzzyDate::default(),
+                                repeat: 0,
+                                private: false,
+                                hidden_from_status_lists: false,
+                                custom_lists: progress
+                                    .custom_lists
+                                    .iter()
+                                    .map(|(name, _)| (name.clone(), false))
+                                    .collect(),
+                                advanced_scores: progress
+                                    .advanced_scores
+                                    .iter()
+                                    .map(|(category, _)| (category.clone(), 0.0))
+                                    .collect(),
+                                ..progress.clone()
+                            };
+                            return self.update(Message::UserProgressLoaded(Ok(cleared)));
+                        }
+                    }
+                    Err(e) => {
+                        self.error = Some(format!("Failed to remove from list: {}", e));
+                    }
+                }
+
+                Command::none()
+            }
+            Message::ToggleWatchOrder => {
+                self.show_watch_order = !self.show_watch_order;
+                if !self.show_watch_order
+                    || self.watch_order.is_some()
+                    || self.is_loading_watch_order
+                {
+                    return Command::none();
+                }
+                let Some(media_id) = self.anime_id else {
+                    return Command::none();
+                };
+
+                self.is_loading_watch_order = true;
+                let client = self.client.clone();
+
+                Command::perform(
+                    async move {
+                        walk_franchise(&client, media_id, MAX_FRANCHISE_ENTRIES)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    move |result| Message::WatchOrderLoaded(media_id, result),
+                )
+            }
+            Message::WatchOrderLoaded(media_id, result) => {
+                // A walk for a media we have since navigated away from
+                if self.anime_id != Some(media_id) {
+                    return Command::none();
+                }
+                self.is_loading_watch_order = false;
+
+                match result {
+                    Ok(entries) => self.watch_order = Some(entries),
+                    Err(e) => {
+                        self.show_watch_order = false;
+                        self.error = Some(format!("Failed to load watch order: {}", e));
+                    }
+                }
+
+                Command::none()
+            }
+            // Propagate card clicks up to the parent
+            Message::Credits(CreditsMessage::MediaSelected(id, media_type)) => {
+                Command::perform(async move { (id, media_type) }, |(id, media_type)| {
+                    Message::MediaSelected(id, media_type)
+                })
+            }
+            Message::Credits(CreditsMessage::CharacterSelected(id)) => {
+                Command::perform(async move { id }, Message::CharacterSelected)
+            }
+            Message::CharacterSelected(_)
+            | Message::StaffSelected(_)
+            | Message::StudioSelected(_)
+            | Message::MediaSelected(_, _) => {
+                // Handled by the parent component
+                Command::none()
+            }
             Message::Error(e) => {
                 self.error = Some(e);
                 Command::none()
@@ -463,10 +837,300 @@ impl DetailsScreen {
         }
     }
 
-    pub fn view(&self) -> Element<Message> {
+    // Everything the editor sends besides status, score and progress
+    fn temp_details(&self) -> ListEntryDetails {
+        ListEntryDetails {
+            notes: Some(self.temp_notes.clone()),
+            started_at: Some(self.started_at_picker.date()),
+            completed_at: Some(self.completed_at_picker.date()),
+            repeat: Some(self.temp_repeat),
+            private: Some(self.temp_private),
+            hidden_from_status_lists: Some(self.temp_hidden_from_status_lists),
+            custom_lists: if self.temp_custom_lists.is_empty() {
+                None
+            } else {
+                Some(
+                    self.temp_custom_lists
+                        .iter()
+                        .filter(|(_, enabled)| *enabled)
+                        .map(|(name, _)| name.clone())
+                        .collect(),
+                )
+            },
+            advanced_scores: if self.temp_advanced_scores.is_empty() {
+                None
+            } else {
+                Some(
+                    self.temp_advanced_scores
+                        .iter()
+                        .map(|(_, score)| *score as f64)
+                        .collect(),
+                )
+            },
+        }
+    }
+
+    // Slider, stars or faces depending on the viewer's score format
+    fn score_editor(&self, raw: f32) -> Element<'_, Message> {
+        let format = self.score_format;
+        let score = format.from_raw(raw as f64) as f32;
+        let label = text(format!("Score: {}", format.display(raw as f64))).size(14);
+
+        let picker = |labels: &[&'static str]| {
+            let mut choices = row![].spacing(5);
+            for (i, label) in labels.iter().enumerate() {
+                let value = (i + 1) as f32;
+                let selected = match format {
+                    // Stars fill up to the score, faces are picked alone
+                    ScoreFormat::POINT_5 => value <= score,
+                    _ => value == score,
+                };
+                choices = choices.push(
+                    button(text(*label).size(20))
+                        // Picking the current score again clears it
+                        .on_press(Message::ScoreChanged(if value == score {
+                            0.0
+                        } else {
+                            value
+                        }))
+                        .padding(5)
+                        .style(if selected {
+                            iced::theme::Button::Primary
+                        } else {
+                            iced::theme::Button::Secondary
+                        }),
+                );
+            }
+            choices
+        };
+
+        let editor: Element<Message> = match format {
+            ScoreFormat::POINT_5 => picker(&["★", "★", "★", "★", "★"]).into(),
+            ScoreFormat::POINT_3 => picker(&[":(", ":|", ":)"]).into(),
+            _ => slider(0.0..=format.max() as f32, score, Message::ScoreChanged)
+                .step(format.step() as f32)
+                .into(),
+        };
+
+        column![label, editor].spacing(10).into()
+    }
+
+    fn studio_links(&self, studios: &[StudioPreview]) -> Element<'_, Message> {
+        if studios.is_empty() {
+            return text("").into();
+        }
+
+        let mut links = row![text("Studios:").size(14)]
+            .spacing(5)
+            .align_items(Alignment::Center);
+        for studio in studios {
+            links = links.push(
+                button(text(&studio.name).size(14))
+                    .on_press(Message::StudioSelected(studio.id))
+                    .style(iced::theme::Button::Text)
+                    .padding(0),
+            );
+        }
+        links.into()
+    }
+
+    // The franchise in release order, with list status on every entry
+    fn watch_order_section(&self, anime: &AnimeDetails) -> Element<'_, Message> {
+        let heading = if anime.media_type == MediaType::MANGA {
+            "Read Order"
+        } else {
+            "Watch Order"
+        };
+
+        let mut section = column![row![
+            text(heading).size(20),
+            button(text(if self.show_watch_order {
+                "Hide"
+            } else {
+                "Show"
+            }))
+            .on_press(Message::ToggleWatchOrder)
+            .style(iced::theme::Button::Secondary)
+            .padding(5),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center)]
+        .spacing(10);
+
+        if !self.show_watch_order {
+            return section.into();
+        }
+
+        let Some(entries) = &self.watch_order else {
+            return section
+                .push(text("Following sequels, prequels and side stories...").size(14))
+                .into();
+        };
+
+        for (index, entry) in entries.iter().enumerate() {
+            let title = entry.title.preferred(self.title_language);
+            let is_current = entry.id == anime.id;
+
+            let details = [
+                entry.format.clone(),
+                long_date(&entry.start_date),
+                entry.relation.as_deref().map(relation_label),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" • ");
+
+            let title_button = button(text(if is_current {
+                format!("{} (this)", title)
+            } else {
+                title
+            }))
+            .style(iced::theme::Button::Text)
+            .padding(0);
+
+            let mut line = row![
+                text(format!("{}.", index + 1))
+                    .size(14)
+                    .width(Length::Fixed(30.0)),
+                // No need to link back to the page we're on
+                if is_current {
+                    title_button
+                } else {
+                    title_button.on_press(Message::MediaSelected(entry.id, entry.media_type))
+                },
+                text(details).size(12),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+            line =
+                line.push(match &entry.list_status {
+                    Some(status) => text(format!("On list: {}", status)).size(12).style(
+                        iced::theme::Text::Color(iced::Color::from_rgb(0.2, 0.7, 0.3)),
+                    ),
+                    None => text("Not on list").size(12),
+                });
+
+            section = section.push(line);
+        }
+
+        section.into()
+    }
+
+    fn remove_controls(&self, progress: &UserProgress) -> Element<'_, Message> {
+        if progress.list_entry_id.is_none() {
+            return container(text("")).into();
+        }
+
+        if self.confirm_remove {
+            column![
+                text("Remove this entry from your list? Progress, notes and scores will be lost.")
+                    .size(14),
+                row![
+                    button(text("Remove"))
+                        .on_press(Message::ConfirmRemove)
+                        .style(iced::theme::Button::Destructive)
+                        .padding(5),
+                    button(text("Cancel"))
+                        .on_press(Message::CancelRemove)
+                        .style(iced::theme::Button::Secondary)
+                        .padding(5),
+                ]
+                .spacing(10),
+            ]
+            .spacing(5)
+            .into()
+        } else {
+            button(text("Remove from list"))
+                .on_press(Message::RemoveFromList)
+                .style(iced::theme::Button::Destructive)
+                .padding(10)
+                .width(Length::Fill)
+                .into()
+        }
+    }
+
+    fn entry_details_editor(&self) -> Element<'_, Message> {
+        let rewatch_label = if self.media_type == MediaType::MANGA {
+            "Rereads"
+        } else {
+            "Rewatches"
+        };
+
+        let mut editor = column![
+            self.started_at_picker.view().map(Message::StartedAtPicker),
+            self.completed_at_picker
+                .view()
+                .map(Message::CompletedAtPicker),
+            row![
+                text(format!("{}: {}", rewatch_label, self.temp_repeat)).size(14),
+                button(text("-"))
+                    .on_press(Message::RepeatChanged(self.temp_repeat - 1))
+                    .padding(5),
+                button(text("+"))
+                    .on_press(Message::RepeatChanged(self.temp_repeat + 1))
+                    .padding(5),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            text("Notes").size(14),
+            text_input("Notes", &self.temp_notes)
+                .on_input(Message::NotesChanged)
+                .padding(5),
+            checkbox("Private", self.temp_private, Message::PrivateToggled),
+            checkbox(
+                "Hide from status lists",
+                self.temp_hidden_from_status_lists,
+                Message::HiddenFromStatusListsToggled,
+            ),
+        ]
+        .spacing(10);
+
+        if !self.temp_custom_lists.is_empty() {
+            editor = editor.push(text("Custom Lists").size(14));
+            for (name, enabled) in &self.temp_custom_lists {
+                let name_for_toggle = name.clone();
+                editor = editor.push(checkbox(name.as_str(), *enabled, move |enabled| {
+                    Message::CustomListToggled(name_for_toggle.clone(), enabled)
+                }));
+            }
+        }
+
+        if !self.temp_advanced_scores.is_empty() {
+            editor = editor.push(text("Advanced Scores").size(14));
+            for (index, (category, score)) in self.temp_advanced_scores.iter().enumerate() {
+                editor = editor.push(
+                    row![
+                        text(format!("{}: {:.1}", category, score))
+                            .size(14)
+                            .width(Length::Fixed(150.0)),
+                        // Advanced scores are kept in the list's own format
+                        slider(0.0..=self.score_format.max() as f32, *score, move |v| {
+                            Message::AdvancedScoreChanged(index, v)
+                        })
+                        .step(self.score_format.step() as f32),
+                    ]
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+                );
+            }
+        }
+
+        editor.into()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
         if self.is_loading {
+            let label = match (&self.preview, self.media_type) {
+                (Some(media), _) => {
+                    format!("Loading {}...", media.title.preferred(self.title_language))
+                }
+                (None, MediaType::ANIME) => "Loading anime details...".to_string(),
+                (None, MediaType::MANGA) => "Loading manga details...".to_string(),
+            };
             return container(
-                text("Loading anime details...")
+                text(label)
                     .size(20)
                     .width(Length::Fill)
                     .horizontal_alignment(iced::alignment::Horizontal::Center),
@@ -494,12 +1158,47 @@ impl DetailsScreen {
                 );
             }
 
+            if let Some(notice) = &self.notice {
+                content = content.push(
+                    container(text(notice).size(16))
+                        .width(Length::Fill)
+                        .padding(10),
+                );
+            }
+
+            if let Some(banner) = &anime.banner_image {
+                content = content.push(image_or_placeholder(
+                    self.images.get(banner),
+                    "",
+                    Length::Fill,
+                    Length::Fixed(180.0),
+                ));
+            }
+
             // Title and banner area
+            let favourite: Element<Message> = if self.is_authenticated {
+                button(text(if anime.is_favourite { "♥" } else { "♡" }).size(24))
+                    .on_press(Message::ToggleFavourite)
+                    .style(iced::theme::Button::Text)
+                    .into()
+            } else {
+                container(text("")).into()
+            };
+
+            let title = anime.title.preferred(self.title_language);
+            // The romaji or English title underneath, when it differs
+            let subtitle = [&anime.title.romaji, &anime.title.english]
+                .into_iter()
+                .flatten()
+                .find(|t| **t != title && !t.trim().is_empty());
+
             let title_section = column![
-                text(&anime.title).size(30),
-                if let Some(english) = &anime.english_title {
-                    let english_text: Element<Message> = container(text(english).size(20)).into();
-                    english_text
+                row![text(&title).size(30), favourite]
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+                if let Some(subtitle) = subtitle {
+                    let subtitle_text: Element<Message> = container(text(subtitle).size(20)).into();
+                    subtitle_text
                 } else {
                     container(text("")).into()
                 },
@@ -519,11 +1218,14 @@ impl DetailsScreen {
                     }
                 ]
                 .spacing(10),
-                text(format!("⭐ {:.1} / 10", anime.score * 10.0))
-                    .size(16)
-                    .style(iced::theme::Text::Color(iced::Color::from_rgb(
-                        1.0, 0.8, 0.0
-                    ))),
+                text(format!(
+                    "⭐ {} (average)",
+                    self.score_format.display(anime.score as f64 * 10.0)
+                ))
+                .size(16)
+                .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                    1.0, 0.8, 0.0
+                ))),
             ]
             .spacing(5);
 
@@ -531,13 +1233,12 @@ impl DetailsScreen {
 
             // Main info section with cover image and details
             let cover_and_details = row![
-            // Cover image (placeholder for now)
-            container(text("Cover Image"))
-            .width(Length::Fixed(220.0))
-            .height(Length::Fixed(320.0))
-            .center_x()
-            .center_y()
-            .style(iced::theme::Container::Box),
+            image_or_placeholder(
+                self.images.get(&anime.cover_image),
+                "Cover Image",
+                Length::Fixed(220.0),
+                Length::Fixed(320.0),
+            ),
 
             // Details section
             column![
@@ -546,16 +1247,41 @@ impl DetailsScreen {
                     if let (Some(progress), Some(status), Some(score), Some(progress_val)) =
                         (&self.user_progress, &self.temp_status, &self.temp_score, &self.temp_progress) {
 
-                            let max_progress = progress.max_progress.unwrap_or(anime.episodes.unwrap_or(0));
+                            let is_manga = anime.media_type == MediaType::MANGA;
+                            let max_progress = progress.max_progress.unwrap_or(
+                                if is_manga { anime.chapters } else { anime.episodes }.unwrap_or(0),
+                            );
+
+                            // Manga also track volumes alongside chapters
+                            let volumes_editor: Element<Message> = match (is_manga, self.temp_progress_volumes) {
+                                (true, Some(volumes_val)) => {
+                                    let max_volumes = progress.max_volumes.or(anime.volumes).unwrap_or(0);
+                                    column![
+                                        text(if max_volumes > 0 {
+                                            format!("Volumes: {} / {}", volumes_val, max_volumes)
+                                        } else {
+                                            format!("Volumes: {}", volumes_val)
+                                        })
+                                        .size(14),
+                                        slider(0.0..=(max_volumes as f32), volumes_val as f32, |v| {
+                                            Message::VolumesChanged(v as i32)
+                                        })
+                                        .step(1.0),
+                                    ]
+                                    .spacing(10)
+                                    .into()
+                                }
+                                _ => container(text("")).into(),
+                            };
 
-                            container(
+                            let progress_panel: Element<Message> = container(
                                 column![
                                     text("Your Progress").size(18),
 
                                     // Status selection
                                     text("Status").size(14),
                                     row![
-                                        button(text("Watching"))
+                                        button(text(if is_manga { "Reading" } else { "Watching" }))
                                             .on_press(Message::StatusChanged("CURRENT".to_string()))
                                             .style(if status == "CURRENT" {
                                                 iced::theme::Button::Primary
@@ -573,7 +1299,7 @@ impl DetailsScreen {
                                             })
                                             .padding(5),
 
-                                        button(text("Plan to Watch"))
+                                        button(text(if is_manga { "Plan to Read" } else { "Plan to Watch" }))
                                             .on_press(Message::StatusChanged("PLANNING".to_string()))
                                             .style(if status == "PLANNING" {
                                                 iced::theme::Button::Primary
@@ -603,7 +1329,7 @@ impl DetailsScreen {
                                             })
                                             .padding(5),
 
-                                        button(text("Rewatching"))
+                                        button(text(if is_manga { "Rereading" } else { "Rewatching" }))
                                             .on_press(Message::StatusChanged("REPEATING".to_string()))
                                             .style(if status == "REPEATING" {
                                                 iced::theme::Button::Primary
@@ -614,18 +1340,20 @@ impl DetailsScreen {
                                     ]
                                     .spacing(5),
 
-                                    // Score slider
-                                    text(format!("Score: {:.1}", score)).size(14),
-                                    slider(0.0..=10.0, *score, Message::ScoreChanged)
-                                            .step(0.5),
+                                    self.score_editor(*score),
 
-                                            // Episode progress
+                                            // Episode / chapter progress
                                             row![
-                                                text(format!("Progress: {}", progress_val)).size(14),
+                                                text(format!(
+                                                    "{}: {}",
+                                                    if is_manga { "Chapters" } else { "Progress" },
+                                                    progress_val
+                                                ))
+                                                .size(14),
                                                 if max_progress > 0 {
-                                                    container(text(format!("/ {}", max_progress))).into()
+                                                    text(format!("/ {}", max_progress)).size(14)
                                                 } else {
-                                                    container(text("")).into()
+                                                    text("")
                                                 }
                                             ],
 
@@ -634,6 +1362,10 @@ impl DetailsScreen {
                                             })
                                             .step(1.0),
 
+                                            volumes_editor,
+
+                                            self.entry_details_editor(),
+
                                             // Save button
                                             button(
                                                 text(if self.is_saving { "Saving..." } else { "Save Progress" })
@@ -641,13 +1373,16 @@ impl DetailsScreen {
                                             .on_press(Message::SaveProgress)
                                             .padding(10)
                                             .width(Length::Fill),
+
+                                            self.remove_controls(progress),
                                 ]
                                 .spacing(10)
                                 .width(Length::Fill)
                             )
                             .padding(15)
                             .style(iced::theme::Container::Box)
-                            .into()
+                            .into();
+                            progress_panel
                         } else {
                             container(text("")).into()
                         }
@@ -655,7 +1390,11 @@ impl DetailsScreen {
                     container(
                         column![
                             text("Login to track your progress").size(18),
-                            text("You can add this anime to your list and track your progress after logging in.")
+                            text(if anime.media_type == MediaType::MANGA {
+                                "You can add this manga to your list and track your progress after logging in."
+                            } else {
+                                "You can add this anime to your list and track your progress after logging in."
+                            })
                                 .size(14)
                         ]
                         .spacing(5)
@@ -668,29 +1407,32 @@ impl DetailsScreen {
                 },
 
                 // Basic information
-                if let Some(episodes) = anime.episodes {
-                    container(text(format!("Episodes: {}", episodes)).size(14)).into()
+                if anime.media_type == MediaType::MANGA {
+                    text(format!(
+                        "Chapters: {} • Volumes: {}",
+                        anime.chapters.map_or("Unknown".to_string(), |c| c.to_string()),
+                        anime.volumes.map_or("Unknown".to_string(), |v| v.to_string())
+                    ))
+                    .size(14)
+                } else if let Some(episodes) = anime.episodes {
+                    text(format!("Episodes: {}", episodes)).size(14)
                 } else {
-                    container(text("Episodes: Unknown").size(14)).into()
+                    text("Episodes: Unknown").size(14)
                 },
 
                 if let Some(duration) = anime.duration {
-                    container(text(format!("Duration: {} minutes", duration)).size(14)).into()
+                    text(format!("Duration: {} minutes", duration)).size(14)
                 } else {
-                    container(text("")).into()
+                    text("")
                 },
 
                 if !anime.genres.is_empty() {
-                    container(text(format!("Genres: {}", anime.genres.join(", "))).size(14)).into()
+                    text(format!("Genres: {}", anime.genres.join(", "))).size(14)
                 } else {
-                    container(text("")).into()
+                    text("")
                 },
 
-                if !anime.studios.is_empty() {
-                    container(text(format!("Studios: {}", anime.studios.join(", "))).size(14)).into()
-                } else {
-                    container(text("")).into()
-                },
+                self.studio_links(&anime.studios),
             ]
             .spacing(10)
             .width(Length::Fill)
@@ -704,6 +1446,27 @@ impl DetailsScreen {
                 column![text("Synopsis").size(20), text(&anime.description).size(14),].spacing(10),
             );
 
+            // Key staff
+            if !anime.staff_previews.is_empty() {
+                let mut staff_section = column![text("Staff").size(20)].spacing(10);
+                for chunk in anime.staff_previews.chunks(4) {
+                    let mut row_content = row![].spacing(15);
+                    for staff in chunk {
+                        row_content = row_content.push(
+                            button(
+                                column![text(&staff.name).size(14), text(&staff.role).size(12)]
+                                    .spacing(2),
+                            )
+                            .on_press(Message::StaffSelected(staff.id))
+                            .style(iced::theme::Button::Text)
+                            .width(Length::Fixed(180.0)),
+                        );
+                    }
+                    staff_section = staff_section.push(row_content);
+                }
+                content = content.push(staff_section);
+            }
+
             // Characters preview
             if !anime.character_previews.is_empty() {
                 let mut character_section = column![text("Characters").size(20),].spacing(15);
@@ -714,12 +1477,12 @@ impl DetailsScreen {
 
                     for character in chunk {
                         let character_card = column![
-                            // Character image placeholder
-                            container(text(""))
-                                .width(Length::Fixed(80.0))
-                                .height(Length::Fixed(120.0))
-                                .center_x()
-                                .style(iced::theme::Container::Box),
+                            image_or_placeholder(
+                                self.images.get(&character.image_url),
+                                "",
+                                Length::Fixed(80.0),
+                                Length::Fixed(120.0),
+                            ),
                             text(&character.name)
                                 .size(14)
                                 .width(Length::Fill)
@@ -733,7 +1496,11 @@ impl DetailsScreen {
                         .width(Length::Fixed(100.0))
                         .align_items(Alignment::Center);
 
-                        row_content = row_content.push(character_card);
+                        row_content = row_content.push(
+                            button(character_card)
+                                .on_press(Message::CharacterSelected(character.id))
+                                .style(iced::theme::Button::Text),
+                        );
                     }
 
                     character_section = character_section.push(row_content);
@@ -742,6 +1509,39 @@ impl DetailsScreen {
                 content = content.push(character_section);
             }
 
+            // Related media, one grid per relation type
+            if !anime.relations.is_empty() {
+                let mut relations_section = column![text("Relations").size(20)].spacing(15);
+                for (relation, credits) in &anime.relations {
+                    relations_section = relations_section.push(
+                        column![
+                            text(relation_label(relation)).size(16),
+                            MediaCredits::new(credits)
+                                .title_language(self.title_language)
+                                .view()
+                                .map(Message::Credits),
+                        ]
+                        .spacing(10),
+                    );
+                }
+                content = content.push(relations_section);
+            }
+
+            content = content.push(self.watch_order_section(anime));
+
+            if !anime.recommendations.is_empty() {
+                content = content.push(
+                    column![
+                        text("Recommendations").size(20),
+                        MediaCredits::new(&anime.recommendations)
+                            .title_language(self.title_language)
+                            .view()
+                            .map(Message::Credits),
+                    ]
+                    .spacing(10),
+                );
+            }
+
             scrollable(content).height(Length::Fill).into()
         } else if let Some(error) = &self.error {
             container(
@@ -767,3 +1567,322 @@ impl DetailsScreen {
         }
     }
 }
+
+// "SIDE_STORY" -> "Side Story"
+fn relation_label(relation: &str) -> String {
+    relation
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_string() + &chars.as_str().to_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn media_type(media_type: Option<anime_details::MediaType>) -> MediaType {
+    match media_type {
+        Some(anime_details::MediaType::MANGA) => MediaType::MANGA,
+        _ => MediaType::ANIME,
+    }
+}
+
+// The screen's model of a details response
+fn details_from_response(
+    data: anime_details::ResponseData,
+    media_type: MediaType,
+    show_adult: bool,
+) -> Result<AnimeDetails, String> {
+    if let Some(media) = data.media {
+        // Convert to our model
+        let title = media
+            .title
+            .clone()
+            .map(MediaTitle::from)
+            .unwrap_or_default();
+
+        let description = media
+            .description
+            .unwrap_or_default()
+            .replace("<br>", "\n")
+            .replace("<i>", "")
+            .replace("</i>", "");
+
+        let cover_image = media
+            .cover_image
+            .as_ref()
+            .and_then(|img| img.large.clone())
+            .unwrap_or_default();
+
+        let genres = media
+            .genres
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .collect();
+
+        // Extract studio names
+        let studios = if let Some(studio_conn) = media.studios {
+            studio_conn
+                .edges
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|edge| {
+                    let node = edge?.node?;
+                    Some(StudioPreview {
+                        id: node.id as i32,
+                        name: node.name,
+                    })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // Extract key staff
+        let staff_previews = media
+            .staff
+            .and_then(|staff_conn| staff_conn.edges)
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .filter_map(|edge| {
+                let node = edge.node?;
+                Some(StaffPreview {
+                    id: node.id as i32,
+                    name: node.name.and_then(|n| n.full).unwrap_or_default(),
+                    role: edge.role.unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        // Extract character previews
+        let character_previews = if let Some(char_conn) = media.characters {
+            char_conn
+                .edges
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|edge| {
+                    let node = edge.as_ref()?.node.as_ref()?;
+                    let role = edge.as_ref()?.role.as_ref()?;
+
+                    Some(CharacterPreview {
+                        id: node.id as i32,
+                        name: node
+                            .name
+                            .as_ref()
+                            .and_then(|n| n.full.clone())
+                            .unwrap_or_default(),
+                        image_url: node
+                            .image
+                            .as_ref()
+                            .and_then(|img| img.medium.clone())
+                            .unwrap_or_default(),
+                        role: format!("{:?}", role), // Convert enum to string
+                    })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let relations = group_relations(media.relations, show_adult);
+        let recommendations = convert_recommendations(media.recommendations, show_adult);
+
+        // Convert status and format enums to strings
+        let status_str = media
+            .status
+            .map_or_else(|| "Unknown".to_string(), |s| format!("{:?}", s));
+
+        let format_str = media
+            .format
+            .map_or_else(|| "Unknown".to_string(), |f| format!("{:?}", f));
+
+        // Convert season enum to string
+        let season_str = media.season.map(|s| format!("{:?}", s));
+
+        let details = AnimeDetails {
+            id: media.id as i32,
+            media_type,
+            title,
+            description,
+            episodes: media.episodes.map(|e| e as i32),
+            chapters: media.chapters.map(|c| c as i32),
+            volumes: media.volumes.map(|v| v as i32),
+            duration: media.duration.map(|d| d as i32),
+            genres,
+            score: media.average_score.unwrap_or(0) as f32 / 10.0,
+            status: status_str,
+            format: format_str,
+            season: season_str,
+            year: media.season_year.map(|y| y as i32),
+            cover_image,
+            banner_image: media.banner_image,
+            studios,
+            staff_previews,
+            character_previews,
+            relations,
+            recommendations,
+            is_favourite: media.is_favourite,
+        };
+
+        Ok(details)
+    } else {
+        Err(format!("{:?} not found", media_type))
+    }
+}
+
+fn group_relations(
+    relations: Option<anime_details::AnimeDetailsMediaRelations>,
+    show_adult: bool,
+) -> Vec<(String, Vec<MediaCredit>)> {
+    let mut groups: Vec<(String, Vec<MediaCredit>)> = Vec::new();
+
+    let edges = relations
+        .and_then(|r| r.edges)
+        .unwrap_or_default()
+        .into_iter()
+        .flatten();
+    for edge in edges {
+        let Some(media) = edge.node else {
+            continue;
+        };
+        if media.is_adult.unwrap_or(false) && !show_adult {
+            continue;
+        }
+
+        let relation = edge
+            .relation_type
+            .map_or_else(|| "OTHER".to_string(), |r| format!("{:?}", r));
+        let credit = MediaCredit {
+            id: media.id as i32,
+            media_type: media_type(media.type_),
+            title: media.title.map(MediaTitle::from).unwrap_or_default(),
+            format: media.format.map(|f| format!("{:?}", f)),
+            year: media.start_date.and_then(|d| d.year).map(|y| y as i32),
+            role: None,
+            characters: Vec::new(),
+            list_status: media
+                .media_list_entry
+                .and_then(|entry| entry.status)
+                .map(|status| format!("{:?}", status)),
+        };
+
+        match groups.iter_mut().find(|(r, _)| *r == relation) {
+            Some((_, credits)) => credits.push(credit),
+            None => groups.push((relation, vec![credit])),
+        }
+    }
+
+    groups
+}
+
+// Already ranked by rating in the query
+fn convert_recommendations(
+    recommendations: Option<anime_details::AnimeDetailsMediaRecommendations>,
+    show_adult: bool,
+) -> Vec<MediaCredit> {
+    recommendations
+        .and_then(|r| r.nodes)
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .filter_map(|recommendation| {
+            let media = recommendation.media_recommendation?;
+            if media.is_adult.unwrap_or(false) && !show_adult {
+                return None;
+            }
+
+            Some(MediaCredit {
+                id: media.id as i32,
+                media_type: media_type(media.type_),
+                title: media.title.map(MediaTitle::from).unwrap_or_default(),
+                format: media.format.map(|f| format!("{:?}", f)),
+                year: media.start_date.and_then(|d| d.year).map(|y| y as i32),
+                role: Some(format!("Rated {:+}", recommendation.rating.unwrap_or(0))),
+                characters: Vec::new(),
+                list_status: media
+                    .media_list_entry
+                    .and_then(|entry| entry.status)
+                    .map(|status| format!("{:?}", status)),
+            })
+        })
+        .collect()
+}
+
+// The viewer's custom lists and, if enabled, advanced scoring categories
+fn list_options(
+    viewer: &viewer::ViewerViewer,
+    media_type: MediaType,
+) -> (Vec<String>, Vec<String>) {
+    let options = viewer.media_list_options.as_ref();
+    let (custom_lists, scoring, scoring_enabled) = match media_type {
+        MediaType::ANIME => options.and_then(|o| o.anime_list.as_ref()).map(|l| {
+            (
+                l.custom_lists.clone(),
+                l.advanced_scoring.clone(),
+                l.advanced_scoring_enabled,
+            )
+        }),
+        MediaType::MANGA => options.and_then(|o| o.manga_list.as_ref()).map(|l| {
+            (
+                l.custom_lists.clone(),
+                l.advanced_scoring.clone(),
+                l.advanced_scoring_enabled,
+            )
+        }),
+    }
+    .unwrap_or_default();
+
+    let names = |values: Option<Vec<Option<String>>>| -> Vec<String> {
+        values.unwrap_or_default().into_iter().flatten().collect()
+    };
+
+    (
+        names(custom_lists),
+        if scoring_enabled.unwrap_or(false) {
+            names(scoring)
+        } else {
+            Vec::new()
+        },
+    )
+}
+
+// `customLists(asArray: true)` is a list of `{ name, enabled }` objects
+fn entry_custom_lists(names: &[String], lists: Option<&Json>) -> Vec<(String, bool)> {
+    names
+        .iter()
+        .map(|name| {
+            let enabled = lists
+                .and_then(Json::as_array)
+                .and_then(|lists| lists.iter().find(|l| l["name"] == name.as_str()))
+                .and_then(|l| l["enabled"].as_bool())
+                .unwrap_or(false);
+            (name.clone(), enabled)
+        })
+        .collect()
+}
+
+// `advancedScores` maps category names to scores
+fn entry_advanced_scores(categories: &[String], scores: Option<&Json>) -> Vec<(String, f32)> {
+    categories
+        .iter()
+        .map(|category| {
+            let score = scores
+                .and_then(|s| s[category.as_str()].as_f64())
+                .unwrap_or(0.0);
+            (category.clone(), score as f32)
+        })
+        .collect()
+}
+
+fn fuzzy_date(year: Option<i64>, month: Option<i64>, day: Option<i64>) -> FuzzyDate {
+    FuzzyDate {
+        year: year.map(|y| y as i32),
+        month: month.map(|m| m as i32),
+        day: day.map(|d| d as i32),
+    }
+}
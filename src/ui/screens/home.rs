@@ -1,37 +1,50 @@
-use iced::widget::{column, container, scrollable, text};
+use iced::widget::{button, column, container, row, scrollable, text};
 use iced::{Command, Element, Length};
-use iced_native::Element;
+
+use chrono::{DateTime, Local, Utc};
 
 use crate::api::client::AniListClient;
-use crate::api::models::{Media, MediaListEntry};
+use crate::api::models::{MediaListEntry, MediaType, TitleLanguage};
+use crate::data::cache_layer::CacheLayer;
 use crate::ui::components::media_list::{MediaList, Message as MediaListMessage};
 
 #[derive(Debug, Clone)]
 pub enum Message {
     LoadUserData,
-    UserDataLoaded(Result<Vec<MediaListEntry>, String>),
+    Refresh,
+    UserDataLoaded(MediaType, Result<Vec<MediaListEntry>, String>),
+    TabSelected(MediaType),
     MediaListMessage(MediaListMessage),
     AnimeSelected(i32),
+    MangaSelected(i32),
     Error(String),
 }
 
 pub struct HomeScreen {
     client: AniListClient,
+    cache: CacheLayer,
     user_id: Option<i32>,
-    username: Option<String>,
+    media_type: MediaType,
+    title_language: TitleLanguage,
     currently_watching: Vec<MediaListEntry>,
+    currently_reading: Vec<MediaListEntry>,
+    last_synced: Option<DateTime<Utc>>,
     is_authenticated: bool,
     is_loading: bool,
     error: Option<String>,
 }
 
 impl HomeScreen {
-    pub fn new(client: AniListClient) -> Self {
+    pub fn new(client: AniListClient, cache: CacheLayer) -> Self {
         Self {
             client,
+            cache,
             user_id: None,
-            username: None,
+            media_type: MediaType::ANIME,
+            title_language: TitleLanguage::default(),
             currently_watching: Vec::new(),
+            currently_reading: Vec::new(),
+            last_synced: None,
             is_authenticated: false,
             is_loading: false,
             error: None,
@@ -39,36 +52,73 @@ impl HomeScreen {
     }
 
     pub fn init(&mut self) -> Command<Message> {
-        Command::perform(async { () }, |_| Message::LoadUserData)
+        Command::perform(async {}, |_| Message::LoadUserData)
+    }
+
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
     }
 
-    pub fn view(&self) -> Element<Message> {
+    pub fn view(&self) -> Element<'_, Message> {
+        let (noun, heading, empty_message) = match self.media_type {
+            MediaType::ANIME => (
+                "anime",
+                "Currently Watching",
+                "You don't have any anime in your 'Currently Watching' list",
+            ),
+            MediaType::MANGA => (
+                "manga",
+                "Currently Reading",
+                "You don't have any manga in your 'Currently Reading' list",
+            ),
+        };
+        let entries = self.current_entries();
+
         // Create content based on state
         let content = if self.is_loading {
-            column![text("Loading your anime list...").size(20)]
+            column![text(format!("Loading your {} list...", noun)).size(20)]
                 .spacing(20)
                 .padding(40)
         } else if !self.is_authenticated {
             column![
                 text("Welcome to AniList Desktop").size(30),
-                text("Please log in to see your anime list").size(18),
+                text(format!("Please log in to see your {} list", noun)).size(18),
             ]
             .spacing(20)
             .padding(40)
-        } else if self.currently_watching.is_empty() {
+        } else if entries.is_empty() {
             column![
                 text("Welcome to AniList Desktop").size(30),
-                text("You don't have any anime in your 'Currently Watching' list").size(18),
+                text(empty_message).size(18),
             ]
             .spacing(20)
             .padding(40)
         } else {
             // Create a MediaList widget with our data - create owned clone to avoid borrowing
-            let entries_clone = self.currently_watching.clone();
+            let entries_clone = entries.to_vec();
+            let synced_label = self.last_synced.map_or_else(
+                || "Syncing...".to_string(),
+                |synced| {
+                    format!(
+                        "Last synced {}",
+                        synced.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+                    )
+                },
+            );
             column![
-                text("Currently Watching").size(30),
+                text(heading).size(30),
+                row![
+                    text(synced_label).size(12),
+                    button(text("Refresh").size(12))
+                        .on_press(Message::Refresh)
+                        .style(iced::theme::Button::Text)
+                        .padding(0),
+                ]
+                .spacing(10),
                 MediaList::new(entries_clone)
-                    .on_select(|id| MediaListMessage::Selected(id))
+                    .media_type(self.media_type)
+                    .title_language(self.title_language)
+                    .on_select(MediaListMessage::Selected)
                     .view()
                     .map(Message::MediaListMessage)
             ]
@@ -76,6 +126,25 @@ impl HomeScreen {
             .padding(40)
         };
 
+        // Anime / Manga tabs
+        let tab_button = |label: &'static str, media_type: MediaType| {
+            button(text(label))
+                .on_press(Message::TabSelected(media_type))
+                .padding(10)
+                .style(if self.media_type == media_type {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                })
+        };
+        let tabs = row![
+            tab_button("Anime", MediaType::ANIME),
+            tab_button("Manga", MediaType::MANGA),
+        ]
+        .spacing(10)
+        .padding([0, 40]);
+        let content = column![tabs, content];
+
         // Error message if any
         let content_with_error = if let Some(error) = &self.error {
             column![
@@ -103,105 +172,93 @@ impl HomeScreen {
         Element::from(scrollable_element)
     }
 
-    pub fn is_authenticated(&self) -> bool {
-        self.is_authenticated
+    fn current_entries(&self) -> &[MediaListEntry] {
+        match self.media_type {
+            MediaType::ANIME => &self.currently_watching,
+            MediaType::MANGA => &self.currently_reading,
+        }
+    }
+
+    // Show whatever we have cached for this list straight away. Returns
+    // whether it should be refreshed from AniList.
+    fn load_cached(&mut self, media_type: MediaType) -> bool {
+        let Some(user_id) = self.user_id.or_else(|| self.cache.stored_user_id()) else {
+            return true;
+        };
+        self.user_id = Some(user_id);
+
+        let needs_refresh = match self.cache.peek_list(user_id, media_type, Some("CURRENT")) {
+            Ok(Some(cached)) => {
+                // Only a logged in viewer's list gets synced
+                self.is_authenticated = true;
+                self.set_entries(media_type, cached.value);
+                cached.stale
+            }
+            Ok(None) | Err(_) => true,
+        };
+        self.last_synced = self.cache.last_synced(user_id, media_type).ok().flatten();
+
+        needs_refresh
+    }
+
+    fn set_entries(&mut self, media_type: MediaType, entries: Vec<MediaListEntry>) {
+        match media_type {
+            MediaType::ANIME => self.currently_watching = entries,
+            MediaType::MANGA => self.currently_reading = entries,
+        }
+    }
+
+    // Sync the viewer's list through the cache and read back the CURRENT entries
+    async fn refresh_list(
+        client: AniListClient,
+        cache: CacheLayer,
+        media_type: MediaType,
+    ) -> Result<Vec<MediaListEntry>, String> {
+        // Try to get the current user (Viewer)
+        let viewer = match client.get_viewer().await {
+            Ok(viewer_data) => viewer_data
+                .viewer
+                .ok_or_else(|| "Could not get user information".to_string())?,
+            Err(e) => {
+                // Check if it's an authentication error
+                return if e.to_string().contains("Authentication required") {
+                    Err("Not authenticated".to_string())
+                } else {
+                    Err(format!("Failed to get user: {}", e))
+                };
+            }
+        };
+        let user_id = viewer.id as i32;
+
+        cache
+            .refresh_list(user_id, media_type, Some("CURRENT"))
+            .await
+            .map_err(|e| format!("Failed to sync {:?} list: {}", media_type, e))
     }
 
     pub fn update(&mut self, message: Message) -> Command<Message> {
         match message {
             Message::LoadUserData => {
-                // First, check if we're authenticated
+                // Render from the cache first, then refresh in the background if stale
+                if !self.load_cached(self.media_type) {
+                    self.is_loading = false;
+                    return Command::none();
+                }
+                self.update(Message::Refresh)
+            }
+            Message::Refresh => {
                 let client = self.client.clone();
-                self.is_loading = true;
+                let cache = self.cache.clone();
+                let media_type = self.media_type;
+                self.is_loading = self.current_entries().is_empty();
 
                 Command::perform(
-                    async move {
-                        // Try to get the current user (Viewer)
-                        match client.get_viewer().await {
-                            Ok(viewer_data) => {
-                                if let Some(viewer) = viewer_data.viewer {
-                                    let user_id = viewer.id as i32;
-
-                                    // Now fetch the user's anime list
-                                    match client.get_user_anime_list(
-                                        user_id,
-                                    Some(crate::api::client::user_anime_list::MediaListStatus::CURRENT)
-                                ).await {
-                                    Ok(list_data) => {
-                                        // Convert to MediaListEntry objects
-                                        let mut entries = Vec::new();
-
-                                        if let Some(collection) = list_data.media_list_collection {
-                                            for list_opt in collection.lists.unwrap_or_default() {
-                                                if let Some(list) = list_opt {
-                                                    if let Some(list_entries) = list.entries {
-                                                        for entry in list_entries {
-                                                            if let Some(entry) = entry {
-                                                                // Convert to our internal model
-                                                                let media_list_entry = MediaListEntry {
-                                                                    id: entry.id as i32,
-                                                                    media_id: entry.media_id as i32,
-                                                                    status: entry.status.map_or("UNKNOWN".to_string(), |s| format!("{:?}", s)),
-                                                                    score: entry.score,
-                                                                    progress: entry.progress.map(|p| p as i32),
-                                                                    updated_at: entry.updated_at.unwrap_or(0),
-                                                                    media: entry.media.map(|m| {
-                                                                        crate::api::models::Media {
-                                                                            id: m.id as i32,
-                                                                            title: crate::api::models::MediaTitle {
-                                                                                romaji: m.title.as_ref().and_then(|t| t.romaji.clone()),
-                                                                                english: m.title.as_ref().and_then(|t| t.english.clone()),
-                                                                                native: m.title.as_ref().and_then(|t| t.native.clone()),
-                                                                            },
-                                                                            description: None,
-                                                                            episodes: m.episodes.map(|e| e as i32),
-                                                                            duration: None,
-                                                                            genres: None,
-                                                                            average_score: None,
-                                                                            cover_image: m.cover_image.map(|img| {
-                                                                                crate::api::models::MediaCoverImage {
-                                                                                    large: None,
-                                                                                    medium: img.medium,
-                                                                                }
-                                                                            }),
-                                                                            banner_image: None,
-                                                                            status: m.status.map(|s| format!("{:?}", s)),
-                                                                            format: m.format.map(|f| format!("{:?}", f)),
-                                                                        }
-                                                                    }),
-                                                                };
-
-                                                                entries.push(media_list_entry);
-                                                }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-
-                                        Ok((user_id, viewer.name, entries))
-                                    },
-                                    Err(e) => Err(format!("Failed to load anime list: {}", e)),
-                                }
-                                } else {
-                                    Err("Could not get user information".to_string())
-                                }
-                            }
-                            Err(e) => {
-                                // Check if it's an authentication error
-                                if e.to_string().contains("Authentication required") {
-                                    Err("Not authenticated".to_string())
-                                } else {
-                                    Err(format!("Failed to get user: {}", e))
-                                }
-                            }
-                        }
-                    },
-                    |result| match result {
-                        Ok((_, _, entries)) => Message::UserDataLoaded(Ok(entries)),
+                    Self::refresh_list(client, cache, media_type),
+                    move |result| match result {
+                        Ok(entries) => Message::UserDataLoaded(media_type, Ok(entries)),
                         Err(e) => {
                             if e == "Not authenticated" {
-                                Message::UserDataLoaded(Err(e))
+                                Message::UserDataLoaded(media_type, Err(e))
                             } else {
                                 Message::Error(e)
                             }
@@ -209,12 +266,23 @@ impl HomeScreen {
                     },
                 )
             }
-            Message::UserDataLoaded(result) => {
+            Message::TabSelected(media_type) => {
+                self.media_type = media_type;
+
+                // Lists are refreshed lazily when a tab is opened
+                if self.load_cached(media_type) && self.is_authenticated {
+                    Command::perform(async {}, |_| Message::LoadUserData)
+                } else {
+                    Command::none()
+                }
+            }
+            Message::UserDataLoaded(media_type, result) => {
                 self.is_loading = false;
 
                 match result {
                     Ok(entries) => {
-                        self.currently_watching = entries;
+                        self.set_entries(media_type, entries);
+                        self.last_synced = Some(Utc::now());
                         self.is_authenticated = true;
                         self.error = None;
                     }
@@ -230,18 +298,17 @@ impl HomeScreen {
                 Command::none()
             }
             Message::MediaListMessage(media_list_msg) => {
-                match media_list_msg {
-                    MediaListMessage::Selected(id) => {
-                        // The user selected an anime, propagate the message up
-                        Command::perform(async move { id }, Message::AnimeSelected)
-                    }
-                    MediaListMessage::CardClicked(id) => {
-                        // The user clicked a card, propagate the message up
-                        Command::perform(async move { id }, Message::AnimeSelected)
-                    }
+                let id = match media_list_msg {
+                    // The user selected an entry or clicked a card, propagate the message up
+                    MediaListMessage::Selected(id) | MediaListMessage::CardClicked(id) => id,
+                };
+
+                match self.media_type {
+                    MediaType::ANIME => Command::perform(async move { id }, Message::AnimeSelected),
+                    MediaType::MANGA => Command::perform(async move { id }, Message::MangaSelected),
                 }
             }
-            Message::AnimeSelected(_) => {
+            Message::AnimeSelected(_) | Message::MangaSelected(_) => {
                 // This will be handled by the parent component
                 Command::none()
             }
@@ -0,0 +1,399 @@
+use std::collections::HashSet;
+
+use iced::widget::{button, checkbox, column, container, row, scrollable, text, text_input};
+use iced::{Alignment, Command, Element, Length};
+
+use crate::api::client::AniListClient;
+use crate::api::models::{MediaType, TitleLanguage};
+use crate::api::my_list::status_label;
+use crate::data::cache_layer::CacheLayer;
+use crate::data::import::{self, ImportItem, ImportReport, Resolution};
+use crate::data::outbox::Outbox;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    All,
+    New,
+    Conflicts,
+    Unchanged,
+    Unresolved,
+}
+
+impl Filter {
+    const ALL: [Filter; 5] = [
+        Filter::All,
+        Filter::New,
+        Filter::Conflicts,
+        Filter::Unchanged,
+        Filter::Unresolved,
+    ];
+
+    fn matches(self, item: &ImportItem) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::New => item.resolution == Resolution::New,
+            Filter::Conflicts => item.resolution == Resolution::Conflict,
+            Filter::Unchanged => item.resolution == Resolution::Unchanged,
+            Filter::Unresolved => item.resolution == Resolution::Unresolved,
+        }
+    }
+}
+
+impl std::fmt::Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Filter::All => write!(f, "All"),
+            Filter::New => write!(f, "New"),
+            Filter::Conflicts => write!(f, "Conflicts"),
+            Filter::Unchanged => write!(f, "Unchanged"),
+            Filter::Unresolved => write!(f, "Unresolved"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    PathChanged(String),
+    DryRunToggled(bool),
+    Load,
+    Loaded(Result<Vec<ImportItem>, String>),
+    FilterSelected(Filter),
+    // Row index and whether it is included
+    Included(usize, bool),
+    Apply,
+    Applied(ImportReport),
+}
+
+pub struct ImportScreen {
+    client: AniListClient,
+    cache: CacheLayer,
+    outbox: Outbox,
+    path: String,
+    dry_run: bool,
+    items: Vec<ImportItem>,
+    // Rows that will be saved
+    included: HashSet<usize>,
+    filter: Filter,
+    title_language: TitleLanguage,
+    is_loading: bool,
+    is_applying: bool,
+    offline_mode: bool,
+    report: Option<ImportReport>,
+    error: Option<String>,
+}
+
+impl ImportScreen {
+    pub fn new(client: AniListClient, cache: CacheLayer, outbox: Outbox) -> Self {
+        Self {
+            client,
+            cache,
+            outbox,
+            path: String::new(),
+            dry_run: true,
+            items: Vec::new(),
+            included: HashSet::new(),
+            filter: Filter::All,
+            title_language: TitleLanguage::ROMAJI,
+            is_loading: false,
+            is_applying: false,
+            offline_mode: false,
+            report: None,
+            error: None,
+        }
+    }
+
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    pub fn set_offline_mode(&mut self, offline_mode: bool) {
+        self.offline_mode = offline_mode;
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::PathChanged(path) => {
+                self.path = path;
+                Command::none()
+            }
+            Message::DryRunToggled(dry_run) => {
+                self.dry_run = dry_run;
+                Command::none()
+            }
+            Message::Load => {
+                let Some(user_id) = self.cache.stored_user_id() else {
+                    self.error = Some("Log in to import a list".to_string());
+                    return Command::none();
+                };
+                self.is_loading = true;
+                self.error = None;
+                self.report = None;
+
+                let path = self.path.trim().to_string();
+                let client = self.client.clone();
+                let cache = self.cache.clone();
+                Command::perform(
+                    async move {
+                        let contents = std::fs::read_to_string(&path)
+                            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+                        let rows = import::parse(&contents).map_err(|e| e.to_string())?;
+                        // Compare with AniList, not a copy that may be out of date
+                        let list = cache
+                            .refresh_list(user_id, MediaType::ANIME, None)
+                            .await
+                            .map_err(|e| format!("Failed to load your list: {}", e))?;
+
+                        import::resolve(&client, rows, &list)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::Loaded,
+                )
+            }
+            Message::Loaded(result) => {
+                self.is_loading = false;
+
+                match result {
+                    Ok(items) => {
+                        // Conflicts keep the AniList entry unless picked
+                        self.included = items
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, item)| item.resolution == Resolution::New)
+                            .map(|(i, _)| i)
+                            .collect();
+                        self.items = items;
+                        self.filter = Filter::All;
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                Command::none()
+            }
+            Message::FilterSelected(filter) => {
+                self.filter = filter;
+                Command::none()
+            }
+            Message::Included(index, included) => {
+                if included {
+                    self.included.insert(index);
+                } else {
+                    self.included.remove(&index);
+                }
+                Command::none()
+            }
+            Message::Apply => {
+                self.is_applying = true;
+                self.report = None;
+
+                let items: Vec<ImportItem> = self
+                    .items
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| self.included.contains(i))
+                    .map(|(_, item)| item.clone())
+                    .collect();
+                let client = self.client.clone();
+                let outbox = self.outbox.clone();
+                let dry_run = self.dry_run;
+                let offline_mode = self.offline_mode;
+                Command::perform(
+                    async move { import::apply(&client, &outbox, &items, dry_run, offline_mode).await },
+                    Message::Applied,
+                )
+            }
+            Message::Applied(report) => {
+                self.is_applying = false;
+                if !report.dry_run {
+                    // Saved rows are now unchanged
+                    self.included.clear();
+                }
+                self.report = Some(report);
+                Command::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let mut content = column![text("Import a list").size(30)]
+            .spacing(20)
+            .padding(30);
+
+        content = content.push(
+            column![
+                text("A MyAnimeList XML export, or a CSV file from Kitsu, a spreadsheet or this app. CSV files need a header row with at least a title column.")
+                    .size(14),
+                row![
+                    text_input("Path to the file", &self.path)
+                        .on_input(Message::PathChanged)
+                        .on_submit(Message::Load)
+                        .padding(10)
+                        .width(Length::Fill),
+                    button(text(if self.is_loading { "Matching..." } else { "Load" }))
+                        .on_press(Message::Load)
+                        .padding(10),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            ]
+            .spacing(10),
+        );
+
+        if let Some(error) = &self.error {
+            content = content.push(
+                text(error)
+                    .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                        0.8, 0.2, 0.2,
+                    )))
+                    .size(16),
+            );
+        }
+
+        if self.items.is_empty() {
+            return scrollable(content).height(Length::Fill).into();
+        }
+
+        // Filter tabs with counts
+        let mut tabs = row![].spacing(10);
+        for filter in Filter::ALL {
+            let count = self
+                .items
+                .iter()
+                .filter(|item| filter.matches(item))
+                .count();
+            tabs = tabs.push(
+                button(text(format!("{} ({})", filter, count)))
+                    .on_press(Message::FilterSelected(filter))
+                    .padding(8)
+                    .style(if self.filter == filter {
+                        iced::theme::Button::Primary
+                    } else {
+                        iced::theme::Button::Secondary
+                    }),
+            );
+        }
+        content = content.push(tabs);
+
+        let mut table = column![row![
+            text("").width(Length::Fixed(30.0)),
+            text("In the file").width(Length::FillPortion(3)),
+            text("On AniList").width(Length::FillPortion(3)),
+            text("Matched by").width(Length::FillPortion(1)),
+            text("Import").width(Length::FillPortion(2)),
+            text("Current").width(Length::FillPortion(2)),
+        ]
+        .spacing(10)]
+        .spacing(8);
+
+        for (i, item) in self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| self.filter.matches(item))
+        {
+            let include: Element<Message> = if item.update().is_some() {
+                checkbox("", self.included.contains(&i), move |included| {
+                    Message::Included(i, included)
+                })
+                .into()
+            } else {
+                text("").into()
+            };
+            let anilist_title = match (&item.title, item.media_id) {
+                (Some(title), _) => title.preferred(self.title_language),
+                (None, Some(id)) => format!("#{}", id),
+                (None, None) => "No match".to_string(),
+            };
+            let current = item
+                .existing
+                .as_ref()
+                .map(|existing| entry_summary(&existing.status, existing.score, existing.progress))
+                .unwrap_or_else(|| "Not on your list".to_string());
+
+            table = table.push(
+                row![
+                    container(include).width(Length::Fixed(30.0)),
+                    text(format!("{}. {}", item.row.line, item.row.title))
+                        .size(14)
+                        .width(Length::FillPortion(3)),
+                    text(anilist_title).size(14).width(Length::FillPortion(3)),
+                    text(
+                        item.matched_by
+                            .map(|source| source.to_string())
+                            .unwrap_or_default()
+                    )
+                    .size(14)
+                    .width(Length::FillPortion(1)),
+                    text(entry_summary(
+                        &item.row.status,
+                        item.row.score,
+                        item.row.progress
+                    ))
+                    .size(14)
+                    .width(Length::FillPortion(2)),
+                    text(current).size(14).width(Length::FillPortion(2)),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            );
+        }
+        content = content.push(table);
+
+        let apply = button(text(if self.is_applying {
+            "Importing..."
+        } else if self.dry_run {
+            "Dry run"
+        } else {
+            "Import"
+        }))
+        .padding(10);
+        content = content.push(
+            row![
+                checkbox(
+                    "Dry run, only count what would change",
+                    self.dry_run,
+                    Message::DryRunToggled
+                ),
+                if self.is_applying || self.included.is_empty() {
+                    apply
+                } else {
+                    apply.on_press(Message::Apply)
+                },
+                text(format!("{} selected", self.included.len())).size(14),
+            ]
+            .spacing(20)
+            .align_items(Alignment::Center),
+        );
+
+        if let Some(report) = &self.report {
+            let mut summary = column![text(if report.dry_run {
+                format!("Dry run: {} entries would be saved", report.saved)
+            } else {
+                format!(
+                    "Saved {} entries, {} queued until you are back online, {} failed",
+                    report.saved,
+                    report.queued,
+                    report.failed.len()
+                )
+            })
+            .size(16)]
+            .spacing(5);
+            for (title, error) in &report.failed {
+                summary = summary.push(text(format!("{}: {}", title, error)).size(14));
+            }
+            content = content.push(summary);
+        }
+
+        scrollable(content).height(Length::Fill).into()
+    }
+}
+
+fn entry_summary(status: &str, score: Option<f64>, progress: Option<i32>) -> String {
+    let mut summary = status_label(status, MediaType::ANIME).to_string();
+    if let Some(progress) = progress.filter(|p| *p > 0) {
+        summary.push_str(&format!(", ep {}", progress));
+    }
+    if let Some(score) = score.filter(|s| *s > 0.0) {
+        summary.push_str(&format!(", {}/100", score));
+    }
+    summary
+}
@@ -1,5 +1,14 @@
+pub mod activity;
+pub mod calendar;
+pub mod character;
 pub mod details;
 pub mod home;
+pub mod import;
+pub mod my_list;
+pub mod notifications;
 pub mod profile;
 pub mod search;
+pub mod seasonal;
 pub mod settings;
+pub mod staff;
+pub mod studio;
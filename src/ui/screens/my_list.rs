@@ -0,0 +1,1046 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, slider, text, text_input,
+};
+use iced::{Alignment, Command, Element, Length};
+use log::warn;
+
+use crate::api::client::AniListClient;
+use crate::api::models::{
+    ListEntryDetails, ListLayout, MediaFormat, MediaType, ScoreFormat, TitleLanguage,
+};
+use crate::api::my_list::{
+    status_label, BulkEdit, ListFilter, ListItem, ListSort, ListTab, MyList, STATUSES,
+};
+use crate::data::cache_layer::{CacheLayer, CacheSource};
+use crate::data::database::ListUpdate;
+use crate::data::outbox::{Outbox, SaveOutcome};
+use crate::ui::images::{image_or_placeholder, ImageLoader};
+use crate::ui::screens::search::Choice;
+use crate::utils::config::{load_config, save_config};
+use crate::utils::text::{countdown, time_ago};
+
+const GRID_COLUMNS: usize = 6;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Load,
+    Refresh,
+    Loaded(u64, Result<MyList, String>),
+    MediaTypeSelected(MediaType),
+    TabSelected(ListTab),
+    SortSelected(ListSort),
+    FormatSelected(Choice<MediaFormat>),
+    GenreSelected(GenreChoice),
+    YearSelected(Choice<i32>),
+    SearchChanged(String),
+    ClearFilters,
+    LayoutSelected(ListLayout),
+    LayoutSaved(Result<(), String>),
+    ImageLoaded(Result<String, String>),
+    MediaSelected(i32, MediaType),
+    // Media id and the change in episodes or chapters
+    ProgressStepped(i32, i32),
+    CompletionScoreChanged(f32),
+    CompleteEntry,
+    DismissCompletion,
+    Selected(i32, bool),
+    SelectAll,
+    ClearSelection,
+    BulkScoreChanged(f32),
+    DeleteSelected,
+    CancelDelete,
+    ApplyBulk(BulkEdit),
+    Saved(Result<SaveOutcome, String>),
+}
+
+// A status in the bulk status picker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusOption {
+    status: &'static str,
+    label: &'static str,
+}
+
+impl std::fmt::Display for StatusOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+// An entry that just reached its last episode, offered to be completed
+struct Completion {
+    media_id: i32,
+    // 0-100
+    score: f64,
+}
+
+// `Choice` needs `Copy`, which genre names aren't
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenreChoice(Option<String>);
+
+impl std::fmt::Display for GenreChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(genre) => write!(f, "{}", genre),
+            None => write!(f, "Any"),
+        }
+    }
+}
+
+pub struct MyListScreen {
+    client: AniListClient,
+    cache: CacheLayer,
+    outbox: Outbox,
+    images: ImageLoader,
+    offline_mode: bool,
+    title_language: TitleLanguage,
+    score_format: ScoreFormat,
+    media_type: MediaType,
+    list: Option<MyList>,
+    // Shown from SQLite while the full list is fetched
+    is_partial: bool,
+    tab: ListTab,
+    sort: ListSort,
+    filter: ListFilter,
+    // Layout picked for each tab, see `ListTab::key`
+    layouts: HashMap<String, ListLayout>,
+    // Bumped on every load so a late response for the other media type is dropped
+    generation: u64,
+    is_loading: bool,
+    error: Option<String>,
+    // Shown after an edit was queued offline
+    notice: Option<String>,
+    completion: Option<Completion>,
+    // Media ids picked for a bulk edit
+    selected: HashSet<i32>,
+    // 0-100
+    bulk_score: f64,
+    confirming_delete: bool,
+}
+
+impl MyListScreen {
+    pub fn new(
+        client: AniListClient,
+        cache: CacheLayer,
+        outbox: Outbox,
+        images: ImageLoader,
+    ) -> Self {
+        let layouts = load_config()
+            .map(|config| config.list_layouts)
+            .unwrap_or_default();
+
+        Self {
+            client,
+            cache,
+            outbox,
+            images,
+            offline_mode: false,
+            title_language: TitleLanguage::default(),
+            score_format: ScoreFormat::default(),
+            media_type: MediaType::ANIME,
+            list: None,
+            is_partial: false,
+            tab: ListTab::default(),
+            sort: ListSort::default(),
+            filter: ListFilter::default(),
+            layouts,
+            generation: 0,
+            is_loading: false,
+            error: None,
+            notice: None,
+            completion: None,
+            selected: HashSet::new(),
+            bulk_score: 0.0,
+            confirming_delete: false,
+        }
+    }
+
+    // In offline mode edits go straight to the outbox
+    pub fn set_offline_mode(&mut self, offline_mode: bool) {
+        self.offline_mode = offline_mode;
+    }
+
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    pub fn set_score_format(&mut self, score_format: ScoreFormat) {
+        self.score_format = score_format;
+    }
+
+    // Edits made elsewhere invalidate the cache, so check it on every visit
+    pub fn init(&mut self) -> Command<Message> {
+        self.update(Message::Load)
+    }
+
+    fn layout(&self) -> ListLayout {
+        self.layouts
+            .get(&self.tab.key(self.media_type))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Load => {
+                let Some(user_id) = self.cache.stored_user_id() else {
+                    self.list = None;
+                    self.error = Some("Log in to see your list".to_string());
+                    return Command::none();
+                };
+                self.error = None;
+
+                match self.cache.peek_my_list(user_id, self.media_type) {
+                    Ok(Some(cached)) if cached.source == CacheSource::Memory => {
+                        self.list = Some(cached.value);
+                        self.is_partial = false;
+                        return self.load_covers();
+                    }
+                    // Keep a full list we already have over the partial stored one
+                    Ok(Some(cached)) if self.list.is_none() => {
+                        self.list = Some(cached.value);
+                        self.is_partial = true;
+                    }
+                    Ok(_) => {}
+                    Err(e) => warn!("Failed to read the cached list: {}", e),
+                }
+
+                Command::batch(vec![self.load_covers(), self.update(Message::Refresh)])
+            }
+            Message::Refresh => {
+                let Some(user_id) = self.cache.stored_user_id() else {
+                    return Command::none();
+                };
+                self.generation += 1;
+                self.is_loading = true;
+
+                let cache = self.cache.clone();
+                let media_type = self.media_type;
+                let generation = self.generation;
+                Command::perform(
+                    async move {
+                        cache
+                            .refresh_my_list(user_id, media_type)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    move |result| Message::Loaded(generation, result),
+                )
+            }
+            Message::Loaded(generation, result) => {
+                if generation != self.generation {
+                    return Command::none();
+                }
+                self.is_loading = false;
+
+                match result {
+                    Ok(list) => {
+                        // The custom list may have been deleted on AniList
+                        if !list.tabs().contains(&self.tab) {
+                            self.tab = ListTab::default();
+                        }
+                        self.list = Some(list);
+                        self.is_partial = false;
+                        self.load_covers()
+                    }
+                    Err(e) => {
+                        self.error = Some(format!("Failed to load your list: {}", e));
+                        Command::none()
+                    }
+                }
+            }
+            Message::MediaTypeSelected(media_type) => {
+                if media_type == self.media_type {
+                    return Command::none();
+                }
+                self.media_type = media_type;
+                self.list = None;
+                self.tab = ListTab::default();
+                self.clear_selection();
+                self.completion = None;
+                // Anime and manga formats don't overlap
+                self.filter.format = None;
+                self.update(Message::Load)
+            }
+            Message::TabSelected(tab) => {
+                self.tab = tab;
+                // Bulk edits only apply to what is on screen
+                self.clear_selection();
+                self.load_covers()
+            }
+            Message::SortSelected(sort) => {
+                self.sort = sort;
+                Command::none()
+            }
+            Message::FormatSelected(format) => {
+                self.filter.format = format.into_option();
+                self.load_covers()
+            }
+            Message::GenreSelected(GenreChoice(genre)) => {
+                self.filter.genre = genre;
+                self.load_covers()
+            }
+            Message::YearSelected(year) => {
+                self.filter.year = year.into_option();
+                self.load_covers()
+            }
+            Message::SearchChanged(search) => {
+                self.filter.text = search;
+                self.load_covers()
+            }
+            Message::ClearFilters => {
+                self.filter = ListFilter::default();
+                self.load_covers()
+            }
+            Message::LayoutSelected(layout) => {
+                let key = self.tab.key(self.media_type);
+                self.layouts.insert(key.clone(), layout);
+
+                Command::batch(vec![
+                    self.load_covers(),
+                    Command::perform(
+                        async move {
+                            // Reload so settings saved elsewhere aren't overwritten
+                            let mut config = load_config().map_err(|e| e.to_string())?;
+                            config.list_layouts.insert(key, layout);
+                            save_config(&config).map_err(|e| e.to_string())
+                        },
+                        Message::LayoutSaved,
+                    ),
+                ])
+            }
+            Message::LayoutSaved(result) => {
+                if let Err(e) = result {
+                    warn!("Failed to save the list layout: {}", e);
+                }
+                Command::none()
+            }
+            Message::ImageLoaded(result) => {
+                if let Err(e) = result {
+                    warn!("Failed to load cover: {}", e);
+                }
+                Command::none()
+            }
+            Message::MediaSelected(_, _) => {
+                // Handled by the parent component
+                Command::none()
+            }
+            Message::ProgressStepped(media_id, step) => {
+                let media_type = self.media_type;
+                let Some(item) = self.list.as_mut().and_then(|list| list.item_mut(media_id)) else {
+                    return Command::none();
+                };
+                let progress = (item.progress + step).max(0);
+                if progress == item.progress || item.total.is_some_and(|total| progress > total) {
+                    return Command::none();
+                }
+
+                // Starting something planned puts it in progress
+                let status = (item.status == "PLANNING").then(|| "CURRENT".to_string());
+                let update = ListUpdate {
+                    entry_id: (item.entry_id > 0).then_some(item.entry_id),
+                    media_id,
+                    media_type,
+                    status: status.clone(),
+                    score: None,
+                    progress: Some(progress),
+                    progress_volumes: None,
+                    details: ListEntryDetails::default(),
+                };
+
+                // Shown straight away so repeated clicks keep counting up
+                item.progress = progress;
+                if let Some(status) = status {
+                    item.status = status;
+                }
+                item.updated_at = Utc::now().timestamp();
+                if step > 0 && item.total == Some(progress) && item.status != "COMPLETED" {
+                    self.completion = Some(Completion {
+                        media_id,
+                        score: item.score,
+                    });
+                }
+
+                self.save(update)
+            }
+            Message::CompletionScoreChanged(score) => {
+                if let Some(completion) = &mut self.completion {
+                    completion.score = self.score_format.to_raw(score as f64);
+                }
+                Command::none()
+            }
+            Message::CompleteEntry => {
+                let media_type = self.media_type;
+                let Some(completion) = self.completion.take() else {
+                    return Command::none();
+                };
+                let Some(item) = self
+                    .list
+                    .as_mut()
+                    .and_then(|list| list.item_mut(completion.media_id))
+                else {
+                    return Command::none();
+                };
+
+                item.status = "COMPLETED".to_string();
+                item.score = completion.score;
+                item.updated_at = Utc::now().timestamp();
+                let update = ListUpdate {
+                    entry_id: (item.entry_id > 0).then_some(item.entry_id),
+                    media_id: item.media_id,
+                    media_type,
+                    status: Some(item.status.clone()),
+                    score: (completion.score > 0.0).then_some(completion.score),
+                    progress: Some(item.progress),
+                    progress_volumes: None,
+                    details: ListEntryDetails::default(),
+                };
+                self.save(update)
+            }
+            Message::DismissCompletion => {
+                self.completion = None;
+                Command::none()
+            }
+            Message::Selected(media_id, selected) => {
+                if selected {
+                    self.selected.insert(media_id);
+                } else {
+                    self.selected.remove(&media_id);
+                }
+                self.confirming_delete = false;
+                Command::none()
+            }
+            Message::SelectAll => {
+                self.selected = self
+                    .visible_items()
+                    .into_iter()
+                    .map(|item| item.media_id)
+                    .collect();
+                Command::none()
+            }
+            Message::ClearSelection => {
+                self.clear_selection();
+                Command::none()
+            }
+            Message::BulkScoreChanged(score) => {
+                self.bulk_score = self.score_format.to_raw(score as f64);
+                Command::none()
+            }
+            Message::DeleteSelected => {
+                self.confirming_delete = true;
+                Command::none()
+            }
+            Message::CancelDelete => {
+                self.confirming_delete = false;
+                Command::none()
+            }
+            Message::ApplyBulk(edit) => {
+                let Some(list) = &mut self.list else {
+                    return Command::none();
+                };
+                let items: Vec<ListItem> = list
+                    .items
+                    .iter()
+                    .filter(|item| self.selected.contains(&item.media_id))
+                    .cloned()
+                    .collect();
+                if items.is_empty() {
+                    return Command::none();
+                }
+
+                list.apply(&self.selected, &edit, Utc::now().timestamp());
+                self.clear_selection();
+
+                let client = self.client.clone();
+                let outbox = self.outbox.clone();
+                let media_type = self.media_type;
+                let offline_mode = self.offline_mode;
+                Command::perform(
+                    async move {
+                        outbox
+                            .save_bulk(&client, &items, media_type, &edit, offline_mode)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::Saved,
+                )
+            }
+            Message::Saved(result) => match result {
+                Ok(SaveOutcome::Saved) => Command::none(),
+                Ok(SaveOutcome::Queued) => {
+                    self.notice = Some(
+                        "Saved offline, your changes will be synced once you're back online"
+                            .to_string(),
+                    );
+                    Command::none()
+                }
+                Err(e) => {
+                    self.error = Some(format!("Failed to save your changes: {}", e));
+                    // Put back what AniList actually has
+                    self.update(Message::Refresh)
+                }
+            },
+        }
+    }
+
+    fn save(&self, update: ListUpdate) -> Command<Message> {
+        let client = self.client.clone();
+        let outbox = self.outbox.clone();
+        let offline_mode = self.offline_mode;
+        Command::perform(
+            async move {
+                outbox
+                    .save(&client, update, offline_mode)
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            Message::Saved,
+        )
+    }
+
+    fn clear_selection(&mut self) {
+        self.selected.clear();
+        self.confirming_delete = false;
+    }
+
+    fn visible_items(&self) -> Vec<&ListItem> {
+        self.list.as_ref().map_or_else(Vec::new, |list| {
+            list.view(&self.tab, &self.filter, self.sort, self.title_language)
+        })
+    }
+
+    // The table layout has no covers
+    fn load_covers(&self) -> Command<Message> {
+        if self.layout() == ListLayout::Table {
+            return Command::none();
+        }
+
+        let urls: Vec<String> = self
+            .visible_items()
+            .into_iter()
+            .filter_map(|item| item.cover_url.clone())
+            .collect();
+        self.images.load_all(urls, Message::ImageLoaded)
+    }
+
+    fn progress_label(&self, item: &ListItem) -> String {
+        let total = item
+            .total
+            .map_or_else(|| "?".to_string(), |total| total.to_string());
+        match self.media_type {
+            MediaType::ANIME => format!("{}/{}", item.progress, total),
+            MediaType::MANGA if item.progress_volumes > 0 => format!(
+                "{}/{} · Vol. {}",
+                item.progress, total, item.progress_volumes
+            ),
+            MediaType::MANGA => format!("{}/{}", item.progress, total),
+        }
+    }
+
+    fn score_label(&self, item: &ListItem) -> String {
+        if item.score > 0.0 {
+            self.score_format.display(item.score)
+        } else {
+            "-".to_string()
+        }
+    }
+
+    fn format_label(item: &ListItem) -> String {
+        let format = item.format.as_deref().and_then(|format| {
+            MediaFormat::for_media_type(MediaType::ANIME)
+                .iter()
+                .chain(MediaFormat::for_media_type(MediaType::MANGA))
+                .find(|f| format!("{:?}", f) == format)
+        });
+        format.map_or_else(|| "-".to_string(), |f| f.to_string())
+    }
+
+    fn open_button<'a>(
+        &self,
+        content: impl Into<Element<'a, Message>>,
+        item: &ListItem,
+    ) -> Element<'a, Message> {
+        button(content)
+            .on_press(Message::MediaSelected(item.media_id, self.media_type))
+            .style(iced::theme::Button::Text)
+            .padding(0)
+            .into()
+    }
+
+    fn select_box(&self, item: &ListItem) -> Element<'_, Message> {
+        let media_id = item.media_id;
+        checkbox("", self.selected.contains(&media_id), move |selected| {
+            Message::Selected(media_id, selected)
+        })
+        .into()
+    }
+
+    // -1 and +1 around the progress, stopping at zero and at the last episode
+    fn progress_controls(&self, item: &ListItem) -> Element<'_, Message> {
+        let step = |label: &str, step: i32, enabled: bool| {
+            let button = button(text(label).size(12))
+                .padding([2, 6])
+                .style(iced::theme::Button::Secondary);
+            if enabled {
+                button.on_press(Message::ProgressStepped(item.media_id, step))
+            } else {
+                button
+            }
+        };
+
+        row![
+            step("-1", -1, item.progress > 0),
+            text(self.progress_label(item)).size(14),
+            step(
+                "+1",
+                1,
+                item.total.is_none_or(|total| item.progress < total)
+            ),
+        ]
+        .spacing(6)
+        .align_items(Alignment::Center)
+        .into()
+    }
+
+    fn grid_view(&self, items: &[&ListItem]) -> Element<'_, Message> {
+        let mut grid = column![].spacing(20);
+
+        for chunk in items.chunks(GRID_COLUMNS) {
+            let mut cards = row![].spacing(15);
+            for item in chunk {
+                let cover = column![
+                    image_or_placeholder(
+                        item.cover_url.as_ref().and_then(|url| self.images.get(url)),
+                        "",
+                        Length::Fixed(120.0),
+                        Length::Fixed(170.0),
+                    ),
+                    text(item.title.preferred(self.title_language))
+                        .size(14)
+                        .width(Length::Fill)
+                        .horizontal_alignment(iced::alignment::Horizontal::Center),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center);
+
+                cards = cards.push(
+                    column![
+                        self.select_box(item),
+                        self.open_button(cover, item),
+                        self.progress_controls(item),
+                    ]
+                    .spacing(5)
+                    .width(Length::Fixed(130.0))
+                    .align_items(Alignment::Center),
+                );
+            }
+            grid = grid.push(cards);
+        }
+
+        grid.into()
+    }
+
+    fn table_view(&self, items: &[&ListItem]) -> Element<'_, Message> {
+        let cell =
+            |label: String, portion: u16| text(label).size(14).width(Length::FillPortion(portion));
+
+        let mut table = column![row![
+            container(text("")).width(Length::Fixed(20.0)),
+            cell("Title".to_string(), 6),
+            cell("Score".to_string(), 1),
+            cell("Progress".to_string(), 3),
+            cell("Format".to_string(), 1),
+            cell("Year".to_string(), 1),
+        ]
+        .spacing(10)]
+        .spacing(6);
+
+        for item in items {
+            table = table.push(
+                row![
+                    container(self.select_box(item)).width(Length::Fixed(20.0)),
+                    container(self.open_button(
+                        text(item.title.preferred(self.title_language)).size(14),
+                        item
+                    ))
+                    .width(Length::FillPortion(6)),
+                    cell(self.score_label(item), 1),
+                    container(self.progress_controls(item)).width(Length::FillPortion(3)),
+                    cell(Self::format_label(item), 1),
+                    cell(
+                        item.year.map_or_else(|| "-".to_string(), |y| y.to_string()),
+                        1
+                    ),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            );
+        }
+
+        table.into()
+    }
+
+    fn detailed_view(&self, items: &[&ListItem]) -> Element<'_, Message> {
+        let now = Utc::now().timestamp();
+        let mut list = column![].spacing(10);
+
+        for item in items {
+            let mut facts = vec![Self::format_label(item)];
+            if let Some(year) = item.year {
+                facts.push(year.to_string());
+            }
+            if !item.genres.is_empty() {
+                facts.push(item.genres.join(", "));
+            }
+
+            let mut details = column![
+                self.open_button(
+                    text(item.title.preferred(self.title_language)).size(18),
+                    item
+                ),
+                text(facts.join(" · ")).size(13),
+                row![
+                    text("Progress").size(14),
+                    self.progress_controls(item),
+                    text(format!("Score {}", self.score_label(item))).size(14),
+                    text(format!("Updated {}", time_ago(item.updated_at, now))).size(14),
+                ]
+                .spacing(20)
+                .align_items(Alignment::Center),
+            ]
+            .spacing(5)
+            .width(Length::Fill);
+
+            if let (Some(airing_at), Some(episode)) = (item.next_airing_at, item.next_episode) {
+                details = details.push(
+                    text(format!(
+                        "Episode {} {}",
+                        episode,
+                        countdown(airing_at - now)
+                    ))
+                    .size(13),
+                );
+            }
+
+            list = list.push(
+                container(
+                    row![
+                        self.select_box(item),
+                        image_or_placeholder(
+                            item.cover_url.as_ref().and_then(|url| self.images.get(url)),
+                            "",
+                            Length::Fixed(60.0),
+                            Length::Fixed(85.0),
+                        ),
+                        details,
+                    ]
+                    .spacing(15)
+                    .align_items(Alignment::Center),
+                )
+                .padding(10)
+                .width(Length::Fill)
+                .style(iced::theme::Container::Box),
+            );
+        }
+
+        list.into()
+    }
+
+    fn score_slider(&self, score: f64, on_change: fn(f32) -> Message) -> Element<'_, Message> {
+        let format = self.score_format;
+        row![
+            slider(
+                0.0..=format.max() as f32,
+                format.from_raw(score) as f32,
+                on_change
+            )
+            .step(format.step() as f32)
+            .width(Length::Fixed(150.0)),
+            text(format.display(score)).size(14),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .into()
+    }
+
+    fn completion_view(&self, completion: &Completion) -> Option<Element<'_, Message>> {
+        let item = self.list.as_ref()?.item(completion.media_id)?;
+
+        let prompt = row![
+            text(format!(
+                "You finished {}. Mark it as completed?",
+                item.title.preferred(self.title_language)
+            ))
+            .size(16)
+            .width(Length::Fill),
+            text("Score").size(14),
+            self.score_slider(completion.score, Message::CompletionScoreChanged),
+            button(text("Complete").size(14))
+                .on_press(Message::CompleteEntry)
+                .padding(5),
+            button(text("Not now").size(14))
+                .on_press(Message::DismissCompletion)
+                .padding(5)
+                .style(iced::theme::Button::Secondary),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        Some(
+            container(prompt)
+                .padding(10)
+                .width(Length::Fill)
+                .style(iced::theme::Container::Box)
+                .into(),
+        )
+    }
+
+    fn bulk_bar(&self, list: &MyList) -> Element<'_, Message> {
+        let count = self.selected.len();
+        let mut bar = row![
+            text(format!("{} selected", count)).size(14),
+            button(text("Select all").size(14))
+                .on_press(Message::SelectAll)
+                .padding(5)
+                .style(iced::theme::Button::Secondary),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+        if count == 0 {
+            return bar.into();
+        }
+
+        let statuses: Vec<StatusOption> = STATUSES
+            .iter()
+            .map(|status| StatusOption {
+                status,
+                label: status_label(status, self.media_type),
+            })
+            .collect();
+        bar = bar
+            .push(
+                button(text("Clear").size(14))
+                    .on_press(Message::ClearSelection)
+                    .padding(5)
+                    .style(iced::theme::Button::Secondary),
+            )
+            .push(
+                pick_list(statuses, None, |option: StatusOption| {
+                    Message::ApplyBulk(BulkEdit::Status(option.status.to_string()))
+                })
+                .placeholder("Set status"),
+            )
+            .push(self.score_slider(self.bulk_score, Message::BulkScoreChanged))
+            .push(
+                button(text("Set score").size(14))
+                    .on_press(Message::ApplyBulk(BulkEdit::Score(self.bulk_score)))
+                    .padding(5),
+            );
+
+        if !list.custom_lists.is_empty() {
+            // From a custom list tab entries move out of it, elsewhere they are added
+            let from = match &self.tab {
+                ListTab::Custom(name) => Some(name.clone()),
+                ListTab::Status(_) => None,
+            };
+            let label = if from.is_some() {
+                "Move to list"
+            } else {
+                "Add to list"
+            };
+            bar = bar.push(
+                pick_list(list.custom_lists.clone(), None, move |to| {
+                    Message::ApplyBulk(BulkEdit::MoveToList {
+                        from: from.clone(),
+                        to,
+                    })
+                })
+                .placeholder(label),
+            );
+        }
+
+        bar = if self.confirming_delete {
+            bar.push(text(format!("Delete {} entries?", count)).size(14))
+                .push(
+                    button(text("Delete").size(14))
+                        .on_press(Message::ApplyBulk(BulkEdit::Delete))
+                        .padding(5)
+                        .style(iced::theme::Button::Destructive),
+                )
+                .push(
+                    button(text("Cancel").size(14))
+                        .on_press(Message::CancelDelete)
+                        .padding(5)
+                        .style(iced::theme::Button::Secondary),
+                )
+        } else {
+            bar.push(
+                button(text("Delete").size(14))
+                    .on_press(Message::DeleteSelected)
+                    .padding(5)
+                    .style(iced::theme::Button::Destructive),
+            )
+        };
+
+        // The actions can be wider than the window
+        scrollable(bar)
+            .horizontal_scroll(iced::widget::scrollable::Properties::default())
+            .into()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let toggle = |label: String, selected: bool, message: Message| {
+            button(text(label).size(14))
+                .on_press(message)
+                .padding(5)
+                .style(if selected {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                })
+        };
+
+        let mut header = row![
+            text("My List").size(28),
+            toggle(
+                "Anime".to_string(),
+                self.media_type == MediaType::ANIME,
+                Message::MediaTypeSelected(MediaType::ANIME)
+            ),
+            toggle(
+                "Manga".to_string(),
+                self.media_type == MediaType::MANGA,
+                Message::MediaTypeSelected(MediaType::MANGA)
+            ),
+            button(text("Refresh").size(14))
+                .on_press(Message::Refresh)
+                .padding(5),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+        if self.is_loading {
+            header = header.push(text("Syncing...").size(12));
+        }
+
+        let mut content = column![header].spacing(15).padding(20);
+
+        if let Some(error) = &self.error {
+            content = content.push(text(error).size(16).style(iced::theme::Text::Color(
+                iced::Color::from_rgb(0.8, 0.2, 0.2),
+            )));
+        }
+
+        if let Some(notice) = &self.notice {
+            content = content.push(text(notice).size(14));
+        }
+        if let Some(completion) = self
+            .completion
+            .as_ref()
+            .and_then(|c| self.completion_view(c))
+        {
+            content = content.push(completion);
+        }
+
+        let Some(list) = &self.list else {
+            if self.is_loading {
+                content = content.push(text("Loading your list...").size(18));
+            }
+            return scrollable(content).height(Length::Fill).into();
+        };
+
+        let mut tabs = row![].spacing(5);
+        for tab in list.tabs() {
+            let name = match &tab {
+                ListTab::Status(status) => status_label(status, self.media_type).to_string(),
+                ListTab::Custom(name) => name.clone(),
+            };
+            tabs = tabs.push(toggle(
+                format!("{} ({})", name, list.count(&tab)),
+                tab == self.tab,
+                Message::TabSelected(tab),
+            ));
+        }
+        // Custom lists can make the tabs wider than the window
+        content = content.push(
+            scrollable(tabs).horizontal_scroll(iced::widget::scrollable::Properties::default()),
+        );
+
+        let genres: Vec<GenreChoice> = std::iter::once(GenreChoice(None))
+            .chain(list.genres().into_iter().map(|g| GenreChoice(Some(g))))
+            .collect();
+        let filters = row![
+            text_input("Filter by title...", &self.filter.text)
+                .on_input(Message::SearchChanged)
+                .padding(5)
+                .width(Length::Fixed(200.0)),
+            text("Format").size(14),
+            pick_list(
+                Choice::options(MediaFormat::for_media_type(self.media_type)),
+                Some(Choice::from_option(self.filter.format)),
+                Message::FormatSelected,
+            ),
+            text("Genre").size(14),
+            pick_list(
+                genres,
+                Some(GenreChoice(self.filter.genre.clone())),
+                Message::GenreSelected,
+            ),
+            text("Year").size(14),
+            pick_list(
+                Choice::options(&list.years()),
+                Some(Choice::from_option(self.filter.year)),
+                Message::YearSelected,
+            ),
+            button(text("Clear").size(14))
+                .on_press(Message::ClearFilters)
+                .padding(5)
+                .style(iced::theme::Button::Secondary),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let layout = self.layout();
+        let mut display = row![
+            text("Sort").size(14),
+            pick_list(&ListSort::ALL[..], Some(self.sort), Message::SortSelected),
+            text("|").size(14),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+        for option in ListLayout::ALL {
+            display = display.push(toggle(
+                option.to_string(),
+                option == layout,
+                Message::LayoutSelected(option),
+            ));
+        }
+
+        content = content.push(filters).push(display);
+        if self.is_partial {
+            content = content.push(
+                text(
+                    "Showing your stored list, formats, years and custom lists appear once synced",
+                )
+                .size(12),
+            );
+        }
+
+        let items = self.visible_items();
+        if !items.is_empty() {
+            content = content.push(self.bulk_bar(list));
+        }
+        if items.is_empty() {
+            content = content.push(text("Nothing here").size(16));
+        } else {
+            content = content.push(match layout {
+                ListLayout::Grid => self.grid_view(&items),
+                ListLayout::Table => self.table_view(&items),
+                ListLayout::Detailed => self.detailed_view(&items),
+            });
+        }
+
+        scrollable(content).height(Length::Fill).into()
+    }
+}
@@ -0,0 +1,254 @@
+use chrono::Utc;
+use iced::widget::{button, column, row, scrollable, text};
+use iced::{Alignment, Command, Element, Length};
+
+use crate::api::client::AniListClient;
+use crate::api::models::{
+    Notification, NotificationGroup, NotificationLink, NotificationType, TitleLanguage,
+};
+use crate::utils::text::time_ago;
+
+const PER_PAGE: i32 = 25;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Load,
+    GroupSelected(Option<NotificationGroup>),
+    LoadMore,
+    ResultsReceived(u64, i32, Result<(Vec<Notification>, bool), String>),
+    // Handled by the parent component
+    Open(NotificationLink),
+}
+
+pub struct NotificationsScreen {
+    client: AniListClient,
+    title_language: TitleLanguage,
+    // None shows every group
+    group: Option<NotificationGroup>,
+    notifications: Vec<Notification>,
+    page: i32,
+    has_next_page: bool,
+    // Bumped on every new query so late responses for an old one are dropped
+    generation: u64,
+    is_loading: bool,
+    error: Option<String>,
+}
+
+impl NotificationsScreen {
+    pub fn new(client: AniListClient) -> Self {
+        Self {
+            client,
+            title_language: TitleLanguage::default(),
+            group: None,
+            notifications: Vec::new(),
+            page: 1,
+            has_next_page: false,
+            generation: 0,
+            is_loading: false,
+            error: None,
+        }
+    }
+
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    // Notifications come in all the time, so reload on every visit
+    pub fn init(&mut self) -> Command<Message> {
+        self.update(Message::Load)
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Load => {
+                self.notifications.clear();
+                self.page = 1;
+                self.generation += 1;
+                self.is_loading = true;
+                self.error = None;
+                self.fetch()
+            }
+            Message::GroupSelected(group) => {
+                self.group = group;
+                self.update(Message::Load)
+            }
+            Message::LoadMore => {
+                if self.is_loading || !self.has_next_page {
+                    return Command::none();
+                }
+
+                self.page += 1;
+                self.is_loading = true;
+                self.fetch()
+            }
+            Message::ResultsReceived(generation, _, result) => {
+                if generation != self.generation {
+                    return Command::none();
+                }
+                self.is_loading = false;
+
+                match result {
+                    Ok((notifications, has_next_page)) => {
+                        self.notifications.extend(notifications);
+                        self.has_next_page = has_next_page;
+                    }
+                    Err(e) => {
+                        self.error = Some(format!("Failed to load notifications: {}", e));
+                    }
+                }
+
+                Command::none()
+            }
+            Message::Open(_) => {
+                // This will be handled by the parent component
+                Command::none()
+            }
+        }
+    }
+
+    fn fetch(&self) -> Command<Message> {
+        let client = self.client.clone();
+        let types: Vec<NotificationType> = match self.group {
+            Some(group) => group.types().to_vec(),
+            None => NotificationGroup::ALL
+                .iter()
+                .flat_map(|group| group.types())
+                .copied()
+                .collect(),
+        };
+        let page = self.page;
+        let generation = self.generation;
+
+        Command::perform(
+            async move {
+                if !client.is_authenticated().await {
+                    return Err("Log in to see your notifications".to_string());
+                }
+
+                // Opening the inbox reads everything in it
+                client
+                    .get_notifications(&types, Some(page), Some(PER_PAGE), page == 1)
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            move |result| Message::ResultsReceived(generation, page, result),
+        )
+    }
+
+    fn notification_row(&self, notification: &Notification, now: i64) -> Element<'_, Message> {
+        let message = text(notification.message(self.title_language)).size(16);
+        let headline: Element<Message> = match notification.link() {
+            Some(link) => button(message)
+                .on_press(Message::Open(link))
+                .style(iced::theme::Button::Text)
+                .padding(0)
+                .into(),
+            None => message.into(),
+        };
+
+        let mut details = row![text(time_ago(notification.created_at, now)).size(12)]
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        // Activity notifications open the activity, so link the user separately
+        if notification.notification_type.group() == NotificationGroup::Activity {
+            if let Some(user) = &notification.user {
+                details = details.push(
+                    button(text(format!("View {}'s profile", user)).size(12))
+                        .on_press(Message::Open(NotificationLink::User(user.clone())))
+                        .style(iced::theme::Button::Text)
+                        .padding(0),
+                );
+            }
+        }
+
+        let mut item = column![headline, details].spacing(4);
+        if let Some(reason) = notification.reason.as_deref().filter(|r| !r.is_empty()) {
+            item = item.push(text(format!("Reason: {}", reason)).size(12));
+        }
+
+        item.into()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let mut filters = row![button(text("All").size(14))
+            .on_press(Message::GroupSelected(None))
+            .padding(5)
+            .style(if self.group.is_none() {
+                iced::theme::Button::Primary
+            } else {
+                iced::theme::Button::Secondary
+            })]
+        .spacing(5)
+        .align_items(Alignment::Center);
+        for group in NotificationGroup::ALL {
+            filters = filters.push(
+                button(text(group.to_string()).size(14))
+                    .on_press(Message::GroupSelected(Some(group)))
+                    .padding(5)
+                    .style(if self.group == Some(group) {
+                        iced::theme::Button::Primary
+                    } else {
+                        iced::theme::Button::Secondary
+                    }),
+            );
+        }
+
+        let header = row![
+            text("Notifications").size(28),
+            button(text("Refresh")).on_press(Message::Load).padding(5),
+        ]
+        .spacing(20)
+        .align_items(Alignment::Center);
+
+        let mut content = column![header, filters].spacing(20).padding(20);
+
+        if let Some(error) = &self.error {
+            content = content.push(text(error).size(16).style(iced::theme::Text::Color(
+                iced::Color::from_rgb(0.8, 0.2, 0.2),
+            )));
+        }
+
+        if self.is_loading && self.notifications.is_empty() {
+            content = content.push(text("Loading notifications...").size(18));
+        } else if self.notifications.is_empty() && self.error.is_none() {
+            content = content.push(text("No notifications").size(18));
+        }
+
+        let now = Utc::now().timestamp();
+        let groups = match self.group {
+            Some(group) => vec![group],
+            None => NotificationGroup::ALL.to_vec(),
+        };
+        for group in groups {
+            let mut in_group = self
+                .notifications
+                .iter()
+                .filter(|n| n.notification_type.group() == group)
+                .peekable();
+            if in_group.peek().is_none() {
+                continue;
+            }
+
+            let mut section = column![text(group.to_string()).size(20)].spacing(12);
+            for notification in in_group {
+                section = section.push(self.notification_row(notification, now));
+            }
+            content = content.push(section);
+        }
+
+        if self.has_next_page {
+            content = content.push(
+                button(text(if self.is_loading {
+                    "Loading..."
+                } else {
+                    "Load more"
+                }))
+                .on_press(Message::LoadMore)
+                .padding(10),
+            );
+        }
+
+        scrollable(content).height(Length::Fill).into()
+    }
+}
@@ -1,8 +1,17 @@
-use crate::utils::ui_helpers::{row_is_empty, RowExt};
-use iced::widget::{column, container, row, scrollable, text};
+use iced::widget::{button, column, container, row, scrollable, text};
 use iced::{Command, Element, Length};
 
 use crate::api::client::AniListClient;
+use crate::api::compare::{compare_anime_lists, ListComparison};
+use crate::api::models::{
+    Activity, ActivityQuery, FollowList, FollowUser, MediaTitle, MediaType, ScoreFormat,
+    TitleLanguage,
+};
+use crate::ui::components::activity_card::{ActivityCard, Message as CardMessage};
+use crate::ui::images::{image_or_placeholder, ImageLoader};
+
+const RECENT_ACTIVITY: i32 = 10;
+const FOLLOWS_PER_PAGE: i32 = 50;
 
 #[derive(Debug, Clone)]
 pub struct UserProfile {
@@ -11,6 +20,9 @@ pub struct UserProfile {
     pub about: Option<String>,
     pub avatar_url: String,
     pub banner_url: Option<String>,
+    // Whether the viewer follows them, and whether they follow the viewer
+    pub is_following: bool,
+    pub is_follower: bool,
     pub anime_count: i32,
     pub anime_mean_score: f32,
     pub anime_minutes_watched: i32,
@@ -25,7 +37,7 @@ pub struct UserProfile {
 #[derive(Debug, Clone)]
 pub struct FavoriteItem {
     pub id: i32,
-    pub title: String,
+    pub title: MediaTitle,
     pub image_url: String,
 }
 
@@ -36,36 +48,105 @@ pub struct FavoriteCharacter {
     pub image_url: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileTab {
+    Overview,
+    Follows(FollowList),
+    Compare,
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
     LoadProfile(String),
     ProfileLoaded(Result<UserProfile, String>),
     FavoriteAnimeSelected(i32),
     FavoriteMangaSelected(i32),
+    FavoriteCharacterSelected(i32),
+    ActivityLoaded(i32, Result<Vec<Activity>, String>),
+    Card(CardMessage),
+    UserSelected(String),
+    MediaSelected(i32, MediaType),
+    ActivitySelected(i32),
+    ImageLoaded(Result<String, String>),
+    TabSelected(ProfileTab),
+    ToggleFollow,
+    FollowToggled(i32, Result<bool, String>),
+    FollowsLoaded(u64, Result<(Vec<FollowUser>, bool), String>),
+    LoadMoreFollows,
+    ComparisonLoaded(i32, Result<ListComparison, String>),
     Error(String),
 }
 
 pub struct ProfileScreen {
     client: AniListClient,
+    images: ImageLoader,
     username: Option<String>,
     profile: Option<UserProfile>,
+    title_language: TitleLanguage,
+    score_format: ScoreFormat,
+    // The logged in user, None when logged out
+    viewer_id: Option<i32>,
+    recent_activity: Vec<Activity>,
+    tab: ProfileTab,
+    is_toggling_follow: bool,
+    follows: Vec<FollowUser>,
+    follows_page: i32,
+    follows_has_next_page: bool,
+    // Bumped on every new follow list so late pages of an old one are dropped
+    follows_generation: u64,
+    is_loading_follows: bool,
+    comparison: Option<ListComparison>,
+    is_comparing: bool,
     is_loading: bool,
     error: Option<String>,
 }
 
 impl ProfileScreen {
-    pub fn new(client: AniListClient) -> Self {
+    pub fn new(client: AniListClient, images: ImageLoader) -> Self {
         Self {
             client,
+            images,
             username: None,
             profile: None,
+            title_language: TitleLanguage::default(),
+            score_format: ScoreFormat::default(),
+            viewer_id: None,
+            recent_activity: Vec::new(),
+            tab: ProfileTab::Overview,
+            is_toggling_follow: false,
+            follows: Vec::new(),
+            follows_page: 1,
+            follows_has_next_page: false,
+            follows_generation: 0,
+            is_loading_follows: false,
+            comparison: None,
+            is_comparing: false,
             is_loading: false,
             error: None,
         }
     }
 
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    pub fn set_score_format(&mut self, score_format: ScoreFormat) {
+        self.score_format = score_format;
+    }
+
+    pub fn set_viewer_id(&mut self, viewer_id: Option<i32>) {
+        self.viewer_id = viewer_id;
+    }
+
     pub fn load(&mut self, username: String) -> Command<Message> {
         self.username = Some(username.clone());
+        self.recent_activity.clear();
+        self.tab = ProfileTab::Overview;
+        self.follows.clear();
+        self.follows_generation += 1;
+        self.is_loading_follows = false;
+        self.comparison = None;
+        self.is_comparing = false;
         self.is_loading = true;
         self.error = None;
 
@@ -135,12 +216,8 @@ impl ProfileScreen {
                                                             let node = node.as_ref()?;
                                                             let title = node
                                                                 .title
-                                                                .as_ref()
-                                                                .and_then(|t| {
-                                                                    t.romaji
-                                                                        .clone()
-                                                                        .or(t.english.clone())
-                                                                })
+                                                                .clone()
+                                                                .map(MediaTitle::from)
                                                                 .unwrap_or_default();
 
                                                             let image_url = node
@@ -180,12 +257,8 @@ impl ProfileScreen {
                                                             let node = node.as_ref()?;
                                                             let title = node
                                                                 .title
-                                                                .as_ref()
-                                                                .and_then(|t| {
-                                                                    t.romaji
-                                                                        .clone()
-                                                                        .or(t.english.clone())
-                                                                })
+                                                                .clone()
+                                                                .map(MediaTitle::from)
                                                                 .unwrap_or_default();
 
                                                             let image_url = node
@@ -257,6 +330,8 @@ impl ProfileScreen {
                                         about: user.about,
                                         avatar_url,
                                         banner_url: user.banner_image,
+                                        is_following: user.is_following.unwrap_or(false),
+                                        is_follower: user.is_follower.unwrap_or(false),
                                         anime_count,
                                         anime_mean_score,
                                         anime_minutes_watched,
@@ -284,17 +359,188 @@ impl ProfileScreen {
 
                 match result {
                     Ok(profile) => {
+                        let user_id = profile.id;
+                        let urls: Vec<String> = std::iter::once(profile.avatar_url.clone())
+                            .chain(profile.banner_url.clone())
+                            .chain(profile.favorite_anime.iter().map(|f| f.image_url.clone()))
+                            .chain(profile.favorite_manga.iter().map(|f| f.image_url.clone()))
+                            .chain(
+                                profile
+                                    .favorite_characters
+                                    .iter()
+                                    .map(|c| c.image_url.clone()),
+                            )
+                            .collect();
                         self.profile = Some(profile);
                         self.error = None;
+                        Command::batch(vec![
+                            self.load_activity(user_id),
+                            self.images.load_all(urls, Message::ImageLoaded),
+                        ])
                     }
                     Err(e) => {
                         self.error = Some(format!("Failed to load profile: {}", e));
+                        Command::none()
+                    }
+                }
+            }
+            Message::ActivityLoaded(user_id, result) => {
+                // A response for a profile we have since navigated away from
+                if self.profile.as_ref().map(|p| p.id) != Some(user_id) {
+                    return Command::none();
+                }
+
+                match result {
+                    Ok(activities) => self.recent_activity = activities,
+                    Err(e) => log::warn!("Failed to load recent activity: {}", e),
+                }
+
+                Command::none()
+            }
+            // Propagate card clicks up to the parent
+            Message::Card(CardMessage::UserSelected(name)) => {
+                Command::perform(async move { name }, Message::UserSelected)
+            }
+            Message::Card(CardMessage::MediaSelected(id, media_type)) => {
+                Command::perform(async move { (id, media_type) }, |(id, media_type)| {
+                    Message::MediaSelected(id, media_type)
+                })
+            }
+            Message::Card(CardMessage::RepliesSelected(id) | CardMessage::ToggleLike(id)) => {
+                Command::perform(async move { id }, Message::ActivitySelected)
+            }
+            Message::ImageLoaded(result) => {
+                // The view picks loaded images up from the loader
+                if let Err(e) = result {
+                    log::debug!("Failed to load image: {}", e);
+                }
+                Command::none()
+            }
+            Message::TabSelected(tab) => {
+                self.tab = tab;
+                let Some(user_id) = self.profile.as_ref().map(|p| p.id) else {
+                    return Command::none();
+                };
+
+                match tab {
+                    ProfileTab::Overview => Command::none(),
+                    ProfileTab::Follows(list) => {
+                        self.follows.clear();
+                        self.follows_page = 1;
+                        self.follows_generation += 1;
+                        self.fetch_follows(user_id, list)
                     }
+                    ProfileTab::Compare => {
+                        let Some(viewer_id) = self.viewer_id else {
+                            self.error = Some("Log in to compare lists".to_string());
+                            return Command::none();
+                        };
+                        if self.comparison.is_some() || self.is_comparing {
+                            return Command::none();
+                        }
+                        self.is_comparing = true;
+
+                        let client = self.client.clone();
+                        Command::perform(
+                            async move {
+                                compare_anime_lists(&client, viewer_id, user_id)
+                                    .await
+                                    .map_err(|e| e.to_string())
+                            },
+                            move |result| Message::ComparisonLoaded(user_id, result),
+                        )
+                    }
+                }
+            }
+            Message::ToggleFollow => {
+                let Some(user_id) = self.profile.as_ref().map(|p| p.id) else {
+                    return Command::none();
+                };
+                if self.is_toggling_follow {
+                    return Command::none();
+                }
+                self.is_toggling_follow = true;
+
+                let client = self.client.clone();
+                Command::perform(
+                    async move {
+                        client
+                            .toggle_follow(user_id)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    move |result| Message::FollowToggled(user_id, result),
+                )
+            }
+            Message::FollowToggled(user_id, result) => {
+                self.is_toggling_follow = false;
+                let Some(profile) = self.profile.as_mut().filter(|p| p.id == user_id) else {
+                    return Command::none();
+                };
+
+                match result {
+                    Ok(is_following) => {
+                        profile.is_following = is_following;
+                        // Lists can only be compared with people the viewer follows
+                        if !is_following && self.tab == ProfileTab::Compare {
+                            self.tab = ProfileTab::Overview;
+                        }
+                    }
+                    Err(e) => self.error = Some(format!("Failed to update follow: {}", e)),
+                }
+
+                Command::none()
+            }
+            Message::FollowsLoaded(generation, result) => {
+                if generation != self.follows_generation {
+                    return Command::none();
+                }
+                self.is_loading_follows = false;
+
+                match result {
+                    Ok((users, has_next_page)) => {
+                        self.follows.extend(users);
+                        self.follows_has_next_page = has_next_page;
+                    }
+                    Err(e) => self.error = Some(format!("Failed to load follows: {}", e)),
+                }
+
+                Command::none()
+            }
+            Message::LoadMoreFollows => {
+                let ProfileTab::Follows(list) = self.tab else {
+                    return Command::none();
+                };
+                let Some(user_id) = self.profile.as_ref().map(|p| p.id) else {
+                    return Command::none();
+                };
+                if self.is_loading_follows || !self.follows_has_next_page {
+                    return Command::none();
+                }
+
+                self.follows_page += 1;
+                self.fetch_follows(user_id, list)
+            }
+            Message::ComparisonLoaded(user_id, result) => {
+                // A response for a profile we have since navigated away from
+                if self.profile.as_ref().map(|p| p.id) != Some(user_id) {
+                    return Command::none();
+                }
+                self.is_comparing = false;
+
+                match result {
+                    Ok(comparison) => self.comparison = Some(comparison),
+                    Err(e) => self.error = Some(format!("Failed to compare lists: {}", e)),
                 }
 
                 Command::none()
             }
-            Message::FavoriteAnimeSelected(_) | Message::FavoriteMangaSelected(_) => {
+            Message::FavoriteAnimeSelected(_)
+            | Message::FavoriteMangaSelected(_)
+            | Message::FavoriteCharacterSelected(_)
+            | Message::UserSelected(_)
+            | Message::MediaSelected(_, _)
+            | Message::ActivitySelected(_) => {
                 // These would be handled by the parent component
                 Command::none()
             }
@@ -305,7 +551,207 @@ impl ProfileScreen {
         }
     }
 
-    pub fn view(&self) -> Element<Message> {
+    fn fetch_follows(&mut self, user_id: i32, list: FollowList) -> Command<Message> {
+        self.is_loading_follows = true;
+
+        let client = self.client.clone();
+        let page = self.follows_page;
+        let generation = self.follows_generation;
+
+        Command::perform(
+            async move {
+                client
+                    .get_user_follows(user_id, list, Some(page), Some(FOLLOWS_PER_PAGE))
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            move |result| Message::FollowsLoaded(generation, result),
+        )
+    }
+
+    fn tab_button(&self, label: &str, tab: ProfileTab) -> Element<'_, Message> {
+        button(text(label).size(14))
+            .on_press(Message::TabSelected(tab))
+            .padding(5)
+            .style(if self.tab == tab {
+                iced::theme::Button::Primary
+            } else {
+                iced::theme::Button::Secondary
+            })
+            .into()
+    }
+
+    // Follow button and the tabs for the user's social graph
+    fn social_bar(&self, profile: &UserProfile) -> Element<'_, Message> {
+        let mut bar = row![
+            self.tab_button("Overview", ProfileTab::Overview),
+            self.tab_button("Followers", ProfileTab::Follows(FollowList::Followers)),
+            self.tab_button("Following", ProfileTab::Follows(FollowList::Following)),
+        ]
+        .spacing(5)
+        .align_items(iced::Alignment::Center);
+
+        // Nothing to follow or compare on the viewer's own profile
+        if self.viewer_id.is_none() || self.viewer_id == Some(profile.id) {
+            return bar.into();
+        }
+
+        if profile.is_following {
+            bar = bar.push(self.tab_button("Compare Lists", ProfileTab::Compare));
+        }
+        bar = bar.push(
+            button(text(if self.is_toggling_follow {
+                "Saving..."
+            } else if profile.is_following {
+                "Unfollow"
+            } else {
+                "Follow"
+            }))
+            .on_press(Message::ToggleFollow)
+            .padding(5),
+        );
+        if profile.is_follower {
+            bar = bar.push(text("Follows you").size(14));
+        }
+
+        bar.into()
+    }
+
+    fn follows_view(&self, list: FollowList) -> Element<'_, Message> {
+        let mut section = column![text(list.to_string()).size(20)].spacing(10);
+
+        if self.is_loading_follows && self.follows.is_empty() {
+            section = section.push(text("Loading...").size(16));
+        } else if self.follows.is_empty() {
+            section = section.push(text("Nobody yet").size(16));
+        }
+
+        for user in &self.follows {
+            let mut user_row = row![button(text(&user.name).size(16))
+                .on_press(Message::UserSelected(user.name.clone()))
+                .style(iced::theme::Button::Text)
+                .padding(0)]
+            .spacing(10)
+            .align_items(iced::Alignment::Center);
+            if user.is_following && self.viewer_id != Some(user.id) {
+                user_row = user_row.push(text("Following").size(12));
+            }
+            section = section.push(user_row);
+        }
+
+        if self.follows_has_next_page {
+            section = section.push(
+                button(text(if self.is_loading_follows {
+                    "Loading..."
+                } else {
+                    "Load more"
+                }))
+                .on_press(Message::LoadMoreFollows)
+                .padding(10),
+            );
+        }
+
+        section.into()
+    }
+
+    // A 0-100 score difference in the viewer's score format
+    fn score_difference(&self, difference: f64) -> String {
+        format!("{:+.1}", difference * self.score_format.max() / 100.0)
+    }
+
+    fn compare_view(&self, profile: &UserProfile) -> Element<'_, Message> {
+        let Some(comparison) = &self.comparison else {
+            let status = if self.is_comparing {
+                "Comparing lists..."
+            } else {
+                "Lists could not be compared"
+            };
+            return text(status).size(16).into();
+        };
+
+        let mut summary =
+            column![text(format!("{} titles in common", comparison.shared.len())).size(16)]
+                .spacing(5);
+        if let Some(mean) = comparison.mean_difference() {
+            summary = summary.push(
+                text(format!(
+                    "On average {} scores them {} compared to you",
+                    profile.name,
+                    self.score_difference(mean)
+                ))
+                .size(14),
+            );
+        }
+
+        let mut shared = column![text("Shared Titles").size(20)].spacing(8);
+        for title in &comparison.shared {
+            let mut scores = format!(
+                "You: {} · {}: {}",
+                self.score_format.display(title.our_score),
+                profile.name,
+                self.score_format.display(title.their_score)
+            );
+            if let Some(difference) = title.difference() {
+                scores.push_str(&format!(" ({})", self.score_difference(difference)));
+            }
+            shared = shared.push(
+                row![
+                    button(text(title.title.preferred(self.title_language)).size(14))
+                        .on_press(Message::MediaSelected(title.media_id, MediaType::ANIME))
+                        .style(iced::theme::Button::Text)
+                        .padding(0)
+                        .width(Length::FillPortion(3)),
+                    text(scores).size(14).width(Length::FillPortion(2)),
+                ]
+                .spacing(10),
+            );
+        }
+
+        let mut recommendations =
+            column![text(format!("Rated Highly by {}, Not Seen by You", profile.name)).size(20)]
+                .spacing(8);
+        if comparison.recommendations.is_empty() {
+            recommendations = recommendations.push(text("Nothing new to suggest").size(14));
+        }
+        for entry in &comparison.recommendations {
+            recommendations = recommendations.push(
+                row![
+                    button(text(entry.title.preferred(self.title_language)).size(14))
+                        .on_press(Message::MediaSelected(entry.media_id, MediaType::ANIME))
+                        .style(iced::theme::Button::Text)
+                        .padding(0)
+                        .width(Length::FillPortion(3)),
+                    text(self.score_format.display(entry.score))
+                        .size(14)
+                        .width(Length::FillPortion(2)),
+                ]
+                .spacing(10),
+            );
+        }
+
+        column![summary, recommendations, shared].spacing(20).into()
+    }
+
+    fn load_activity(&self, user_id: i32) -> Command<Message> {
+        let client = self.client.clone();
+        let query = ActivityQuery {
+            user_id: Some(user_id),
+            ..Default::default()
+        };
+
+        Command::perform(
+            async move {
+                client
+                    .get_activities(&query, Some(1), Some(RECENT_ACTIVITY))
+                    .await
+                    .map(|(activities, _)| activities)
+                    .map_err(|e| e.to_string())
+            },
+            move |result| Message::ActivityLoaded(user_id, result),
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
         if self.is_loading {
             let username = self.username.as_deref().unwrap_or("user");
 
@@ -338,20 +784,29 @@ impl ProfileScreen {
                 );
             }
 
+            if let Some(banner) = &profile.banner_url {
+                content = content.push(image_or_placeholder(
+                    self.images.get(banner),
+                    "",
+                    Length::Fill,
+                    Length::Fixed(160.0),
+                ));
+            }
+
             // User header
             let header = row![
-                // Avatar image (placeholder for now)
-                container(text("Avatar"))
-                    .width(Length::Fixed(100.0))
-                    .height(Length::Fixed(100.0))
-                    .center_x()
-                    .center_y()
-                    .style(iced::theme::Container::Box),
+                image_or_placeholder(
+                    self.images.get(&profile.avatar_url),
+                    "Avatar",
+                    Length::Fixed(100.0),
+                    Length::Fixed(100.0),
+                ),
                 // User info
                 column![
                     text(&profile.name).size(30),
                     if let Some(about) = &profile.about {
-                        container(text(about).size(14)).into()
+                        let about_text: Element<Message> = container(text(about).size(14)).into();
+                        about_text
                     } else {
                         container(text("")).into()
                     },
@@ -361,7 +816,19 @@ impl ProfileScreen {
             ]
             .spacing(20);
 
-            content = content.push(header);
+            content = content.push(header).push(self.social_bar(profile));
+
+            match self.tab {
+                ProfileTab::Overview => {}
+                ProfileTab::Follows(list) => {
+                    content = content.push(self.follows_view(list));
+                    return scrollable(content).height(Length::Fill).into();
+                }
+                ProfileTab::Compare => {
+                    content = content.push(self.compare_view(profile));
+                    return scrollable(content).height(Length::Fill).into();
+                }
+            }
 
             // Statistics section
             let stats_section = column![
@@ -406,13 +873,13 @@ impl ProfileScreen {
 
                 for (i, favorite) in profile.favorite_anime.iter().enumerate().take(6) {
                     let favorite_card = column![
-                        // Image placeholder
-                        container(text(""))
-                            .width(Length::Fixed(80.0))
-                            .height(Length::Fixed(120.0))
-                            .center_x()
-                            .style(iced::theme::Container::Box),
-                        text(&favorite.title)
+                        image_or_placeholder(
+                            self.images.get(&favorite.image_url),
+                            "",
+                            Length::Fixed(80.0),
+                            Length::Fixed(120.0),
+                        ),
+                        text(favorite.title.preferred(self.title_language))
                             .size(14)
                             .width(Length::Fill)
                             .horizontal_alignment(iced::alignment::Horizontal::Center),
@@ -421,7 +888,11 @@ impl ProfileScreen {
                     .width(Length::Fixed(100.0))
                     .align_items(iced::Alignment::Center);
 
-                    row_content = row_content.push(favorite_card);
+                    row_content = row_content.push(
+                        button(favorite_card)
+                            .on_press(Message::FavoriteAnimeSelected(favorite.id))
+                            .style(iced::theme::Button::Text),
+                    );
 
                     // Create a new row after every 6 items
                     if (i + 1) % 6 == 0 && i > 0 {
@@ -445,13 +916,13 @@ impl ProfileScreen {
 
                 for (i, favorite) in profile.favorite_manga.iter().enumerate().take(6) {
                     let favorite_card = column![
-                        // Image placeholder
-                        container(text(""))
-                            .width(Length::Fixed(80.0))
-                            .height(Length::Fixed(120.0))
-                            .center_x()
-                            .style(iced::theme::Container::Box),
-                        text(&favorite.title)
+                        image_or_placeholder(
+                            self.images.get(&favorite.image_url),
+                            "",
+                            Length::Fixed(80.0),
+                            Length::Fixed(120.0),
+                        ),
+                        text(favorite.title.preferred(self.title_language))
                             .size(14)
                             .width(Length::Fill)
                             .horizontal_alignment(iced::alignment::Horizontal::Center),
@@ -460,7 +931,11 @@ impl ProfileScreen {
                     .width(Length::Fixed(100.0))
                     .align_items(iced::Alignment::Center);
 
-                    row_content = row_content.push(favorite_card);
+                    row_content = row_content.push(
+                        button(favorite_card)
+                            .on_press(Message::FavoriteMangaSelected(favorite.id))
+                            .style(iced::theme::Button::Text),
+                    );
 
                     // Create a new row after every 6 items
                     if (i + 1) % 6 == 0 && i > 0 {
@@ -485,12 +960,12 @@ impl ProfileScreen {
 
                 for (i, character) in profile.favorite_characters.iter().enumerate().take(6) {
                     let favorite_card = column![
-                        // Image placeholder
-                        container(text(""))
-                            .width(Length::Fixed(80.0))
-                            .height(Length::Fixed(120.0))
-                            .center_x()
-                            .style(iced::theme::Container::Box),
+                        image_or_placeholder(
+                            self.images.get(&character.image_url),
+                            "",
+                            Length::Fixed(80.0),
+                            Length::Fixed(120.0),
+                        ),
                         text(&character.name)
                             .size(14)
                             .width(Length::Fill)
@@ -500,7 +975,11 @@ impl ProfileScreen {
                     .width(Length::Fixed(100.0))
                     .align_items(iced::Alignment::Center);
 
-                    row_content = row_content.push(favorite_card);
+                    row_content = row_content.push(
+                        button(favorite_card)
+                            .on_press(Message::FavoriteCharacterSelected(character.id))
+                            .style(iced::theme::Button::Text),
+                    );
 
                     // Create a new row after every 6 items
                     if (i + 1) % 6 == 0 && i > 0 {
@@ -515,6 +994,20 @@ impl ProfileScreen {
                 content = content.push(favorites_section);
             }
 
+            if !self.recent_activity.is_empty() {
+                let mut activity_section = column![text("Recent Activity").size(20)].spacing(10);
+                for activity in &self.recent_activity {
+                    activity_section = activity_section.push(
+                        ActivityCard::new(activity)
+                            .title_language(self.title_language)
+                            .show_actions(false)
+                            .view()
+                            .map(Message::Card),
+                    );
+                }
+                content = content.push(activity_section);
+            }
+
             scrollable(content).height(Length::Fill).into()
         } else if let Some(error) = &self.error {
             container(
@@ -1,16 +1,124 @@
-use iced::widget::{button, column, container, row, scrollable, text, text_input};
+use iced::widget::{button, column, container, pick_list, row, scrollable, text, text_input};
 use iced::{Alignment, Command, Element, Length};
 
 use crate::api::client::AniListClient;
+use crate::api::models::{
+    MediaFormat, MediaSeason, MediaSort, MediaStatus, MediaTitle, MediaType, SearchFilters,
+    TitleLanguage,
+};
+
+// AniList's genre collection, minus the adult-only one
+const GENRES: [&str; 18] = [
+    "Action",
+    "Adventure",
+    "Comedy",
+    "Drama",
+    "Ecchi",
+    "Fantasy",
+    "Horror",
+    "Mahou Shoujo",
+    "Mecha",
+    "Music",
+    "Mystery",
+    "Psychological",
+    "Romance",
+    "Sci-Fi",
+    "Slice of Life",
+    "Sports",
+    "Supernatural",
+    "Thriller",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Country {
+    code: &'static str,
+    name: &'static str,
+}
+
+impl std::fmt::Display for Country {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+const COUNTRIES: [Country; 4] = [
+    Country {
+        code: "JP",
+        name: "Japan",
+    },
+    Country {
+        code: "KR",
+        name: "South Korea",
+    },
+    Country {
+        code: "CN",
+        name: "China",
+    },
+    Country {
+        code: "TW",
+        name: "Taiwan",
+    },
+];
+
+// A pick list entry that can also clear the filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice<T> {
+    Any,
+    Only(T),
+}
+
+impl<T> Choice<T> {
+    pub fn options(values: &[T]) -> Vec<Choice<T>>
+    where
+        T: Copy,
+    {
+        std::iter::once(Choice::Any)
+            .chain(values.iter().copied().map(Choice::Only))
+            .collect()
+    }
+
+    pub fn from_option(value: Option<T>) -> Self {
+        value.map_or(Choice::Any, Choice::Only)
+    }
+
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Choice::Any => None,
+            Choice::Only(value) => Some(value),
+        }
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Choice<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Choice::Any => write!(f, "Any"),
+            Choice::Only(value) => value.fmt(f),
+        }
+    }
+}
+
+// Free-text filter inputs, parsed when the search runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    Tags,
+    YearFrom,
+    YearTo,
+    ScoreMin,
+    ScoreMax,
+    LengthMin,
+    LengthMax,
+}
 
 // Search result item
 #[derive(Debug, Clone)]
 pub struct SearchResult {
     pub id: i32,
-    pub title: String,
+    pub title: MediaTitle,
     pub image_url: String,
     pub format: String,
     pub episodes: Option<i32>,
+    pub chapters: Option<i32>,
     pub year: Option<i32>,
     pub score: Option<f64>,
 }
@@ -18,7 +126,17 @@ pub struct SearchResult {
 // Screen state
 #[derive(Debug, Clone)]
 pub struct SearchScreen {
-    query: String,
+    filters: SearchFilters,
+    show_filters: bool,
+    tags: String,
+    year_from: String,
+    year_to: String,
+    score_min: String,
+    score_max: String,
+    length_min: String,
+    length_max: String,
+    media_type: MediaType,
+    title_language: TitleLanguage,
     results: Vec<SearchResult>,
     page: i32,
     has_next_page: bool,
@@ -30,17 +148,42 @@ pub struct SearchScreen {
 #[derive(Debug, Clone)]
 pub enum Message {
     QueryChanged(String),
+    MediaTypeChanged(MediaType),
+    FiltersToggled,
+    GenreToggled(&'static str),
+    FilterTextChanged(FilterField, String),
+    SeasonSelected(Choice<MediaSeason>),
+    FormatToggled(MediaFormat),
+    StatusSelected(Choice<MediaStatus>),
+    CountrySelected(Choice<Country>),
+    OnListSelected(Option<bool>),
+    SortSelected(MediaSort),
+    ClearFilters,
     Search,
     LoadMore,
     ResultsReceived(Result<(Vec<SearchResult>, bool), String>),
     AnimeSelected(i32),
+    MangaSelected(i32),
     Error(String),
 }
 
 impl SearchScreen {
     pub fn new(client: AniListClient) -> Self {
         Self {
-            query: String::new(),
+            filters: SearchFilters {
+                sort: Some(MediaSort::POPULARITY_DESC),
+                ..Default::default()
+            },
+            show_filters: false,
+            tags: String::new(),
+            year_from: String::new(),
+            year_to: String::new(),
+            score_min: String::new(),
+            score_max: String::new(),
+            length_min: String::new(),
+            length_max: String::new(),
+            media_type: MediaType::ANIME,
+            title_language: TitleLanguage::default(),
             results: Vec::new(),
             page: 1,
             has_next_page: false,
@@ -50,15 +193,119 @@ impl SearchScreen {
         }
     }
 
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    // Takes effect from the next search
+    pub fn set_show_adult(&mut self, show_adult: bool) {
+        self.filters.include_adult = show_adult;
+    }
+
     pub fn update(&mut self, message: Message) -> Command<Message> {
         match message {
             Message::QueryChanged(query) => {
-                self.query = query;
+                self.filters.query = query;
+                Command::none()
+            }
+            Message::MediaTypeChanged(media_type) => {
+                if self.media_type == media_type {
+                    return Command::none();
+                }
+
+                self.media_type = media_type;
+                self.results.clear();
+                self.page = 1;
+                self.has_next_page = false;
+
+                // Anime formats don't apply to manga and vice versa
+                let formats = MediaFormat::for_media_type(media_type);
+                self.filters.formats.retain(|f| formats.contains(f));
+
+                // Re-run the current search against the other media type
+                if self.filters.query.trim().is_empty() && !self.filters.is_filtered() {
+                    Command::none()
+                } else {
+                    self.update(Message::Search)
+                }
+            }
+            Message::FiltersToggled => {
+                self.show_filters = !self.show_filters;
+                Command::none()
+            }
+            Message::GenreToggled(genre) => {
+                // Cycle: any -> included -> excluded -> any
+                let genre = genre.to_string();
+                if let Some(index) = self.filters.genres.iter().position(|g| *g == genre) {
+                    self.filters.genres.remove(index);
+                    self.filters.excluded_genres.push(genre);
+                } else if let Some(index) = self
+                    .filters
+                    .excluded_genres
+                    .iter()
+                    .position(|g| *g == genre)
+                {
+                    self.filters.excluded_genres.remove(index);
+                } else {
+                    self.filters.genres.push(genre);
+                }
+                Command::none()
+            }
+            Message::FilterTextChanged(field, value) => {
+                *self.filter_text_mut(field) = value;
+                Command::none()
+            }
+            Message::SeasonSelected(season) => {
+                self.filters.season = season.into_option();
+                Command::none()
+            }
+            Message::FormatToggled(format) => {
+                if let Some(index) = self.filters.formats.iter().position(|f| *f == format) {
+                    self.filters.formats.remove(index);
+                } else {
+                    self.filters.formats.push(format);
+                }
+                Command::none()
+            }
+            Message::StatusSelected(status) => {
+                self.filters.status = status.into_option();
+                Command::none()
+            }
+            Message::CountrySelected(country) => {
+                self.filters.country = country.into_option().map(|c| c.code.to_string());
+                Command::none()
+            }
+            Message::OnListSelected(on_list) => {
+                self.filters.on_list = on_list;
+                Command::none()
+            }
+            Message::SortSelected(sort) => {
+                self.filters.sort = Some(sort);
+                Command::none()
+            }
+            Message::ClearFilters => {
+                self.filters = SearchFilters {
+                    query: std::mem::take(&mut self.filters.query),
+                    sort: self.filters.sort,
+                    include_adult: self.filters.include_adult,
+                    ..Default::default()
+                };
+                for field in [
+                    FilterField::Tags,
+                    FilterField::YearFrom,
+                    FilterField::YearTo,
+                    FilterField::ScoreMin,
+                    FilterField::ScoreMax,
+                    FilterField::LengthMin,
+                    FilterField::LengthMax,
+                ] {
+                    self.filter_text_mut(field).clear();
+                }
                 Command::none()
             }
             Message::Search => {
-                if self.query.trim().is_empty() {
-                    self.error = Some("Please enter a search query".to_string());
+                if let Err(e) = self.parse_filter_text() {
+                    self.error = Some(e);
                     return Command::none();
                 }
 
@@ -97,7 +344,7 @@ impl SearchScreen {
 
                 Command::none()
             }
-            Message::AnimeSelected(_) => {
+            Message::AnimeSelected(_) | Message::MangaSelected(_) => {
                 // This will be handled by the parent component
                 Command::none()
             }
@@ -109,15 +356,70 @@ impl SearchScreen {
         }
     }
 
+    fn filter_text_mut(&mut self, field: FilterField) -> &mut String {
+        match field {
+            FilterField::Tags => &mut self.tags,
+            FilterField::YearFrom => &mut self.year_from,
+            FilterField::YearTo => &mut self.year_to,
+            FilterField::ScoreMin => &mut self.score_min,
+            FilterField::ScoreMax => &mut self.score_max,
+            FilterField::LengthMin => &mut self.length_min,
+            FilterField::LengthMax => &mut self.length_max,
+        }
+    }
+
+    // Copy the free-text inputs into the filters, rejecting anything malformed
+    fn parse_filter_text(&mut self) -> Result<(), String> {
+        let length = match self.media_type {
+            MediaType::ANIME => "Episodes",
+            MediaType::MANGA => "Chapters",
+        };
+
+        let year_from = parse_number("Year from", &self.year_from)?;
+        let year_to = parse_number("Year to", &self.year_to)?;
+        let score_min = parse_number("Minimum score", &self.score_min)?;
+        let score_max = parse_number("Maximum score", &self.score_max)?;
+        let length_min = parse_number(length, &self.length_min)?;
+        let length_max = parse_number(length, &self.length_max)?;
+
+        if let (Some(from), Some(to)) = (year_from, year_to) {
+            if from > to {
+                return Err("Year from must not be after year to".to_string());
+            }
+        }
+        if score_min.into_iter().chain(score_max).any(|s| s > 100) {
+            return Err("Scores range from 0 to 100".to_string());
+        }
+
+        self.filters.tags = self
+            .tags
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect();
+        self.filters.year_from = year_from;
+        self.filters.year_to = year_to;
+        self.filters.score_min = score_min;
+        self.filters.score_max = score_max;
+        self.filters.length_min = length_min;
+        self.filters.length_max = length_max;
+        Ok(())
+    }
+
     fn execute_search(&self) -> Command<Message> {
-        let query = self.query.clone();
+        let filters = self.filters.clone();
         let page = self.page;
+        let media_type = self.media_type;
         let client = self.client.clone();
 
         Command::perform(
             async move {
                 // Execute the search with pagination
-                match client.search_anime(query, Some(page), Some(25)).await {
+                match client
+                    .search_media_filtered(&filters, media_type, Some(page), Some(25))
+                    .await
+                {
                     Ok(data) => {
                         if let Some(page_data) = data.page {
                             // Extract pagination info
@@ -135,11 +437,8 @@ impl SearchScreen {
                                 .filter_map(|media_option| {
                                     let media = media_option?;
 
-                                    // Extract title
-                                    let title = media
-                                        .title
-                                        .and_then(|t| t.romaji.or(t.english))
-                                        .unwrap_or_else(|| "Unknown Title".to_string());
+                                    let title =
+                                        media.title.map(MediaTitle::from).unwrap_or_default();
 
                                     // Extract image URL
                                     let image_url = media
@@ -159,6 +458,7 @@ impl SearchScreen {
                                         image_url,
                                         format,
                                         episodes: media.episodes.map(|e| e as i32),
+                                        chapters: media.chapters.map(|c| c as i32),
                                         year: media.season_year.map(|y| y as i32),
                                         score: media.average_score.map(|s| s as f64),
                                     })
@@ -177,27 +477,68 @@ impl SearchScreen {
         )
     }
 
-    pub fn view(&self) -> Element<Message> {
+    pub fn view(&self) -> Element<'_, Message> {
+        let (noun, placeholder) = match self.media_type {
+            MediaType::ANIME => ("anime", "Search anime by title..."),
+            MediaType::MANGA => ("manga", "Search manga by title..."),
+        };
+
+        // Anime / Manga toggle
+        let type_button = |label: &'static str, media_type: MediaType| {
+            button(text(label))
+                .on_press(Message::MediaTypeChanged(media_type))
+                .padding(10)
+                .style(if self.media_type == media_type {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                })
+        };
+
         // Search bar
         let search_bar = row![
-            text_input("Search anime by title...", &self.query)
+            type_button("Anime", MediaType::ANIME),
+            type_button("Manga", MediaType::MANGA),
+            text_input(placeholder, &self.filters.query)
                 .padding(10)
-                .on_input(Message::QueryChanged),
+                .on_input(Message::QueryChanged)
+                .on_submit(Message::Search),
+            button(text(if self.show_filters {
+                "Hide Filters"
+            } else {
+                "Filters"
+            }))
+            .on_press(Message::FiltersToggled)
+            .padding(10)
+            .style(if self.filters.is_filtered() {
+                iced::theme::Button::Primary
+            } else {
+                iced::theme::Button::Secondary
+            }),
             button(text("Search")).on_press(Message::Search).padding(10),
         ]
         .spacing(10)
         .padding(10)
         .width(Length::Fill);
 
+        let filter_panel: Element<Message> = if self.show_filters {
+            self.filter_panel()
+        } else {
+            container(text("")).into()
+        };
+
         // Results area
         let mut results_column = column![].spacing(15).padding(10);
 
         if self.results.is_empty() && !self.is_loading && self.error.is_none() {
             results_column = results_column.push(
-                text("Search for anime to see results")
-                    .size(18)
-                    .width(Length::Fill)
-                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+                text(format!(
+                    "Search for {} or pick some filters to see results",
+                    noun
+                ))
+                .size(18)
+                .width(Length::Fill)
+                .horizontal_alignment(iced::alignment::Horizontal::Center),
             );
         } else {
             // Display results in a grid-like layout (rows of 4)
@@ -212,16 +553,21 @@ impl SearchScreen {
                             .height(Length::Fixed(180.0))
                             .center_x()
                             .center_y(),
-                        text(&result.title)
+                        text(result.title.preferred(self.title_language))
                             .size(16)
                             .width(Length::Fill)
                             .horizontal_alignment(iced::alignment::Horizontal::Center),
                         text(format!(
                             "{} {}",
                             result.format,
-                            result
-                                .episodes
-                                .map_or_else(String::new, |e| format!("• {} eps", e))
+                            match self.media_type {
+                                MediaType::ANIME => result
+                                    .episodes
+                                    .map_or_else(String::new, |e| format!("• {} eps", e)),
+                                MediaType::MANGA => result
+                                    .chapters
+                                    .map_or_else(String::new, |c| format!("• {} ch", c)),
+                            }
                         ))
                         .size(12)
                         .width(Length::Fill)
@@ -242,10 +588,13 @@ impl SearchScreen {
                     .align_items(Alignment::Center);
 
                     // Make the card clickable
-                    let result_id = result.id;
+                    let on_press = match self.media_type {
+                        MediaType::ANIME => Message::AnimeSelected(result.id),
+                        MediaType::MANGA => Message::MangaSelected(result.id),
+                    };
                     row_content = row_content.push(
                         button(result_card)
-                            .on_press(Message::AnimeSelected(result_id))
+                            .on_press(on_press)
                             .style(iced::theme::Button::Text)
                             .width(Length::Fixed(150.0)),
                     );
@@ -297,10 +646,172 @@ impl SearchScreen {
         };
 
         // Main content
-        let content = column![search_bar, error_display, loading_indicator, results_column,]
-            .spacing(10)
-            .width(Length::Fill);
+        let content = column![
+            search_bar,
+            filter_panel,
+            error_display,
+            loading_indicator,
+            results_column,
+        ]
+        .spacing(10)
+        .width(Length::Fill);
 
         scrollable(content).height(Length::Fill).into()
     }
+
+    fn filter_panel(&self) -> Element<'_, Message> {
+        let label = |content: &'static str| text(content).size(14).width(Length::Fixed(80.0));
+        let toggle_style = |active: bool| {
+            if active {
+                iced::theme::Button::Primary
+            } else {
+                iced::theme::Button::Secondary
+            }
+        };
+        let number_input = |placeholder: &'static str, field: FilterField, value: &String| {
+            text_input(placeholder, value)
+                .on_input(move |v| Message::FilterTextChanged(field, v))
+                .on_submit(Message::Search)
+                .padding(5)
+                .width(Length::Fixed(80.0))
+        };
+
+        // Genres: click once to include, twice to exclude, a third time to clear
+        let mut genres = column![label("Genres")].spacing(5);
+        for chunk in GENRES.chunks(6) {
+            let mut genre_row = row![].spacing(5);
+            for genre in chunk {
+                let (prefix, style) = if self.filters.genres.iter().any(|g| g == genre) {
+                    ("+ ", iced::theme::Button::Primary)
+                } else if self.filters.excluded_genres.iter().any(|g| g == genre) {
+                    ("− ", iced::theme::Button::Destructive)
+                } else {
+                    ("", iced::theme::Button::Secondary)
+                };
+                genre_row = genre_row.push(
+                    button(text(format!("{}{}", prefix, genre)).size(14))
+                        .on_press(Message::GenreToggled(genre))
+                        .padding(5)
+                        .style(style),
+                );
+            }
+            genres = genres.push(genre_row);
+        }
+
+        let tags = row![
+            label("Tags"),
+            text_input("Comma separated, e.g. Time Skip, Isekai", &self.tags)
+                .on_input(|v| Message::FilterTextChanged(FilterField::Tags, v))
+                .on_submit(Message::Search)
+                .padding(5),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let mut formats = row![label("Format")]
+            .spacing(5)
+            .align_items(Alignment::Center);
+        for format in MediaFormat::for_media_type(self.media_type) {
+            formats = formats.push(
+                button(text(format.to_string()).size(14))
+                    .on_press(Message::FormatToggled(*format))
+                    .padding(5)
+                    .style(toggle_style(self.filters.formats.contains(format))),
+            );
+        }
+
+        let country = COUNTRIES
+            .iter()
+            .copied()
+            .find(|c| self.filters.country.as_deref() == Some(c.code));
+        let when = row![
+            label("Year"),
+            number_input("From", FilterField::YearFrom, &self.year_from),
+            number_input("To", FilterField::YearTo, &self.year_to),
+            text("Season").size(14),
+            pick_list(
+                Choice::options(&MediaSeason::ALL),
+                Some(Choice::from_option(self.filters.season)),
+                Message::SeasonSelected,
+            ),
+            text("Status").size(14),
+            pick_list(
+                Choice::options(&MediaStatus::ALL),
+                Some(Choice::from_option(self.filters.status)),
+                Message::StatusSelected,
+            ),
+            text("Country").size(14),
+            pick_list(
+                Choice::options(&COUNTRIES),
+                Some(Choice::from_option(country)),
+                Message::CountrySelected,
+            ),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let length = match self.media_type {
+            MediaType::ANIME => "Episodes",
+            MediaType::MANGA => "Chapters",
+        };
+        let ranges = row![
+            label("Score"),
+            number_input("Min", FilterField::ScoreMin, &self.score_min),
+            number_input("Max", FilterField::ScoreMax, &self.score_max),
+            text(length).size(14),
+            number_input("Min", FilterField::LengthMin, &self.length_min),
+            number_input("Max", FilterField::LengthMax, &self.length_max),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let on_list_button = |content: &'static str, on_list: Option<bool>| {
+            button(text(content).size(14))
+                .on_press(Message::OnListSelected(on_list))
+                .padding(5)
+                .style(toggle_style(self.filters.on_list == on_list))
+        };
+        let list_and_sort = row![
+            label("List"),
+            on_list_button("Any", None),
+            on_list_button("On my list", Some(true)),
+            on_list_button("Not on my list", Some(false)),
+            text("Sort by").size(14),
+            pick_list(
+                &MediaSort::ALL[..],
+                self.filters.sort,
+                Message::SortSelected
+            ),
+            button(text("Clear Filters"))
+                .on_press(Message::ClearFilters)
+                .padding(5)
+                .style(iced::theme::Button::Secondary),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        container(
+            column![genres, tags, formats, when, ranges, list_and_sort]
+                .spacing(10)
+                .padding(10),
+        )
+        .style(iced::theme::Container::Box)
+        .width(Length::Fill)
+        .into()
+    }
+}
+
+// An empty input means no bound
+fn parse_number(label: &str, value: &str) -> Result<Option<i32>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    value
+        .parse::<i32>()
+        .ok()
+        .filter(|n| *n >= 0)
+        .map(Some)
+        .ok_or_else(|| format!("{} must be a whole number", label))
 }
@@ -0,0 +1,409 @@
+use chrono::{Datelike, Local};
+use iced::widget::{button, column, container, pick_list, row, scrollable, text};
+use iced::{Alignment, Command, Element, Length};
+
+use crate::api::client::AniListClient;
+use crate::api::models::{
+    ListEntryDetails, MediaFormat, MediaSeason, MediaSort, MediaTitle, MediaType, TitleLanguage,
+};
+use crate::data::database::ListUpdate;
+use crate::data::outbox::{Outbox, SaveOutcome};
+
+// The sort orders that make sense for a single season
+const SORTS: [MediaSort; 3] = [
+    MediaSort::POPULARITY_DESC,
+    MediaSort::SCORE_DESC,
+    MediaSort::START_DATE,
+];
+
+// A show in the seasonal chart
+#[derive(Debug, Clone)]
+pub struct SeasonalEntry {
+    pub id: i32,
+    pub title: MediaTitle,
+    pub format: String,
+    pub episodes: Option<i32>,
+    pub score: Option<i32>,
+    pub start_date: Option<String>,
+    pub list_status: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Load,
+    PreviousSeason,
+    NextSeason,
+    FormatToggled(MediaFormat),
+    SortSelected(MediaSort),
+    LoadMore,
+    ResultsReceived(u64, Result<(Vec<SeasonalEntry>, bool), String>),
+    AddToPlanning(i32),
+    AddedToPlanning(i32, Result<SaveOutcome, String>),
+    AnimeSelected(i32),
+}
+
+pub struct SeasonalScreen {
+    client: AniListClient,
+    outbox: Outbox,
+    offline_mode: bool,
+    title_language: TitleLanguage,
+    season: MediaSeason,
+    year: i32,
+    formats: Vec<MediaFormat>,
+    sort: MediaSort,
+    entries: Vec<SeasonalEntry>,
+    page: i32,
+    has_next_page: bool,
+    // Bumped on every new query so late responses for an old one are dropped
+    generation: u64,
+    is_loading: bool,
+    error: Option<String>,
+}
+
+impl SeasonalScreen {
+    pub fn new(client: AniListClient, outbox: Outbox) -> Self {
+        let today = Local::now();
+
+        Self {
+            client,
+            outbox,
+            offline_mode: false,
+            title_language: TitleLanguage::default(),
+            season: MediaSeason::from_month(today.month()),
+            year: today.year(),
+            formats: Vec::new(),
+            sort: MediaSort::POPULARITY_DESC,
+            entries: Vec::new(),
+            page: 1,
+            has_next_page: false,
+            generation: 0,
+            is_loading: false,
+            error: None,
+        }
+    }
+
+    pub fn set_offline_mode(&mut self, offline_mode: bool) {
+        self.offline_mode = offline_mode;
+    }
+
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    // Load the chart the first time the screen is opened
+    pub fn init(&mut self) -> Command<Message> {
+        if self.entries.is_empty() && !self.is_loading {
+            self.update(Message::Load)
+        } else {
+            Command::none()
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Load => {
+                self.entries.clear();
+                self.page = 1;
+                self.generation += 1;
+                self.is_loading = true;
+                self.error = None;
+                self.fetch()
+            }
+            Message::PreviousSeason => {
+                (self.season, self.year) = self.season.previous(self.year);
+                self.update(Message::Load)
+            }
+            Message::NextSeason => {
+                (self.season, self.year) = self.season.next(self.year);
+                self.update(Message::Load)
+            }
+            Message::FormatToggled(format) => {
+                if let Some(index) = self.formats.iter().position(|f| *f == format) {
+                    self.formats.remove(index);
+                } else {
+                    self.formats.push(format);
+                }
+                self.update(Message::Load)
+            }
+            Message::SortSelected(sort) => {
+                self.sort = sort;
+                self.update(Message::Load)
+            }
+            Message::LoadMore => {
+                if self.is_loading || !self.has_next_page {
+                    return Command::none();
+                }
+
+                self.page += 1;
+                self.is_loading = true;
+                self.fetch()
+            }
+            Message::ResultsReceived(generation, result) => {
+                if generation != self.generation {
+                    return Command::none();
+                }
+                self.is_loading = false;
+
+                match result {
+                    Ok((entries, has_next_page)) => {
+                        self.entries.extend(entries);
+                        self.has_next_page = has_next_page;
+                    }
+                    Err(e) => {
+                        self.error = Some(format!("Failed to load seasonal chart: {}", e));
+                    }
+                }
+
+                Command::none()
+            }
+            Message::AddToPlanning(id) => {
+                let client = self.client.clone();
+                let outbox = self.outbox.clone();
+                let offline_mode = self.offline_mode;
+                let update = ListUpdate {
+                    entry_id: None,
+                    media_id: id,
+                    media_type: MediaType::ANIME,
+                    status: Some("PLANNING".to_string()),
+                    score: None,
+                    progress: None,
+                    progress_volumes: None,
+                    details: ListEntryDetails::default(),
+                };
+
+                Command::perform(
+                    async move {
+                        outbox
+                            .save(&client, update, offline_mode)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    move |result| Message::AddedToPlanning(id, result),
+                )
+            }
+            Message::AddedToPlanning(id, result) => {
+                match result {
+                    Ok(_) => {
+                        if let Some(entry) = self.entries.iter_mut().find(|e| e.id == id) {
+                            entry.list_status = Some("PLANNING".to_string());
+                        }
+                    }
+                    Err(e) => self.error = Some(format!("Failed to add to Planning: {}", e)),
+                }
+
+                Command::none()
+            }
+            Message::AnimeSelected(_) => {
+                // This will be handled by the parent component
+                Command::none()
+            }
+        }
+    }
+
+    fn fetch(&self) -> Command<Message> {
+        let client = self.client.clone();
+        let season = self.season;
+        let year = self.year;
+        let formats = self.formats.clone();
+        let sort = self.sort;
+        let page = self.page;
+        let generation = self.generation;
+
+        Command::perform(
+            async move {
+                let data = client
+                    .get_seasonal_anime(season, year, formats, sort, Some(page), Some(30))
+                    .await
+                    .map_err(|e| e.to_string())?;
+
+                let Some(page_data) = data.page else {
+                    return Ok((Vec::new(), false));
+                };
+
+                let has_next_page = page_data
+                    .page_info
+                    .as_ref()
+                    .and_then(|info| info.has_next_page)
+                    .unwrap_or(false);
+
+                let entries = page_data
+                    .media
+                    .unwrap_or_default()
+                    .into_iter()
+                    .flatten()
+                    .map(|media| SeasonalEntry {
+                        id: media.id as i32,
+                        title: media.title.map(MediaTitle::from).unwrap_or_default(),
+                        format: media
+                            .format
+                            .map_or("Unknown".to_string(), |f| format!("{:?}", f)),
+                        episodes: media.episodes.map(|e| e as i32),
+                        score: media.average_score.map(|s| s as i32),
+                        start_date: media.start_date.and_then(|date| {
+                            match (date.year, date.month, date.day) {
+                                (Some(y), Some(m), Some(d)) => {
+                                    Some(format!("{}-{:02}-{:02}", y, m, d))
+                                }
+                                (Some(y), Some(m), None) => Some(format!("{}-{:02}", y, m)),
+                                _ => None,
+                            }
+                        }),
+                        list_status: media
+                            .media_list_entry
+                            .and_then(|entry| entry.status)
+                            .map(|status| format!("{:?}", status)),
+                    })
+                    .collect();
+
+                Ok((entries, has_next_page))
+            },
+            move |result| Message::ResultsReceived(generation, result),
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        // Season navigation
+        let season_nav = row![
+            button(text("◀"))
+                .on_press(Message::PreviousSeason)
+                .padding(10),
+            text(format!("{} {}", self.season, self.year)).size(24),
+            button(text("▶")).on_press(Message::NextSeason).padding(10),
+        ]
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+        // Format filters, nothing selected means every format
+        let mut format_row = row![text("Format:").size(14)]
+            .spacing(5)
+            .align_items(Alignment::Center);
+        for format in MediaFormat::ANIME_FORMATS {
+            format_row = format_row.push(
+                button(text(format.to_string()).size(14))
+                    .on_press(Message::FormatToggled(format))
+                    .padding(5)
+                    .style(if self.formats.contains(&format) {
+                        iced::theme::Button::Primary
+                    } else {
+                        iced::theme::Button::Secondary
+                    }),
+            );
+        }
+
+        let sort_picker = row![
+            text("Sort by:").size(14),
+            pick_list(&SORTS[..], Some(self.sort), Message::SortSelected),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center);
+
+        let controls = column![
+            season_nav,
+            row![format_row, sort_picker]
+                .spacing(30)
+                .align_items(Alignment::Center),
+        ]
+        .spacing(10);
+
+        let mut results = column![].spacing(15);
+
+        if self.is_loading && self.entries.is_empty() {
+            results = results.push(text("Loading seasonal chart...").size(18));
+        } else if self.entries.is_empty() && self.error.is_none() {
+            results = results.push(text("Nothing found for this season").size(18));
+        }
+
+        // Cards in rows of 4
+        for chunk in self.entries.chunks(4) {
+            let mut row_content = row![].spacing(20);
+
+            for entry in chunk {
+                let details = column![
+                    container(text("Image"))
+                        .width(Length::Fixed(120.0))
+                        .height(Length::Fixed(180.0))
+                        .center_x()
+                        .center_y(),
+                    text(entry.title.preferred(self.title_language))
+                        .size(16)
+                        .width(Length::Fill)
+                        .horizontal_alignment(iced::alignment::Horizontal::Center),
+                    text(format!(
+                        "{}{}",
+                        entry.format,
+                        entry
+                            .episodes
+                            .map_or_else(String::new, |e| format!(" • {} eps", e))
+                    ))
+                    .size(12),
+                    text(format!(
+                        "{}{}",
+                        entry.start_date.clone().unwrap_or_default(),
+                        entry
+                            .score
+                            .map_or_else(String::new, |s| format!(" • Score: {}%", s))
+                    ))
+                    .size(12),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center);
+
+                // Quick add, or the current list status if it is already on the list
+                let list_action: Element<Message> = match &entry.list_status {
+                    Some(status) => text(format!("On list: {}", status)).size(12).into(),
+                    None => button(text("+ Planning").size(12))
+                        .on_press(Message::AddToPlanning(entry.id))
+                        .padding(5)
+                        .into(),
+                };
+
+                row_content = row_content.push(
+                    column![
+                        button(details)
+                            .on_press(Message::AnimeSelected(entry.id))
+                            .style(iced::theme::Button::Text)
+                            .width(Length::Fixed(150.0)),
+                        list_action,
+                    ]
+                    .spacing(5)
+                    .width(Length::Fixed(150.0))
+                    .align_items(Alignment::Center),
+                );
+            }
+
+            results = results.push(row_content);
+        }
+
+        if self.has_next_page {
+            results = results.push(
+                button(text(if self.is_loading {
+                    "Loading..."
+                } else {
+                    "Load More"
+                }))
+                .on_press(Message::LoadMore)
+                .padding(10)
+                .width(Length::Fill),
+            );
+        }
+
+        // Error message if any
+        let error_display: Element<Message> = if let Some(error) = &self.error {
+            text(error)
+                .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                    0.8, 0.2, 0.2,
+                )))
+                .size(16)
+                .into()
+        } else {
+            container(text("")).into()
+        };
+
+        let content = column![controls, error_display, results]
+            .spacing(20)
+            .padding(20)
+            .width(Length::Fill);
+
+        scrollable(content).height(Length::Fill).into()
+    }
+}
@@ -2,11 +2,19 @@ use iced::widget::{
     button, column, container, pick_list, row, scrollable, text, text_input, toggler,
 };
 use iced::{Command, Element, Length};
-use std::borrow::Cow;
+use log::warn;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
-use crate::data::database::Database;
-use crate::utils::config::{load_config, save_config, Config};
+use crate::api::models::TitleLanguage;
+use crate::data::backup::{self, BackupReason, Snapshot};
+use crate::data::database::{Database, PendingListUpdate};
+use crate::data::export::{default_path, export_anime_list, ExportFormat};
+use crate::data::outbox::Outbox;
+use crate::utils::config::{load_config, save_config, BackupConfig, Config};
+
+// How many snapshots of each kind to keep
+const KEEP_CHOICES: [usize; 5] = [1, 3, 5, 10, 20];
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Theme {
@@ -25,6 +33,19 @@ impl std::fmt::Display for Theme {
     }
 }
 
+// A title language, or None to follow the AniList account setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TitleChoice(Option<TitleLanguage>);
+
+impl std::fmt::Display for TitleChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(language) => write!(f, "{}", language),
+            None => write!(f, "Use AniList setting"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
     ThemeSelected(Theme),
@@ -33,34 +54,65 @@ pub enum Message {
     RedirectUriChanged(String),
     OfflineModeToggled(bool),
     LanguageChanged(String),
+    TitleLanguageSelected(TitleChoice),
     SaveConfig,
     ConfigSaved(Result<(), String>),
     ClearCache,
     ClearCacheCompleted(Result<(), String>),
     ClearAuth,
     ClearAuthCompleted(Result<(), String>),
+    ExportList(ExportFormat),
+    ListExported(Result<String, String>),
+    // Handled by the app, which opens the import screen
+    OpenImport,
+    BackupsToggled(bool),
+    BackupAuthToggled(bool),
+    BackupKeepSelected(usize),
+    BackUpNow,
+    BackedUp(BackupReason, Result<Option<Snapshot>, String>),
+    SnapshotSelected(Snapshot),
+    RestoreSnapshot,
+    CancelRestore,
+    ConfirmRestore,
+    Restored(Result<String, String>),
+    // Offline edits AniList rejected; retrying is also handled by the app,
+    // which replays the outbox
+    RetryEdit(i64),
+    DiscardEdit(i64),
     Error(String),
 }
 
 pub struct SettingsScreen {
     db: Arc<Mutex<Database>>,
+    outbox: Outbox,
     config: Config,
     client_id: String,
     client_secret: String,
     redirect_uri: String,
     offline_mode: bool,
     language: String,
+    title_language: Option<TitleLanguage>,
     theme: Theme,
     is_saving: bool,
     is_clearing_cache: bool,
     is_clearing_auth: bool,
+    is_exporting: bool,
+    // Unsaved backup settings
+    backup: BackupConfig,
+    backup_dir: Option<PathBuf>,
+    snapshots: Vec<Snapshot>,
+    selected_snapshot: Option<Snapshot>,
+    confirming_restore: bool,
+    is_backing_up: bool,
+    is_restoring: bool,
+    failed_edits: Vec<PendingListUpdate>,
     error: Option<String>,
     success_message: Option<String>,
     available_languages: Vec<String>,
 }
 
 impl SettingsScreen {
-    pub fn new(db: Arc<Mutex<Database>>) -> Self {
+    pub fn new(db: Arc<Mutex<Database>>, outbox: Outbox) -> Self {
         // Load current config
         let config = load_config().unwrap_or_else(|_| {
             println!("Failed to load config, using defaults");
@@ -83,18 +135,37 @@ impl SettingsScreen {
             "es".to_string(),
         ];
 
+        let backup_dir = Database::backup_dir().ok();
+        let snapshots = backup_dir
+            .as_deref()
+            .and_then(|dir| backup::list(dir).ok())
+            .unwrap_or_default();
+
+        let failed_edits = outbox.failed().unwrap_or_default();
+
         Self {
             db,
+            outbox,
             config: config.clone(),
             client_id: config.auth_config.client_id.clone(),
             client_secret: config.auth_config.client_secret.clone(),
             redirect_uri: config.auth_config.redirect_uri.clone(),
             offline_mode: config.offline_mode,
             language: config.language.clone(),
+            title_language: config.title_language,
             theme,
             is_saving: false,
             is_clearing_cache: false,
             is_clearing_auth: false,
+            is_exporting: false,
+            backup: config.backup.clone(),
+            backup_dir,
+            snapshots,
+            selected_snapshot: None,
+            confirming_restore: false,
+            is_backing_up: false,
+            is_restoring: false,
+            failed_edits,
             error: None,
             success_message: None,
             available_languages,
@@ -127,18 +198,24 @@ impl SettingsScreen {
                 self.language = language;
                 Command::none()
             }
+            Message::TitleLanguageSelected(TitleChoice(title_language)) => {
+                self.title_language = title_language;
+                Command::none()
+            }
             Message::SaveConfig => {
                 self.is_saving = true;
                 self.error = None;
                 self.success_message = None;
 
-                // Create updated config
-                let mut updated_config = self.config.clone();
+                // Start from the file, other screens keep their settings in it too
+                let mut updated_config = load_config().unwrap_or_else(|_| self.config.clone());
                 updated_config.auth_config.client_id = self.client_id.clone();
                 updated_config.auth_config.client_secret = self.client_secret.clone();
                 updated_config.auth_config.redirect_uri = self.redirect_uri.clone();
                 updated_config.offline_mode = self.offline_mode;
                 updated_config.language = self.language.clone();
+                updated_config.title_language = self.title_language;
+                updated_config.backup = self.backup.clone();
                 updated_config.theme = match self.theme {
                     Theme::Light => "light".to_string(),
                     Theme::Dark => "dark".to_string(),
@@ -168,6 +245,8 @@ impl SettingsScreen {
                         self.config.auth_config.redirect_uri = self.redirect_uri.clone();
                         self.config.offline_mode = self.offline_mode;
                         self.config.language = self.language.clone();
+                        self.config.title_language = self.title_language;
+                        self.config.backup = self.backup.clone();
                         self.config.theme = match self.theme {
                             Theme::Light => "light".to_string(),
                             Theme::Dark => "dark".to_string(),
@@ -256,6 +335,162 @@ impl SettingsScreen {
 
                 Command::none()
             }
+            Message::ExportList(format) => {
+                self.is_exporting = true;
+                self.error = None;
+                self.success_message = None;
+
+                let db = self.db.clone();
+
+                Command::perform(
+                    async move {
+                        let db_guard = db
+                            .lock()
+                            .map_err(|_| "Failed to access database".to_string())?;
+                        let user_id = db_guard
+                            .get_user_id()
+                            .map_err(|_| "Log in and sync your list to export it".to_string())?;
+                        let path = default_path(format).map_err(|e| e.to_string())?;
+
+                        let report = export_anime_list(&db_guard, user_id, format, &path)
+                            .map_err(|e| format!("Failed to export list: {}", e))?;
+                        Ok(if report.skipped > 0 {
+                            format!(
+                                "Exported {} entries to {} ({} without a MyAnimeList id were left out)",
+                                report.exported,
+                                report.path.display(),
+                                report.skipped
+                            )
+                        } else {
+                            format!(
+                                "Exported {} entries to {}",
+                                report.exported,
+                                report.path.display()
+                            )
+                        })
+                    },
+                    Message::ListExported,
+                )
+            }
+            Message::ListExported(result) => {
+                self.is_exporting = false;
+
+                match result {
+                    Ok(message) => self.success_message = Some(message),
+                    Err(e) => self.error = Some(e),
+                }
+
+                Command::none()
+            }
+            Message::OpenImport => Command::none(),
+            Message::BackupsToggled(enabled) => {
+                self.backup.enabled = enabled;
+                Command::none()
+            }
+            Message::BackupAuthToggled(include_auth) => {
+                self.backup.include_auth = include_auth;
+                Command::none()
+            }
+            Message::BackupKeepSelected(keep) => {
+                self.backup.keep = keep;
+                Command::none()
+            }
+            Message::BackUpNow => {
+                self.error = None;
+                self.success_message = None;
+                self.back_up(BackupReason::Manual, self.backup.clone())
+            }
+            Message::BackedUp(reason, result) => {
+                self.is_backing_up = false;
+                self.refresh_snapshots();
+
+                match result {
+                    Ok(Some(snapshot)) if reason == BackupReason::Manual => {
+                        self.success_message =
+                            Some(format!("Backed up to {}", snapshot.path.display()));
+                    }
+                    Ok(_) => {}
+                    Err(e) if reason == BackupReason::Manual => self.error = Some(e),
+                    // Retried on the next tick
+                    Err(e) => {
+                        warn!("Scheduled backup failed: {}", e);
+                        self.error = Some(format!("Scheduled backup failed: {}", e));
+                    }
+                }
+                Command::none()
+            }
+            Message::SnapshotSelected(snapshot) => {
+                self.selected_snapshot = Some(snapshot);
+                self.confirming_restore = false;
+                Command::none()
+            }
+            Message::RestoreSnapshot => {
+                self.confirming_restore = self.selected_snapshot.is_some();
+                Command::none()
+            }
+            Message::CancelRestore => {
+                self.confirming_restore = false;
+                Command::none()
+            }
+            Message::ConfirmRestore => {
+                let (Some(snapshot), Some(dir)) =
+                    (self.selected_snapshot.clone(), self.backup_dir.clone())
+                else {
+                    return Command::none();
+                };
+                self.confirming_restore = false;
+                self.is_restoring = true;
+                self.error = None;
+                self.success_message = None;
+
+                let db = self.db.clone();
+                let settings = self.config.backup.clone();
+
+                Command::perform(
+                    async move {
+                        let mut db_guard = db
+                            .lock()
+                            .map_err(|_| "Failed to access database".to_string())?;
+                        // So the restore can be undone
+                        db_guard
+                            .backup(&dir, BackupReason::PreRestore, settings.include_auth)
+                            .map_err(|e| format!("Failed to back up before restoring: {}", e))?;
+                        db_guard
+                            .restore(&snapshot.path)
+                            .map_err(|e| format!("Failed to restore backup: {}", e))?;
+                        // After restoring, the snapshot may be one of the old ones
+                        backup::rotate(&dir, BackupReason::PreRestore, settings.keep)
+                            .map_err(|e| format!("Failed to remove old backups: {}", e))?;
+                        Ok(format!("Restored the backup from {}", snapshot))
+                    },
+                    Message::Restored,
+                )
+            }
+            Message::Restored(result) => {
+                self.is_restoring = false;
+                self.selected_snapshot = None;
+                self.refresh_snapshots();
+
+                match result {
+                    Ok(message) => self.success_message = Some(message),
+                    Err(e) => self.error = Some(e),
+                }
+                Command::none()
+            }
+            Message::RetryEdit(id) => {
+                if let Err(e) = self.outbox.retry(id) {
+                    self.error = Some(format!("Failed to retry change: {}", e));
+                }
+                self.refresh_failed_edits();
+                Command::none()
+            }
+            Message::DiscardEdit(id) => {
+                if let Err(e) = self.outbox.discard(id) {
+                    self.error = Some(format!("Failed to discard change: {}", e));
+                }
+                self.refresh_failed_edits();
+                Command::none()
+            }
             Message::Error(e) => {
                 self.error = Some(e);
                 self.success_message = None;
@@ -264,7 +499,79 @@ impl SettingsScreen {
         }
     }
 
-    pub fn view(&self) -> Element<Message> {
+    /// Re-read the edits AniList rejected, after the outbox was replayed
+    pub fn refresh_failed_edits(&mut self) {
+        self.failed_edits = self.outbox.failed().unwrap_or_default();
+    }
+
+    /// Back up the database if the saved settings ask for it and the newest
+    /// snapshot is old enough
+    pub fn scheduled_backup(&mut self) -> Command<Message> {
+        if !self.config.backup.enabled {
+            return Command::none();
+        }
+        self.back_up(BackupReason::Scheduled, self.config.backup.clone())
+    }
+
+    fn back_up(&mut self, reason: BackupReason, settings: BackupConfig) -> Command<Message> {
+        let Some(dir) = self.backup_dir.clone() else {
+            return Command::none();
+        };
+        if self.is_backing_up || self.is_restoring {
+            return Command::none();
+        }
+        self.is_backing_up = true;
+
+        let db = self.db.clone();
+
+        Command::perform(
+            async move {
+                let interval = chrono::Duration::hours(settings.interval_hours.into());
+                if reason == BackupReason::Scheduled
+                    && !backup::is_due(&dir, interval).map_err(|e| e.to_string())?
+                {
+                    return Ok(None);
+                }
+
+                let db_guard = db
+                    .lock()
+                    .map_err(|_| "Failed to access database".to_string())?;
+                let snapshot = db_guard
+                    .backup(&dir, reason, settings.include_auth)
+                    .map_err(|e| format!("Failed to back up: {}", e))?;
+                backup::rotate(&dir, reason, settings.keep)
+                    .map_err(|e| format!("Failed to remove old backups: {}", e))?;
+                Ok(Some(snapshot))
+            },
+            move |result| Message::BackedUp(reason, result),
+        )
+    }
+
+    fn refresh_snapshots(&mut self) {
+        self.snapshots = self
+            .backup_dir
+            .as_deref()
+            .and_then(|dir| backup::list(dir).ok())
+            .unwrap_or_default();
+        // The chosen snapshot may have been rotated away
+        if let Some(selected) = &self.selected_snapshot {
+            if !self.snapshots.contains(selected) {
+                self.selected_snapshot = None;
+            }
+        }
+    }
+
+    // The saved setting, not the unsaved toggle state
+    pub fn offline_mode(&self) -> bool {
+        self.config.offline_mode
+    }
+
+    // The saved override, None to use the viewer's AniList setting
+    pub fn title_language(&self) -> Option<TitleLanguage> {
+        self.config.title_language
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
         let mut content = column![].spacing(20).padding(30);
 
         // Page title
@@ -354,6 +661,23 @@ impl SettingsScreen {
             .spacing(10),
         );
 
+        // Title language
+        let title_choices: Vec<TitleChoice> = std::iter::once(TitleChoice(None))
+            .chain(TitleLanguage::ALL.into_iter().map(|l| TitleChoice(Some(l))))
+            .collect();
+        content = content.push(
+            column![
+                text("Title Language").size(18),
+                pick_list(
+                    title_choices,
+                    Some(TitleChoice(self.title_language)),
+                    Message::TitleLanguageSelected
+                )
+                .width(Length::Fixed(200.0))
+            ]
+            .spacing(10),
+        );
+
         // Offline mode
         content = content.push(
             column![
@@ -421,6 +745,162 @@ impl SettingsScreen {
             .spacing(10),
         );
 
+        // Export the synced list
+        let mut export = row![].spacing(10);
+        for format in ExportFormat::ALL {
+            let button = button(text(format!("Export as {}", format))).padding(10);
+            export = export.push(if self.is_exporting {
+                button
+            } else {
+                button.on_press(Message::ExportList(format))
+            });
+        }
+        content = content.push(
+            column![
+                text("Export").size(18),
+                text("Writes your synced anime list to your downloads folder").size(14),
+                export,
+                text("Import").size(18),
+                text("Adds entries from a MyAnimeList XML or CSV export to your anime list")
+                    .size(14),
+                button(text("Import a list..."))
+                    .on_press(Message::OpenImport)
+                    .padding(10),
+            ]
+            .spacing(10),
+        );
+
+        // Backups
+        let mut backups = column![
+            text("Backups").size(18),
+            text(match &self.backup_dir {
+                Some(dir) => format!("Snapshots of the local database, kept in {}", dir.display()),
+                None => "Could not find the backup folder".to_string(),
+            })
+            .size(14),
+            toggler(
+                format!("Back up every {} hours", self.backup.interval_hours),
+                self.backup.enabled,
+                Message::BackupsToggled
+            )
+            .width(Length::Fixed(300.0)),
+            toggler(
+                String::from("Include login tokens"),
+                self.backup.include_auth,
+                Message::BackupAuthToggled
+            )
+            .width(Length::Fixed(300.0)),
+            row![
+                text("Snapshots to keep of each kind").size(14),
+                pick_list(
+                    &KEEP_CHOICES[..],
+                    Some(self.backup.keep),
+                    Message::BackupKeepSelected
+                ),
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center),
+        ]
+        .spacing(10);
+
+        let back_up = button(text(if self.is_backing_up {
+            "Backing up..."
+        } else {
+            "Back Up Now"
+        }))
+        .padding(10);
+        let restore = button(text(if self.is_restoring {
+            "Restoring..."
+        } else {
+            "Restore"
+        }))
+        .padding(10);
+        let busy = self.is_backing_up || self.is_restoring;
+        backups = backups.push(
+            row![
+                if busy {
+                    back_up
+                } else {
+                    back_up.on_press(Message::BackUpNow)
+                },
+                pick_list(
+                    self.snapshots.clone(),
+                    self.selected_snapshot.clone(),
+                    Message::SnapshotSelected
+                )
+                .placeholder(if self.snapshots.is_empty() {
+                    "No backups yet"
+                } else {
+                    "Choose a backup"
+                })
+                .width(Length::Fixed(320.0)),
+                if busy || self.selected_snapshot.is_none() {
+                    restore
+                } else {
+                    restore.on_press(Message::RestoreSnapshot)
+                },
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center),
+        );
+
+        if let (true, Some(snapshot)) = (self.confirming_restore, &self.selected_snapshot) {
+            backups = backups.push(
+                row![
+                    text(format!(
+                        "Replace your local data with the backup from {}? A backup of the current data is taken first.",
+                        snapshot
+                    ))
+                    .size(14)
+                    .width(Length::Fill),
+                    button(text("Restore"))
+                        .on_press(Message::ConfirmRestore)
+                        .padding(10)
+                        .style(iced::theme::Button::Destructive),
+                    button(text("Cancel"))
+                        .on_press(Message::CancelRestore)
+                        .padding(10),
+                ]
+                .spacing(10)
+                .align_items(iced::Alignment::Center),
+            );
+        }
+        content = content.push(backups);
+
+        // Offline edits AniList keeps rejecting
+        if !self.failed_edits.is_empty() {
+            let mut failed = column![
+                text("Unsent Changes").size(18),
+                text("AniList rejected these offline changes, so they are no longer sent").size(14),
+            ]
+            .spacing(10);
+            for pending in &self.failed_edits {
+                let update = &pending.update;
+                failed = failed.push(
+                    row![
+                        text(format!(
+                            "{:?} {}: {}",
+                            update.media_type,
+                            update.media_id,
+                            pending.last_error.as_deref().unwrap_or("Rejected")
+                        ))
+                        .size(14)
+                        .width(Length::Fill),
+                        button(text("Retry"))
+                            .on_press(Message::RetryEdit(pending.id))
+                            .padding(10),
+                        button(text("Discard"))
+                            .on_press(Message::DiscardEdit(pending.id))
+                            .padding(10)
+                            .style(iced::theme::Button::Destructive),
+                    ]
+                    .spacing(10)
+                    .align_items(iced::Alignment::Center),
+                );
+            }
+            content = content.push(failed);
+        }
+
         // About section
         content = content.push(
             column![
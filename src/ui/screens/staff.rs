@@ -0,0 +1,412 @@
+use iced::widget::{button, column, container, row, scrollable, text};
+use iced::{Command, Element, Length};
+
+use crate::api::client::{staff_details, AniListClient};
+use crate::api::models::{FuzzyDate, MediaTitle, MediaType, TitleLanguage};
+use crate::ui::components::media_credits::{
+    merge_credits, MediaCredit, MediaCredits, Message as CreditsMessage,
+};
+use crate::utils::text::{long_date, plain_description};
+
+const PER_PAGE: i32 = 25;
+
+#[derive(Debug, Clone)]
+pub struct StaffProfile {
+    pub id: i32,
+    pub name: String,
+    pub native_name: Option<String>,
+    pub alternative_names: Vec<String>,
+    pub image_url: String,
+    pub description: String,
+    pub language: Option<String>,
+    pub occupations: Vec<String>,
+    pub gender: Option<String>,
+    pub age: Option<i32>,
+    // (start, end), no end while still active
+    pub years_active: Option<(i32, Option<i32>)>,
+    pub home_town: Option<String>,
+    pub birthday: FuzzyDate,
+    pub favourites: i32,
+}
+
+// One page of voice and production roles; both are paged together
+#[derive(Debug, Clone)]
+pub struct RolePage {
+    pub voice_roles: Vec<MediaCredit>,
+    pub production_roles: Vec<MediaCredit>,
+    pub has_next_page: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Loaded(i32, i32, Box<Result<(StaffProfile, RolePage), String>>),
+    LoadMore,
+    Credits(CreditsMessage),
+    CharacterSelected(i32),
+    MediaSelected(i32, MediaType),
+}
+
+pub struct StaffScreen {
+    client: AniListClient,
+    title_language: TitleLanguage,
+    staff_id: Option<i32>,
+    staff: Option<StaffProfile>,
+    voice_roles: Vec<MediaCredit>,
+    production_roles: Vec<MediaCredit>,
+    page: i32,
+    has_next_page: bool,
+    is_loading: bool,
+    error: Option<String>,
+}
+
+impl StaffScreen {
+    pub fn new(client: AniListClient) -> Self {
+        Self {
+            client,
+            title_language: TitleLanguage::default(),
+            staff_id: None,
+            staff: None,
+            voice_roles: Vec::new(),
+            production_roles: Vec::new(),
+            page: 1,
+            has_next_page: false,
+            is_loading: false,
+            error: None,
+        }
+    }
+
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    pub fn staff_id(&self) -> Option<i32> {
+        self.staff_id
+    }
+
+    pub fn load(&mut self, staff_id: i32) -> Command<Message> {
+        self.staff_id = Some(staff_id);
+        self.staff = None;
+        self.voice_roles.clear();
+        self.production_roles.clear();
+        self.page = 1;
+        self.has_next_page = false;
+        self.error = None;
+        self.fetch_page()
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Loaded(staff_id, page, result) => {
+                // A response for someone we have since navigated away from
+                if self.staff_id != Some(staff_id) {
+                    return Command::none();
+                }
+                self.is_loading = false;
+
+                match *result {
+                    Ok((staff, roles)) => {
+                        self.page = page;
+                        self.has_next_page = roles.has_next_page;
+                        merge_credits(&mut self.voice_roles, roles.voice_roles);
+                        merge_credits(&mut self.production_roles, roles.production_roles);
+                        self.staff = Some(staff);
+                    }
+                    Err(e) => self.error = Some(format!("Failed to load staff: {}", e)),
+                }
+
+                Command::none()
+            }
+            Message::LoadMore => {
+                if self.is_loading || !self.has_next_page {
+                    return Command::none();
+                }
+                self.page += 1;
+                self.fetch_page()
+            }
+            // Propagate card clicks up to the parent
+            Message::Credits(CreditsMessage::MediaSelected(id, media_type)) => {
+                Command::perform(async move { (id, media_type) }, |(id, media_type)| {
+                    Message::MediaSelected(id, media_type)
+                })
+            }
+            Message::Credits(CreditsMessage::CharacterSelected(id)) => {
+                Command::perform(async move { id }, Message::CharacterSelected)
+            }
+            Message::CharacterSelected(_) | Message::MediaSelected(_, _) => {
+                // Handled by the parent component
+                Command::none()
+            }
+        }
+    }
+
+    fn fetch_page(&mut self) -> Command<Message> {
+        let Some(staff_id) = self.staff_id else {
+            return Command::none();
+        };
+        self.is_loading = true;
+
+        let client = self.client.clone();
+        let page = self.page;
+
+        Command::perform(
+            async move {
+                let staff = client
+                    .get_staff(staff_id, Some(page), Some(PER_PAGE))
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .staff
+                    .ok_or_else(|| "Staff member not found".to_string())?;
+
+                Ok(convert_staff(staff))
+            },
+            move |result| Message::Loaded(staff_id, page, Box::new(result)),
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let Some(staff) = &self.staff else {
+            let status = match &self.error {
+                Some(error) => {
+                    text(error)
+                        .size(18)
+                        .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                            0.8, 0.2, 0.2,
+                        )))
+                }
+                None => text("Loading staff...").size(18),
+            };
+            return container(status).padding(40).into();
+        };
+
+        let mut facts = column![].spacing(5);
+        if let Some(native) = &staff.native_name {
+            facts = facts.push(text(native).size(18));
+        }
+        if !staff.alternative_names.is_empty() {
+            facts = facts.push(
+                text(format!(
+                    "Also known as: {}",
+                    staff.alternative_names.join(", ")
+                ))
+                .size(14),
+            );
+        }
+        if !staff.occupations.is_empty() {
+            facts = facts.push(text(staff.occupations.join(", ")).size(14));
+        }
+        if let Some(language) = &staff.language {
+            facts = facts.push(text(format!("Language: {}", language)).size(14));
+        }
+        if let Some(birthday) = long_date(&staff.birthday) {
+            facts = facts.push(text(format!("Birthday: {}", birthday)).size(14));
+        }
+        if let Some(age) = staff.age {
+            facts = facts.push(text(format!("Age: {}", age)).size(14));
+        }
+        if let Some(gender) = &staff.gender {
+            facts = facts.push(text(format!("Gender: {}", gender)).size(14));
+        }
+        if let Some((start, end)) = staff.years_active {
+            let end = end.map_or("Present".to_string(), |e| e.to_string());
+            facts = facts.push(text(format!("Years active: {}-{}", start, end)).size(14));
+        }
+        if let Some(home_town) = &staff.home_town {
+            facts = facts.push(text(format!("Hometown: {}", home_town)).size(14));
+        }
+        facts = facts.push(text(format!("❤ {} favourites", staff.favourites)).size(14));
+
+        let mut content = column![
+            text(&staff.name).size(30),
+            row![
+                container(text("Image"))
+                    .width(Length::Fixed(150.0))
+                    .height(Length::Fixed(225.0))
+                    .center_x()
+                    .center_y()
+                    .style(iced::theme::Container::Box),
+                facts,
+            ]
+            .spacing(20),
+        ]
+        .spacing(20)
+        .padding(20);
+
+        if !staff.description.is_empty() {
+            content = content.push(
+                column![
+                    text("Description").size(20),
+                    text(&staff.description).size(14)
+                ]
+                .spacing(10),
+            );
+        }
+
+        for (heading, credits) in [
+            ("Voice Roles", &self.voice_roles),
+            ("Staff Roles", &self.production_roles),
+        ] {
+            if credits.is_empty() {
+                continue;
+            }
+            content = content.push(
+                column![
+                    text(heading).size(20),
+                    MediaCredits::new(credits)
+                        .title_language(self.title_language)
+                        .view()
+                        .map(Message::Credits),
+                ]
+                .spacing(10),
+            );
+        }
+
+        if self.has_next_page {
+            content = content.push(
+                button(text(if self.is_loading {
+                    "Loading..."
+                } else {
+                    "Load more"
+                }))
+                .on_press(Message::LoadMore)
+                .padding(10),
+            );
+        }
+
+        if let Some(error) = &self.error {
+            content = content.push(text(error).size(16).style(iced::theme::Text::Color(
+                iced::Color::from_rgb(0.8, 0.2, 0.2),
+            )));
+        }
+
+        scrollable(content).height(Length::Fill).into()
+    }
+}
+
+fn media_type(media_type: Option<staff_details::MediaType>) -> MediaType {
+    match media_type {
+        Some(staff_details::MediaType::MANGA) => MediaType::MANGA,
+        _ => MediaType::ANIME,
+    }
+}
+
+fn list_status(status: Option<staff_details::MediaListStatus>) -> Option<String> {
+    status.map(|status| format!("{:?}", status))
+}
+
+fn convert_staff(staff: staff_details::StaffDetailsStaff) -> (StaffProfile, RolePage) {
+    let name = staff.name.as_ref();
+    let years_active: Vec<i32> = staff
+        .years_active
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .map(|year| year as i32)
+        .collect();
+
+    let profile = StaffProfile {
+        id: staff.id as i32,
+        name: name
+            .and_then(|n| n.full.clone())
+            .unwrap_or_else(|| "Unknown".to_string()),
+        native_name: name.and_then(|n| n.native.clone()),
+        alternative_names: name
+            .and_then(|n| n.alternative.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .filter(|alt| !alt.is_empty())
+            .collect(),
+        image_url: staff
+            .image
+            .and_then(|img| img.large.or(img.medium))
+            .unwrap_or_default(),
+        description: staff
+            .description
+            .as_deref()
+            .map(plain_description)
+            .unwrap_or_default(),
+        language: staff.language_v2,
+        occupations: staff
+            .primary_occupations
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .collect(),
+        gender: staff.gender,
+        age: staff.age.map(|a| a as i32),
+        years_active: years_active
+            .first()
+            .map(|start| (*start, years_active.get(1).copied())),
+        home_town: staff.home_town,
+        birthday: staff
+            .date_of_birth
+            .map(|date| FuzzyDate {
+                year: date.year.map(|y| y as i32),
+                month: date.month.map(|m| m as i32),
+                day: date.day.map(|d| d as i32),
+            })
+            .unwrap_or_default(),
+        favourites: staff.favourites.unwrap_or(0) as i32,
+    };
+
+    let has_next = |info: Option<bool>| info.unwrap_or(false);
+    let mut has_next_page = false;
+
+    let mut voice_roles = Vec::new();
+    if let Some(connection) = staff.character_media {
+        has_next_page |= has_next(connection.page_info.and_then(|i| i.has_next_page));
+        for edge in connection.edges.unwrap_or_default().into_iter().flatten() {
+            let Some(media) = edge.node else {
+                continue;
+            };
+            voice_roles.push(MediaCredit {
+                id: media.id as i32,
+                media_type: media_type(media.type_),
+                title: media.title.map(MediaTitle::from).unwrap_or_default(),
+                format: media.format.map(|f| format!("{:?}", f)),
+                year: media.start_date.and_then(|d| d.year).map(|y| y as i32),
+                role: edge.character_role.map(|r| format!("{:?}", r)),
+                characters: edge
+                    .characters
+                    .unwrap_or_default()
+                    .into_iter()
+                    .flatten()
+                    .map(|c| {
+                        let name = c.name.and_then(|n| n.full).unwrap_or_default();
+                        (c.id as i32, name)
+                    })
+                    .collect(),
+                list_status: list_status(media.media_list_entry.and_then(|e| e.status)),
+            });
+        }
+    }
+
+    let mut production_roles = Vec::new();
+    if let Some(connection) = staff.staff_media {
+        has_next_page |= has_next(connection.page_info.and_then(|i| i.has_next_page));
+        for edge in connection.edges.unwrap_or_default().into_iter().flatten() {
+            let Some(media) = edge.node else {
+                continue;
+            };
+            production_roles.push(MediaCredit {
+                id: media.id as i32,
+                media_type: media_type(media.type_),
+                title: media.title.map(MediaTitle::from).unwrap_or_default(),
+                format: media.format.map(|f| format!("{:?}", f)),
+                year: media.start_date.and_then(|d| d.year).map(|y| y as i32),
+                role: edge.staff_role,
+                characters: Vec::new(),
+                list_status: list_status(media.media_list_entry.and_then(|e| e.status)),
+            });
+        }
+    }
+
+    (
+        profile,
+        RolePage {
+            voice_roles,
+            production_roles,
+            has_next_page,
+        },
+    )
+}
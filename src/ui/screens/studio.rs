@@ -0,0 +1,254 @@
+use iced::widget::{button, column, container, scrollable, text};
+use iced::{Command, Element, Length};
+
+use crate::api::client::{studio_details, AniListClient};
+use crate::api::models::{MediaTitle, MediaType, TitleLanguage};
+use crate::ui::components::media_credits::{
+    merge_credits, MediaCredit, MediaCredits, Message as CreditsMessage,
+};
+
+const PER_PAGE: i32 = 25;
+
+#[derive(Debug, Clone)]
+pub struct StudioProfile {
+    pub id: i32,
+    pub name: String,
+    pub is_animation_studio: bool,
+    pub favourites: i32,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Loaded(
+        i32,
+        i32,
+        Result<(StudioProfile, Vec<MediaCredit>, bool), String>,
+    ),
+    LoadMore,
+    Credits(CreditsMessage),
+    MediaSelected(i32, MediaType),
+}
+
+pub struct StudioScreen {
+    client: AniListClient,
+    title_language: TitleLanguage,
+    studio_id: Option<i32>,
+    studio: Option<StudioProfile>,
+    // Newest first, with the viewer's list status
+    productions: Vec<MediaCredit>,
+    page: i32,
+    has_next_page: bool,
+    is_loading: bool,
+    error: Option<String>,
+}
+
+impl StudioScreen {
+    pub fn new(client: AniListClient) -> Self {
+        Self {
+            client,
+            title_language: TitleLanguage::default(),
+            studio_id: None,
+            studio: None,
+            productions: Vec::new(),
+            page: 1,
+            has_next_page: false,
+            is_loading: false,
+            error: None,
+        }
+    }
+
+    pub fn set_title_language(&mut self, title_language: TitleLanguage) {
+        self.title_language = title_language;
+    }
+
+    pub fn studio_id(&self) -> Option<i32> {
+        self.studio_id
+    }
+
+    pub fn load(&mut self, studio_id: i32) -> Command<Message> {
+        self.studio_id = Some(studio_id);
+        self.studio = None;
+        self.productions.clear();
+        self.page = 1;
+        self.has_next_page = false;
+        self.error = None;
+        self.fetch_page()
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Loaded(studio_id, page, result) => {
+                // A response for a studio we have since navigated away from
+                if self.studio_id != Some(studio_id) {
+                    return Command::none();
+                }
+                self.is_loading = false;
+
+                match result {
+                    Ok((studio, productions, has_next_page)) => {
+                        self.page = page;
+                        self.has_next_page = has_next_page;
+                        merge_credits(&mut self.productions, productions);
+                        self.studio = Some(studio);
+                    }
+                    Err(e) => self.error = Some(format!("Failed to load studio: {}", e)),
+                }
+
+                Command::none()
+            }
+            Message::LoadMore => {
+                if self.is_loading || !self.has_next_page {
+                    return Command::none();
+                }
+                self.page += 1;
+                self.fetch_page()
+            }
+            // Propagate card clicks up to the parent
+            Message::Credits(CreditsMessage::MediaSelected(id, media_type)) => {
+                Command::perform(async move { (id, media_type) }, |(id, media_type)| {
+                    Message::MediaSelected(id, media_type)
+                })
+            }
+            Message::Credits(CreditsMessage::CharacterSelected(_)) => Command::none(),
+            Message::MediaSelected(_, _) => {
+                // Handled by the parent component
+                Command::none()
+            }
+        }
+    }
+
+    fn fetch_page(&mut self) -> Command<Message> {
+        let Some(studio_id) = self.studio_id else {
+            return Command::none();
+        };
+        self.is_loading = true;
+
+        let client = self.client.clone();
+        let page = self.page;
+
+        Command::perform(
+            async move {
+                let studio = client
+                    .get_studio(studio_id, Some(page), Some(PER_PAGE))
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .studio
+                    .ok_or_else(|| "Studio not found".to_string())?;
+
+                Ok(convert_studio(studio))
+            },
+            move |result| Message::Loaded(studio_id, page, result),
+        )
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let Some(studio) = &self.studio else {
+            let status = match &self.error {
+                Some(error) => {
+                    text(error)
+                        .size(18)
+                        .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                            0.8, 0.2, 0.2,
+                        )))
+                }
+                None => text("Loading studio...").size(18),
+            };
+            return container(status).padding(40).into();
+        };
+
+        let kind = if studio.is_animation_studio {
+            "Animation studio"
+        } else {
+            "Producer"
+        };
+
+        let mut content = column![
+            text(&studio.name).size(30),
+            text(format!("{} • ❤ {} favourites", kind, studio.favourites)).size(14),
+        ]
+        .spacing(20)
+        .padding(20);
+
+        content = content.push(
+            column![
+                text("Productions").size(20),
+                if self.productions.is_empty() {
+                    let empty: Element<Message> = text("No productions listed").size(14).into();
+                    empty
+                } else {
+                    MediaCredits::new(&self.productions)
+                        .title_language(self.title_language)
+                        .view()
+                        .map(Message::Credits)
+                },
+            ]
+            .spacing(10),
+        );
+
+        if self.has_next_page {
+            content = content.push(
+                button(text(if self.is_loading {
+                    "Loading..."
+                } else {
+                    "Load more"
+                }))
+                .on_press(Message::LoadMore)
+                .padding(10),
+            );
+        }
+
+        if let Some(error) = &self.error {
+            content = content.push(text(error).size(16).style(iced::theme::Text::Color(
+                iced::Color::from_rgb(0.8, 0.2, 0.2),
+            )));
+        }
+
+        scrollable(content).height(Length::Fill).into()
+    }
+}
+
+fn convert_studio(
+    studio: studio_details::StudioDetailsStudio,
+) -> (StudioProfile, Vec<MediaCredit>, bool) {
+    let profile = StudioProfile {
+        id: studio.id as i32,
+        name: studio.name,
+        is_animation_studio: studio.is_animation_studio,
+        favourites: studio.favourites.unwrap_or(0) as i32,
+    };
+
+    let connection = studio.media;
+    let has_next_page = connection
+        .as_ref()
+        .and_then(|c| c.page_info.as_ref())
+        .and_then(|info| info.has_next_page)
+        .unwrap_or(false);
+
+    let productions = connection
+        .and_then(|c| c.edges)
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .filter_map(|edge| {
+            let media = edge.node?;
+            Some(MediaCredit {
+                id: media.id as i32,
+                media_type: match media.type_ {
+                    Some(studio_details::MediaType::MANGA) => MediaType::MANGA,
+                    _ => MediaType::ANIME,
+                },
+                title: media.title.map(MediaTitle::from).unwrap_or_default(),
+                format: media.format.map(|f| format!("{:?}", f)),
+                year: media.start_date.and_then(|d| d.year).map(|y| y as i32),
+                role: edge.is_main_studio.then(|| "Main studio".to_string()),
+                characters: Vec::new(),
+                list_status: media
+                    .media_list_entry
+                    .and_then(|entry| entry.status)
+                    .map(|status| format!("{:?}", status)),
+            })
+        })
+        .collect();
+
+    (profile, productions, has_next_page)
+}
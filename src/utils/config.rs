@@ -1,15 +1,28 @@
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::PathBuf;
 
+use crate::api::models::{ListLayout, TitleLanguage};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub auth_config: AuthConfig,
     pub theme: String,
     pub language: String,
     pub offline_mode: bool,
+    #[serde(default = "default_api_endpoint")]
+    pub api_endpoint: String,
+    // Overrides the title language from the viewer's AniList settings
+    #[serde(default)]
+    pub title_language: Option<TitleLanguage>,
+    // Layout of each list tab, keyed like `ANIME/CURRENT`
+    #[serde(default)]
+    pub list_layouts: HashMap<String, ListLayout>,
+    #[serde(default)]
+    pub backup: BackupConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +32,28 @@ pub struct AuthConfig {
     pub redirect_uri: String,
 }
 
+/// Scheduled snapshots of the local database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupConfig {
+    pub enabled: bool,
+    pub interval_hours: u32,
+    // Snapshots of each kind kept before the oldest are deleted
+    pub keep: usize,
+    // Login tokens stay out of snapshots unless this is set
+    pub include_auth: bool,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_hours: 24,
+            keep: 5,
+            include_auth: false,
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -30,10 +65,18 @@ impl Default for Config {
             theme: "default".to_string(),
             language: "en".to_string(),
             offline_mode: false,
+            api_endpoint: default_api_endpoint(),
+            title_language: None,
+            list_layouts: HashMap::new(),
+            backup: BackupConfig::default(),
         }
     }
 }
 
+fn default_api_endpoint() -> String {
+    crate::api::transport::DEFAULT_ENDPOINT.to_string()
+}
+
 pub fn get_config_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
     let proj_dirs = ProjectDirs::from("me", "camniel", "AniListClient")
         .ok_or_else(|| "Could not determine project directory".to_string())?;
@@ -20,6 +20,9 @@ pub enum AppError {
     #[error("UI Error: {0}")]
     UiError(String),
 
+    #[error("Import Error: {0}")]
+    ImportError(String),
+
     #[error("I/O Error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -44,8 +47,11 @@ impl From<reqwest::Error> for AppError {
             AppError::NetworkError(format!("Network timeout: {}", error))
         } else if error.is_connect() {
             AppError::NetworkError(format!("Connection failed: {}", error))
-        } else {
+        } else if error.is_request() {
             AppError::NetworkError(error.to_string())
+        } else {
+            // Builder, redirect and decode errors fail the same way every time
+            AppError::ApiError(error.to_string())
         }
     }
 }
@@ -81,3 +87,20 @@ impl From<anyhow::Error> for AppError {
         AppError::UnknownError(error.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_only_transient_reqwest_errors_are_network_errors() {
+        let client = reqwest::Client::new();
+
+        let invalid = client.get("not a url").send().await.unwrap_err();
+        assert!(matches!(AppError::from(invalid), AppError::ApiError(_)));
+
+        // Nothing listens on port 1
+        let refused = client.get("http://127.0.0.1:1").send().await.unwrap_err();
+        assert!(matches!(AppError::from(refused), AppError::NetworkError(_)));
+    }
+}
@@ -1,35 +1,54 @@
 use iced::widget::svg::{self, Svg};
-use once_cell::sync::Lazy;
-use std::borrow::Cow;
 
 pub struct Icons;
 
 impl Icons {
-    pub fn logo<R: svg::Renderer>() -> Svg<R> {
+    pub fn logo<R: iced_native::svg::Renderer>() -> Svg<R>
+    where
+        R::Theme: svg::StyleSheet,
+    {
         svg::Svg::new(Self::get_icon("logo.svg"))
     }
 
-    pub fn home<R: svg::Renderer>() -> Svg<R> {
+    pub fn home<R: iced_native::svg::Renderer>() -> Svg<R>
+    where
+        R::Theme: svg::StyleSheet,
+    {
         svg::Svg::new(Self::get_icon("home.svg"))
     }
 
-    pub fn search<R: svg::Renderer>() -> Svg<R> {
+    pub fn search<R: iced_native::svg::Renderer>() -> Svg<R>
+    where
+        R::Theme: svg::StyleSheet,
+    {
         svg::Svg::new(Self::get_icon("search.svg"))
     }
 
-    pub fn settings<R: svg::Renderer>() -> Svg<R> {
+    pub fn settings<R: iced_native::svg::Renderer>() -> Svg<R>
+    where
+        R::Theme: svg::StyleSheet,
+    {
         svg::Svg::new(Self::get_icon("settings.svg"))
     }
 
-    pub fn user<R: svg::Renderer>() -> Svg<R> {
+    pub fn user<R: iced_native::svg::Renderer>() -> Svg<R>
+    where
+        R::Theme: svg::StyleSheet,
+    {
         svg::Svg::new(Self::get_icon("user.svg"))
     }
 
-    pub fn play<R: svg::Renderer>() -> Svg<R> {
+    pub fn play<R: iced_native::svg::Renderer>() -> Svg<R>
+    where
+        R::Theme: svg::StyleSheet,
+    {
         svg::Svg::new(Self::get_icon("play.svg"))
     }
 
-    pub fn star<R: svg::Renderer>() -> Svg<R> {
+    pub fn star<R: iced_native::svg::Renderer>() -> Svg<R>
+    where
+        R::Theme: svg::StyleSheet,
+    {
         svg::Svg::new(Self::get_icon("star.svg"))
     }
 
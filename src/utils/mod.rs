@@ -2,6 +2,7 @@ pub mod config;
 pub mod error;
 pub mod icons;
 pub mod logging;
+pub mod text;
 pub mod ui_helpers;
 
 // Re-export important types
@@ -0,0 +1,162 @@
+use chrono::{Datelike, Local, TimeZone};
+
+use crate::api::models::FuzzyDate;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Flatten an AniList description for a plain text widget. Descriptions are
+/// markdown with some HTML mixed in; spoilers (`~!...!~`) are hidden.
+pub fn plain_description(markdown: &str) -> String {
+    let mut text = markdown
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n")
+        .replace("\r\n", "\n");
+
+    while let Some(start) = text.find("~!") {
+        let end = text[start..]
+            .find("!~")
+            .map_or(text.len(), |offset| start + offset + 2);
+        text.replace_range(start..end, "[spoiler]");
+    }
+
+    // [label](url) keeps only the label
+    let mut search_from = 0;
+    while let Some(offset) = text[search_from..].find("](") {
+        let middle = search_from + offset;
+        let open = text[..middle].rfind('[');
+        let close = text[middle..].find(')').map(|c| middle + c);
+        match (open, close) {
+            (Some(open), Some(close)) => {
+                let label = text[open + 1..middle].to_string();
+                text.replace_range(open..=close, &label);
+                search_from = open + label.len();
+            }
+            _ => search_from = middle + 2,
+        }
+    }
+
+    let text = text.replace("__", "").replace("**", "");
+
+    // Drop whatever HTML tags are left
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let starts_tag = c == '<' && chars.peek().is_some_and(|n| n.is_alphabetic() || *n == '/');
+        if starts_tag {
+            for c in chars.by_ref() {
+                if c == '>' {
+                    break;
+                }
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+
+    plain.trim().to_string()
+}
+
+/// "5 April 2000", "5 April" for a birthday without a year, or None when
+/// nothing is known
+pub fn long_date(date: &FuzzyDate) -> Option<String> {
+    let month = date
+        .month
+        .and_then(|m| MONTHS.get(usize::try_from(m - 1).ok()?));
+
+    match (date.day, month, date.year) {
+        (Some(day), Some(month), Some(year)) => Some(format!("{} {} {}", day, month, year)),
+        (Some(day), Some(month), None) => Some(format!("{} {}", day, month)),
+        (None, Some(month), Some(year)) => Some(format!("{} {}", month, year)),
+        (_, _, Some(year)) => Some(year.to_string()),
+        _ => None,
+    }
+}
+
+/// "in 2d 5h", "in 3h 20m", or "Aired" once the time has passed
+pub fn countdown(seconds: i64) -> String {
+    if seconds <= 0 {
+        return "Aired".to_string();
+    }
+
+    let days = seconds / 86_400;
+    let hours = (seconds % 86_400) / 3_600;
+    let minutes = (seconds % 3_600) / 60;
+
+    if days > 0 {
+        format!("in {}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("in {}h {}m", hours, minutes)
+    } else {
+        format!("in {}m", minutes.max(1))
+    }
+}
+
+/// "just now", "5 minutes ago", "3 days ago", or the date for anything
+/// older than a week
+pub fn time_ago(timestamp: i64, now: i64) -> String {
+    let seconds = now - timestamp;
+    let plural =
+        |n: i64, unit: &str| format!("{} {}{} ago", n, unit, if n == 1 { "" } else { "s" });
+
+    match seconds {
+        s if s < 60 => "just now".to_string(),
+        s if s < 3_600 => plural(s / 60, "minute"),
+        s if s < 86_400 => plural(s / 3_600, "hour"),
+        s if s < 7 * 86_400 => plural(s / 86_400, "day"),
+        _ => Local
+            .timestamp_opt(timestamp, 0)
+            .single()
+            .and_then(|time| {
+                long_date(&FuzzyDate {
+                    year: Some(time.year()),
+                    month: Some(time.month() as i32),
+                    day: Some(time.day() as i32),
+                })
+            })
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_description_hides_spoilers_and_markup() {
+        let description = "__Height:__ 170 cm<br>\nA member of the [Survey Corps](https://anilist.co/character/1).\n\n~!He dies.!~ <i>Really</i> <3";
+
+        assert_eq!(
+            plain_description(description),
+            "Height: 170 cm\n\nA member of the Survey Corps.\n\n[spoiler] Really <3"
+        );
+    }
+
+    #[test]
+    fn test_long_date_skips_unknown_parts() {
+        let date = |year, month, day| FuzzyDate { year, month, day };
+
+        assert_eq!(
+            long_date(&date(Some(2000), Some(4), Some(5))).as_deref(),
+            Some("5 April 2000")
+        );
+        assert_eq!(
+            long_date(&date(None, Some(12), Some(25))).as_deref(),
+            Some("25 December")
+        );
+        assert_eq!(long_date(&date(None, None, Some(3))), None);
+    }
+}